@@ -10,6 +10,10 @@ mod windows;
 #[cfg(target_os = "windows")]
 pub use crate::windows::{build_lib, sync_libs};
 
+mod link;
+
+pub use crate::link::{emit_static_link_libs, static_link_order};
+
 use fs_extra::dir::{copy, CopyOptions};
 use rand::distributions::Alphanumeric;
 use rand::{thread_rng, Rng};
@@ -0,0 +1,297 @@
+//! Static link order of the libyal libraries.
+//!
+//! GNU ld resolves symbols in a single pass, so a library has to be listed before every library
+//! it depends on.
+
+/// The direct dependencies of each libyal library.
+/// To support a new library, add it here with the libraries it calls into.
+const DEPENDENCIES: &[(&str, &[&str])] = &[
+    ("libcerror", &[]),
+    ("libcthreads", &["libcerror"]),
+    ("libcdata", &["libcthreads", "libcerror"]),
+    ("libclocale", &["libcerror"]),
+    ("libcnotify", &["libcerror"]),
+    ("libcsplit", &["libcerror"]),
+    ("libuna", &["libcerror"]),
+    (
+        "libcfile",
+        &["libclocale", "libcnotify", "libuna", "libcerror"],
+    ),
+    (
+        "libcpath",
+        &["libclocale", "libcsplit", "libuna", "libcerror"],
+    ),
+    (
+        "libbfio",
+        &[
+            "libcfile",
+            "libcpath",
+            "libcdata",
+            "libclocale",
+            "libcnotify",
+            "libcsplit",
+            "libcthreads",
+            "libuna",
+            "libcerror",
+        ],
+    ),
+    ("libfcache", &["libcdata", "libcthreads", "libcerror"]),
+    (
+        "libfdata",
+        &[
+            "libfcache",
+            "libcdata",
+            "libcnotify",
+            "libcthreads",
+            "libcerror",
+        ],
+    ),
+    ("libfdatetime", &["libcerror"]),
+    ("libfguid", &["libcerror"]),
+    ("libcaes", &["libcerror"]),
+    ("libfusn", &["libfdatetime", "libuna", "libcerror"]),
+    (
+        "libfwnt",
+        &[
+            "libcdata",
+            "libcnotify",
+            "libcthreads",
+            "libuna",
+            "libcerror",
+        ],
+    ),
+    (
+        "libfvalue",
+        &[
+            "libfdatetime",
+            "libfwnt",
+            "libcdata",
+            "libcnotify",
+            "libuna",
+            "libcerror",
+        ],
+    ),
+    (
+        "libfwevt",
+        &[
+            "libfdatetime",
+            "libfguid",
+            "libfvalue",
+            "libfwnt",
+            "libcdata",
+            "libcnotify",
+            "libcthreads",
+            "libuna",
+            "libcerror",
+        ],
+    ),
+    (
+        "libmapidb",
+        &["libfdatetime", "libfguid", "libfwnt", "libuna", "libcerror"],
+    ),
+    (
+        "libfsntfs",
+        &[
+            "libbfio",
+            "libfcache",
+            "libfdata",
+            "libfdatetime",
+            "libfguid",
+            "libfusn",
+            "libfwnt",
+            "libcdata",
+            "libcnotify",
+            "libcthreads",
+            "libuna",
+            "libcerror",
+        ],
+    ),
+    (
+        "libscca",
+        &[
+            "libbfio",
+            "libfcache",
+            "libfdata",
+            "libfdatetime",
+            "libfvalue",
+            "libfwnt",
+            "libcdata",
+            "libcnotify",
+            "libuna",
+            "libcerror",
+        ],
+    ),
+    (
+        "libevtx",
+        &[
+            "libbfio",
+            "libfcache",
+            "libfdata",
+            "libfdatetime",
+            "libfguid",
+            "libfvalue",
+            "libfwevt",
+            "libfwnt",
+            "libcdata",
+            "libcnotify",
+            "libuna",
+            "libcerror",
+        ],
+    ),
+    (
+        "libregf",
+        &[
+            "libbfio",
+            "libfcache",
+            "libfdata",
+            "libfdatetime",
+            "libfwnt",
+            "libcdata",
+            "libcnotify",
+            "libuna",
+            "libcerror",
+        ],
+    ),
+    (
+        "libesedb",
+        &[
+            "libbfio",
+            "libfcache",
+            "libfdata",
+            "libfdatetime",
+            "libfguid",
+            "libfvalue",
+            "libfwnt",
+            "libmapidb",
+            "libcdata",
+            "libcnotify",
+            "libuna",
+            "libcerror",
+        ],
+    ),
+    (
+        "libvhdi",
+        &[
+            "libbfio",
+            "libfcache",
+            "libfdata",
+            "libfguid",
+            "libcdata",
+            "libcnotify",
+            "libcthreads",
+            "libuna",
+            "libcerror",
+        ],
+    ),
+    (
+        "libqcow",
+        &[
+            "libbfio",
+            "libcaes",
+            "libfcache",
+            "libfdata",
+            "libcdata",
+            "libcnotify",
+            "libcthreads",
+            "libuna",
+            "libcerror",
+        ],
+    ),
+];
+
+fn dependencies(lib_name: &str) -> &'static [&'static str] {
+    DEPENDENCIES
+        .iter()
+        .find(|(name, _)| *name == lib_name)
+        .map(|(_, dependencies)| *dependencies)
+        .unwrap_or(&[])
+}
+
+fn visit(lib_name: &'static str, order: &mut Vec<&'static str>) {
+    if order.contains(&lib_name) {
+        return;
+    }
+
+    for dependency in dependencies(lib_name) {
+        visit(dependency, order);
+    }
+
+    order.push(lib_name);
+}
+
+/// Returns `lib_name` and all of its transitive dependencies, dependents before dependencies.
+pub fn static_link_order(lib_name: &str) -> Vec<&'static str> {
+    let mut order = vec![];
+
+    for dependency in dependencies(lib_name) {
+        visit(dependency, &mut order);
+    }
+
+    order.reverse();
+
+    match DEPENDENCIES.iter().find(|(name, _)| *name == lib_name) {
+        Some((name, _)) => order.insert(0, name),
+        None => panic!("{} is missing from the dependency list", lib_name),
+    }
+
+    order
+}
+
+/// Emits `cargo:rustc-link-lib` directives to statically link `lib_name` and its dependencies.
+pub fn emit_static_link_libs(lib_name: &str) {
+    for lib in static_link_order(lib_name) {
+        println!("cargo:rustc-link-lib=static={}", lib);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(order: &[&str], lib_name: &str) -> usize {
+        order.iter().position(|lib| *lib == lib_name).unwrap()
+    }
+
+    #[test]
+    fn test_dependents_come_before_dependencies() {
+        let order = static_link_order("libfsntfs");
+
+        assert_eq!(order[0], "libfsntfs");
+        assert!(position(&order, "libfsntfs") < position(&order, "libbfio"));
+        assert!(position(&order, "libbfio") < position(&order, "libcerror"));
+        assert_eq!(order.last(), Some(&"libcerror"));
+
+        for (lib, dependencies) in DEPENDENCIES {
+            if let Some(idx) = order.iter().position(|l| l == lib) {
+                for dependency in dependencies.iter() {
+                    assert!(
+                        idx < position(&order, dependency),
+                        "{} before {}",
+                        lib,
+                        dependency
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_each_library_is_listed_once() {
+        let mut order = static_link_order("libevtx");
+        let len = order.len();
+
+        order.sort();
+        order.dedup();
+
+        assert_eq!(order.len(), len);
+    }
+
+    #[test]
+    fn test_every_dependency_is_listed() {
+        for (_, dependencies) in DEPENDENCIES {
+            for dependency in dependencies.iter() {
+                assert!(DEPENDENCIES.iter().any(|(name, _)| name == dependency));
+            }
+        }
+    }
+}
@@ -64,7 +64,44 @@ macro_rules! get_sized_utf8_string {
                 Err(Error::try_from(error)?)
             } else {
                 // Discard nul terminator;
-                name.pop().expect("name_size was checked to be > 0");
+                name.pop();
+                let s = String::from_utf8(name).map_err(|e| Error::StringContainsInvalidUTF8(e))?;
+                Ok(s)
+            }
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! get_sized_utf8_string_by_index {
+    ($self: ident, $get_size: ident, $get_string: ident, $index: expr) => {{
+        let mut name_size = 0_usize;
+        let mut error = ptr::null_mut();
+
+        if unsafe { $get_size($self.as_type_ref(), $index, &mut name_size, &mut error) } != 1 {
+            return Err(Error::try_from(error)?);
+        };
+
+        if name_size == 0 {
+            Ok(String::new())
+        } else {
+            let mut name = vec![0; name_size];
+            let mut error = ptr::null_mut();
+
+            if unsafe {
+                $get_string(
+                    $self.as_type_ref(),
+                    $index,
+                    name.as_mut_ptr(),
+                    name.len(),
+                    &mut error,
+                )
+            } != 1
+            {
+                Err(Error::try_from(error)?)
+            } else {
+                // Discard nul terminator;
+                name.pop();
                 let s = String::from_utf8(name).map_err(|e| Error::StringContainsInvalidUTF8(e))?;
                 Ok(s)
             }
@@ -140,3 +177,17 @@ macro_rules! get_u64_field {
         }
     }};
 }
+
+#[macro_export]
+macro_rules! get_u32_field {
+    ($self: ident, $getter: ident) => {{
+        let mut value = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe { $getter($self.as_type_ref(), &mut value, &mut error) } != 1 {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(value)
+        }
+    }};
+}
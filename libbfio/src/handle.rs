@@ -252,11 +252,23 @@ impl Handle {
             LibbfioAccessFlags::Truncate => OpenOptions::new().create(true).open(path),
         };
 
+        Handle::from_file(f.map_err(|e| Error::FailedToOpenFile(e))?, flags)
+    }
+
+    /// Creates a handle backed by an already opened file, which is closed with the handle.
+    pub fn from_file(file: File, flags: LibbfioAccessFlags) -> Result<Handle, Error> {
+        Handle::from_io_handle(IoHandle::file(file), flags)
+    }
+
+    /// Creates a read-only handle backed by any seekable stream, e.g. a disk image container.
+    pub fn from_reader(reader: impl Read + Seek + 'static) -> Result<Handle, Error> {
+        Handle::from_io_handle(IoHandle::reader(reader), LibbfioAccessFlags::Read)
+    }
+
+    fn from_io_handle(io_handle: IoHandle, flags: LibbfioAccessFlags) -> Result<Handle, Error> {
         let mut handle = ptr::null_mut();
         let mut error = ptr::null_mut();
 
-        let io_handle = IoHandle::file(f.map_err(|e| Error::FailedToOpenFile(e))?);
-
         // Allocate the fat pointer on the heap, because passing it over ffi boundary is lossy.
         let heap_ptr = Box::into_raw(Box::new(io_handle));
 
@@ -445,7 +457,7 @@ mod tests {
     use crate::handle::{Handle, LibbfioAccessFlags};
 
     use std::fs::File;
-    use std::io::{Read, Seek, SeekFrom, Write};
+    use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 
     use tempdir::TempDir;
 
@@ -526,4 +538,16 @@ mod tests {
 
         assert_eq!(buf, &FILE_CONTENT[2..]);
     }
+
+    #[test]
+    fn test_from_reader() {
+        let mut handle = Handle::from_reader(Cursor::new(FILE_CONTENT.to_vec())).unwrap();
+        let mut buf = vec![];
+
+        handle.seek(SeekFrom::Start(5)).unwrap();
+        handle.read_to_end(&mut buf).unwrap();
+
+        assert_eq!(buf, b"data");
+        assert!(handle.write(b"Hello").is_err());
+    }
 }
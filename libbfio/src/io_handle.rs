@@ -9,11 +9,39 @@ use std::fs::File;
 use std::io::{Read, Seek, SeekFrom, Write};
 use std::os::raw::c_int;
 use std::path::Path;
-use std::slice;
+use std::{io, slice};
 
 pub trait RwSeek: Read + Write + Seek {}
 impl<T: Read + Write + Seek> RwSeek for T {}
 
+/// Adapts a source that can only be read from, by failing every write.
+struct ReadOnly<T>(T);
+
+impl<T: Read> Read for ReadOnly<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl<T: Seek> Seek for ReadOnly<T> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.0.seek(pos)
+    }
+}
+
+impl<T> Write for ReadOnly<T> {
+    fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "stream is read-only",
+        ))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 pub struct IoHandle {
     inner: Box<dyn RwSeek>,
     is_open: bool,
@@ -26,6 +54,13 @@ impl IoHandle {
             is_open: true
         }
     }
+
+    pub fn reader(inner: impl Read + Seek + 'static) -> Self {
+        IoHandle {
+            inner: Box::new(ReadOnly(inner)) as Box<dyn RwSeek>,
+            is_open: true,
+        }
+    }
 }
 
 pub const IO_ERR: i32 = LIBCERROR_ERROR_DOMAINS_LIBCERROR_ERROR_DOMAIN_IO as i32;
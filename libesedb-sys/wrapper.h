@@ -0,0 +1 @@
+#include "libesedb.h"
\ No newline at end of file
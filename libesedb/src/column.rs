@@ -0,0 +1,194 @@
+use crate::error::Error;
+use crate::ffi_error::LibesedbErrorRefMut;
+use crate::file::File;
+use libyal_rs_common::ffi::AsTypeRef;
+use std::convert::TryFrom;
+use std::fmt::{self, Debug};
+use std::os::raw::c_int;
+use std::ptr;
+
+#[repr(C)]
+pub struct __Column(isize);
+
+pub type ColumnRefMut = *mut __Column;
+pub type ColumnRef = *const __Column;
+
+#[repr(C)]
+pub struct Column<'a>(ColumnRefMut, &'a File);
+
+impl<'a> AsTypeRef for Column<'a> {
+    type Ref = ColumnRef;
+    type RefMut = ColumnRefMut;
+
+    #[inline]
+    fn as_type_ref(&self) -> Self::Ref {
+        // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
+        self.0 as *const _
+    }
+
+    #[inline]
+    fn as_type_ref_mut(&mut self) -> Self::RefMut {
+        self.0
+    }
+
+    #[inline]
+    fn as_raw(&mut self) -> *mut Self::RefMut {
+        &mut self.0 as *mut _
+    }
+}
+
+impl<'a> Column<'a> {
+    pub fn wrap_ptr(file: &'a File, ptr: ColumnRefMut) -> Self {
+        Column(ptr, file)
+    }
+}
+
+impl<'a> Drop for Column<'a> {
+    fn drop(&mut self) {
+        use log::trace;
+
+        let mut error = ptr::null_mut();
+
+        trace!("Calling `libesedb_column_free`");
+
+        unsafe {
+            libesedb_column_free(self.as_raw(), &mut error);
+        }
+
+        debug_assert!(error.is_null(), "`libesedb_column_free` failed!");
+    }
+}
+
+impl<'a> Debug for Column<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("Column")
+            .field("Name", &self.get_name().unwrap_or("".to_string()))
+            .field(
+                "Type",
+                &self
+                    .get_type()
+                    .and_then(|t| Ok(format!("{:?}", t)))
+                    .unwrap_or_else(|_| "".to_string()),
+            )
+            .finish()
+    }
+}
+
+#[derive(PartialOrd, PartialEq, Debug, Clone)]
+#[repr(C)]
+pub enum ColumnType {
+    Null = 0,
+    Boolean = 1,
+    Integer8BitUnsigned = 2,
+    Integer16BitSigned = 3,
+    Integer32BitSigned = 4,
+    Currency = 5,
+    FloatingPoint32Bit = 6,
+    DoublePrecisionFloatingPoint64Bit = 7,
+    DateTime = 8,
+    BinaryData = 9,
+    Text = 10,
+    LargeBinaryData = 11,
+    LargeText = 12,
+    SuperLarge = 13,
+    Integer32BitUnsigned = 14,
+    Integer64BitSigned = 15,
+    Guid = 16,
+    Integer16BitUnsigned = 17,
+}
+
+impl TryFrom<u32> for ColumnType {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ColumnType::Null),
+            1 => Ok(ColumnType::Boolean),
+            2 => Ok(ColumnType::Integer8BitUnsigned),
+            3 => Ok(ColumnType::Integer16BitSigned),
+            4 => Ok(ColumnType::Integer32BitSigned),
+            5 => Ok(ColumnType::Currency),
+            6 => Ok(ColumnType::FloatingPoint32Bit),
+            7 => Ok(ColumnType::DoublePrecisionFloatingPoint64Bit),
+            8 => Ok(ColumnType::DateTime),
+            9 => Ok(ColumnType::BinaryData),
+            10 => Ok(ColumnType::Text),
+            11 => Ok(ColumnType::LargeBinaryData),
+            12 => Ok(ColumnType::LargeText),
+            13 => Ok(ColumnType::SuperLarge),
+            14 => Ok(ColumnType::Integer32BitUnsigned),
+            15 => Ok(ColumnType::Integer64BitSigned),
+            16 => Ok(ColumnType::Guid),
+            17 => Ok(ColumnType::Integer16BitUnsigned),
+            _ => Err(Error::UnknownColumnTypeEnumVariant(value)),
+        }
+    }
+}
+
+extern "C" {
+    pub fn libesedb_column_free(
+        column: *mut ColumnRefMut,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_column_get_identifier(
+        column: ColumnRef,
+        identifier: *mut u32,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_column_get_type(
+        column: ColumnRef,
+        column_type: *mut u32,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_column_get_utf8_name_size(
+        column: ColumnRef,
+        utf8_string_size: *mut usize,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_column_get_utf8_name(
+        column: ColumnRef,
+        utf8_string: *mut u8,
+        utf8_string_size: usize,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+}
+
+impl<'a> Column<'a> {
+    /// Retrieves the column identifier.
+    pub fn get_identifier(&self) -> Result<u32, Error> {
+        let mut identifier = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libesedb_column_get_identifier(self.as_type_ref(), &mut identifier, &mut error)
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(identifier)
+        }
+    }
+
+    /// Retrieves the column type.
+    pub fn get_type(&self) -> Result<ColumnType, Error> {
+        let mut type_as_num = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe { libesedb_column_get_type(self.as_type_ref(), &mut type_as_num, &mut error) }
+            != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(ColumnType::try_from(type_as_num)?)
+        }
+    }
+
+    /// Retrieves the column name.
+    pub fn get_name(&self) -> Result<String, Error> {
+        get_sized_utf8_string!(
+            self,
+            libesedb_column_get_utf8_name_size,
+            libesedb_column_get_utf8_name
+        )
+    }
+}
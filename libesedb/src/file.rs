@@ -0,0 +1,341 @@
+use crate::error::Error;
+use crate::ffi_error::LibesedbErrorRefMut;
+use crate::table::{Table, TableRefMut};
+use libbfio_rs::handle::{Handle, HandleRef};
+use libesedb_sys::{
+    LIBESEDB_ACCESS_FLAGS, LIBESEDB_ACCESS_FLAGS_LIBESEDB_ACCESS_FLAG_READ,
+    LIBESEDB_ACCESS_FLAGS_LIBESEDB_ACCESS_FLAG_WRITE,
+};
+use libyal_rs_common::ffi::AsTypeRef;
+use log::error;
+use std::convert::TryFrom;
+use std::ffi::CString;
+use std::os::raw::c_int;
+use std::ptr;
+
+#[repr(C)]
+pub struct __File(isize);
+
+pub type FileRefMut = *mut __File;
+pub type FileRef = *const __File;
+
+#[repr(C)]
+pub struct File(FileRefMut);
+
+impl AsTypeRef for File {
+    type Ref = FileRef;
+    type RefMut = FileRefMut;
+
+    #[inline]
+    fn as_type_ref(&self) -> Self::Ref {
+        // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
+        self.0 as *const _
+    }
+
+    fn as_type_ref_mut(&mut self) -> Self::RefMut {
+        self.0
+    }
+
+    fn as_raw(&mut self) -> *mut Self::RefMut {
+        &mut self.0 as *mut _
+    }
+}
+
+impl File {
+    pub fn wrap_ptr(ptr: FileRefMut) -> File {
+        File(ptr)
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        let mut error = ptr::null_mut();
+
+        if unsafe { libesedb_file_close(self.as_type_ref(), &mut error) } != 0 {
+            error!("`libesedb_file_close` failed!");
+        }
+
+        let mut error = ptr::null_mut();
+        if unsafe { libesedb_file_free(self.as_raw(), &mut error) } != 1 {
+            panic!("`libesedb_file_free` failed!");
+        }
+    }
+}
+
+extern "C" {
+    pub fn libesedb_file_initialize(
+        file: *mut FileRefMut,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_file_free(file: *mut FileRefMut, error: *mut LibesedbErrorRefMut) -> c_int;
+    pub fn libesedb_file_signal_abort(file: FileRef, error: *mut LibesedbErrorRefMut) -> c_int;
+    pub fn libesedb_file_open(
+        file: FileRef,
+        filename: *const ::std::os::raw::c_char,
+        access_flags: c_int,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_file_open_file_io_handle(
+        file: FileRef,
+        file_io_handle: HandleRef,
+        access_flags: c_int,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_file_close(file: FileRef, error: *mut LibesedbErrorRefMut) -> c_int;
+    pub fn libesedb_file_get_type(
+        file: FileRef,
+        file_type: *mut u32,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_file_get_page_size(
+        file: FileRef,
+        page_size: *mut u32,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_file_get_number_of_tables(
+        file: FileRef,
+        number_of_tables: *mut c_int,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_file_get_table(
+        file: FileRef,
+        table_entry: c_int,
+        table: *mut TableRefMut,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_file_get_table_by_utf8_name(
+        file: FileRef,
+        utf8_string: *const u8,
+        utf8_string_length: usize,
+        table: *mut TableRefMut,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+}
+
+pub enum AccessMode {
+    Read,
+    Write,
+}
+
+impl AccessMode {
+    fn as_flag(&self) -> LIBESEDB_ACCESS_FLAGS {
+        match self {
+            AccessMode::Read => LIBESEDB_ACCESS_FLAGS_LIBESEDB_ACCESS_FLAG_READ,
+            AccessMode::Write => LIBESEDB_ACCESS_FLAGS_LIBESEDB_ACCESS_FLAG_WRITE,
+        }
+    }
+}
+
+impl File {
+    /// Opens a database file by filename.
+    pub fn open(filename: impl AsRef<str>, mode: AccessMode) -> Result<Self, Error> {
+        let mut handle = ptr::null_mut();
+
+        let c_string = CString::new(filename.as_ref()).map_err(Error::StringContainsNul)?;
+
+        let mut init_error = ptr::null_mut();
+
+        let retcode = unsafe { libesedb_file_initialize(&mut handle as _, &mut init_error as _) };
+
+        if retcode != 1 {
+            return Err(Error::try_from(init_error)?);
+        }
+
+        let file = File::wrap_ptr(handle);
+
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libesedb_file_open(
+                file.as_type_ref(),
+                c_string.as_ptr(),
+                mode.as_flag() as c_int,
+                &mut error as _,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(file)
+        }
+    }
+
+    /// Opens a database file using a libbfio handle.
+    pub fn open_file_object(file_handle: &Handle) -> Result<Self, Error> {
+        let mut handle = ptr::null_mut();
+        let mut init_error = ptr::null_mut();
+
+        let retcode = unsafe { libesedb_file_initialize(&mut handle as _, &mut init_error as _) };
+
+        if retcode != 1 {
+            return Err(Error::try_from(init_error)?);
+        }
+
+        let file = File::wrap_ptr(handle);
+
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libesedb_file_open_file_io_handle(
+                file.as_type_ref(),
+                file_handle.as_type_ref(),
+                AccessMode::Read.as_flag() as c_int,
+                &mut error as _,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(file)
+        }
+    }
+
+    /// Retrieves the page size.
+    pub fn get_page_size(&self) -> Result<u32, Error> {
+        let mut page_size = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe { libesedb_file_get_page_size(self.as_type_ref(), &mut page_size, &mut error) }
+            != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(page_size)
+        }
+    }
+
+    /// Retrieves the number of tables.
+    pub fn get_number_of_tables(&self) -> Result<c_int, Error> {
+        let mut number_of_tables = 0;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libesedb_file_get_number_of_tables(
+                self.as_type_ref(),
+                &mut number_of_tables,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(number_of_tables)
+        }
+    }
+
+    /// Retrieves a specific table.
+    pub fn get_table(&self, table_entry: c_int) -> Result<Table, Error> {
+        let mut table = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libesedb_file_get_table(self.as_type_ref(), table_entry, &mut table, &mut error)
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(Table::wrap_ptr(self, table))
+        }
+    }
+
+    /// Retrieves the table specified by the name.
+    /// Returns `None` if no such table exists.
+    pub fn get_table_by_name(&self, name: &str) -> Result<Option<Table>, Error> {
+        let mut table = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        match unsafe {
+            libesedb_file_get_table_by_utf8_name(
+                self.as_type_ref(),
+                name.as_ptr(),
+                name.len(),
+                &mut table,
+                &mut error,
+            )
+        } {
+            1 => Ok(Some(Table::wrap_ptr(self, table))),
+            0 => Ok(None),
+            _ => Err(Error::try_from(error)?),
+        }
+    }
+
+    /// Iterates the tables of the database.
+    pub fn iter_tables(&self) -> Result<IterTables, Error> {
+        let num_tables = self.get_number_of_tables()?;
+
+        Ok(IterTables {
+            handle: self,
+            num_tables: num_tables as u32,
+            idx: 0,
+        })
+    }
+}
+
+pub struct IterTables<'a> {
+    handle: &'a File,
+    num_tables: u32,
+    idx: u32,
+}
+
+impl<'a> Iterator for IterTables<'a> {
+    type Item = Result<Table<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.num_tables {
+            let table = self.handle.get_table(self.idx as i32);
+            self.idx += 1;
+            return Some(table);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    #[test]
+    fn test_opens_file_io_works() {
+        let handle = sample_database_io_handle().unwrap();
+        let file_from_io = File::open_file_object(&handle).unwrap();
+        let file_from_disk = sample_database().unwrap();
+
+        assert_eq!(
+            file_from_io.get_number_of_tables().unwrap(),
+            file_from_disk.get_number_of_tables().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_table_name() {
+        let file = sample_database().unwrap();
+        let table = file.get_table(0).unwrap();
+
+        // The catalog is always the first table.
+        assert_eq!(table.get_name().unwrap(), "MSysObjects");
+    }
+
+    #[test]
+    fn test_get_table_by_name() {
+        let file = sample_database().unwrap();
+
+        assert!(file.get_table_by_name("MSysObjects").unwrap().is_some());
+        assert!(file.get_table_by_name("NonExistent").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_iter_tables() {
+        let file = sample_database().unwrap();
+
+        let names: Vec<String> = file
+            .iter_tables()
+            .unwrap()
+            .map(|table| table.and_then(|t| t.get_name()).unwrap())
+            .collect();
+
+        assert_eq!(names.len(), file.get_number_of_tables().unwrap() as usize);
+        assert!(names.iter().any(|name| name == "MSysObjects"));
+    }
+}
@@ -0,0 +1,37 @@
+use crate::error::Error;
+use crate::file::{AccessMode, File};
+use env_logger;
+use lazy_static::lazy_static;
+use libbfio_rs::handle::{Handle, LibbfioAccessFlags};
+use std::path::PathBuf;
+
+lazy_static! {
+    static ref LOGGER_INIT: () = {
+        env_logger::init();
+    };
+}
+
+pub fn sample_database_path() -> String {
+    let this_file = file!();
+    let sample = PathBuf::from(this_file)
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("examples")
+        .join("SRUDB.dat");
+
+    sample.to_str().unwrap().to_string()
+}
+
+pub fn sample_database_io_handle() -> Result<Handle, Error> {
+    let database_path = sample_database_path();
+    Ok(Handle::open_file(database_path, LibbfioAccessFlags::Read).expect("libbfio failed"))
+}
+
+pub fn sample_database() -> Result<File, Error> {
+    let database_path = sample_database_path();
+    File::open(&database_path, AccessMode::Read)
+}
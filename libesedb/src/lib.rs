@@ -0,0 +1,17 @@
+#![allow(unused_imports)]
+#![allow(unused_variables)]
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate libyal_rs_common;
+
+pub mod column;
+pub mod error;
+pub mod ffi_error;
+pub mod file;
+pub mod record;
+pub mod table;
+mod utils;
+
+#[cfg(test)]
+mod fixtures;
@@ -0,0 +1,400 @@
+use crate::column::ColumnType;
+use crate::error::Error;
+use crate::ffi_error::LibesedbErrorRefMut;
+use crate::file::File;
+use crate::utils::datetime_from_filetime;
+use chrono::{DateTime, Utc};
+use libyal_rs_common::ffi::AsTypeRef;
+use std::convert::TryFrom;
+use std::fmt::{self, Debug};
+use std::os::raw::c_int;
+use std::ptr;
+
+#[repr(C)]
+pub struct __Record(isize);
+
+pub type RecordRefMut = *mut __Record;
+pub type RecordRef = *const __Record;
+
+#[repr(C)]
+pub struct Record<'a>(RecordRefMut, &'a File);
+
+impl<'a> AsTypeRef for Record<'a> {
+    type Ref = RecordRef;
+    type RefMut = RecordRefMut;
+
+    #[inline]
+    fn as_type_ref(&self) -> Self::Ref {
+        // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
+        self.0 as *const _
+    }
+
+    #[inline]
+    fn as_type_ref_mut(&mut self) -> Self::RefMut {
+        self.0
+    }
+
+    #[inline]
+    fn as_raw(&mut self) -> *mut Self::RefMut {
+        &mut self.0 as *mut _
+    }
+}
+
+impl<'a> Record<'a> {
+    pub fn wrap_ptr(file: &'a File, ptr: RecordRefMut) -> Self {
+        Record(ptr, file)
+    }
+}
+
+impl<'a> Drop for Record<'a> {
+    fn drop(&mut self) {
+        use log::trace;
+
+        let mut error = ptr::null_mut();
+
+        trace!("Calling `libesedb_record_free`");
+
+        unsafe {
+            libesedb_record_free(self.as_raw(), &mut error);
+        }
+
+        debug_assert!(error.is_null(), "`libesedb_record_free` failed!");
+    }
+}
+
+impl<'a> Debug for Record<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("Record")
+            .field(
+                "NumberOfValues",
+                &self.get_number_of_values().unwrap_or_default(),
+            )
+            .finish()
+    }
+}
+
+type GetValueSize =
+    unsafe extern "C" fn(RecordRef, c_int, *mut usize, *mut LibesedbErrorRefMut) -> c_int;
+type GetValue =
+    unsafe extern "C" fn(RecordRef, c_int, *mut u8, usize, *mut LibesedbErrorRefMut) -> c_int;
+
+extern "C" {
+    pub fn libesedb_record_free(
+        record: *mut RecordRefMut,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_record_get_number_of_values(
+        record: RecordRef,
+        number_of_values: *mut c_int,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_record_get_column_identifier(
+        record: RecordRef,
+        value_entry: c_int,
+        column_identifier: *mut u32,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_record_get_column_type(
+        record: RecordRef,
+        value_entry: c_int,
+        column_type: *mut u32,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_record_get_utf8_column_name_size(
+        record: RecordRef,
+        value_entry: c_int,
+        utf8_string_size: *mut usize,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_record_get_utf8_column_name(
+        record: RecordRef,
+        value_entry: c_int,
+        utf8_string: *mut u8,
+        utf8_string_size: usize,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_record_get_value_boolean(
+        record: RecordRef,
+        value_entry: c_int,
+        value_boolean: *mut u8,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_record_get_value_8bit(
+        record: RecordRef,
+        value_entry: c_int,
+        value_8bit: *mut u8,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_record_get_value_16bit(
+        record: RecordRef,
+        value_entry: c_int,
+        value_16bit: *mut u16,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_record_get_value_32bit(
+        record: RecordRef,
+        value_entry: c_int,
+        value_32bit: *mut u32,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_record_get_value_64bit(
+        record: RecordRef,
+        value_entry: c_int,
+        value_64bit: *mut u64,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_record_get_value_filetime(
+        record: RecordRef,
+        value_entry: c_int,
+        value_filetime: *mut u64,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_record_get_value_utf8_string_size(
+        record: RecordRef,
+        value_entry: c_int,
+        utf8_string_size: *mut usize,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_record_get_value_utf8_string(
+        record: RecordRef,
+        value_entry: c_int,
+        utf8_string: *mut u8,
+        utf8_string_size: usize,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_record_get_value_binary_data_size(
+        record: RecordRef,
+        value_entry: c_int,
+        binary_data_size: *mut usize,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_record_get_value_binary_data(
+        record: RecordRef,
+        value_entry: c_int,
+        binary_data: *mut u8,
+        binary_data_size: usize,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+}
+
+/// Retrieves a fixed-size value, which is `None` if the value is NULL.
+macro_rules! get_optional_value {
+    ($self: ident, $getter: ident, $value_entry: expr, $ty: ty) => {{
+        let mut value: $ty = Default::default();
+        let mut error = ptr::null_mut();
+
+        match unsafe { $getter($self.as_type_ref(), $value_entry, &mut value, &mut error) } {
+            1 => Ok(Some(value)),
+            0 => Ok(None),
+            _ => Err(Error::try_from(error)?),
+        }
+    }};
+}
+
+impl<'a> Record<'a> {
+    /// Retrieves the number of values, one per column.
+    pub fn get_number_of_values(&self) -> Result<c_int, Error> {
+        let mut number_of_values = 0;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libesedb_record_get_number_of_values(
+                self.as_type_ref(),
+                &mut number_of_values,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(number_of_values)
+        }
+    }
+
+    /// Retrieves the identifier of the column of a specific value.
+    pub fn get_column_identifier(&self, value_entry: c_int) -> Result<u32, Error> {
+        let mut column_identifier = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libesedb_record_get_column_identifier(
+                self.as_type_ref(),
+                value_entry,
+                &mut column_identifier,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(column_identifier)
+        }
+    }
+
+    /// Retrieves the type of the column of a specific value.
+    pub fn get_column_type(&self, value_entry: c_int) -> Result<ColumnType, Error> {
+        let mut type_as_num = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libesedb_record_get_column_type(
+                self.as_type_ref(),
+                value_entry,
+                &mut type_as_num,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(ColumnType::try_from(type_as_num)?)
+        }
+    }
+
+    /// Retrieves the name of the column of a specific value.
+    pub fn get_column_name(&self, value_entry: c_int) -> Result<String, Error> {
+        get_sized_utf8_string_by_index!(
+            self,
+            libesedb_record_get_utf8_column_name_size,
+            libesedb_record_get_utf8_column_name,
+            value_entry
+        )
+    }
+
+    /// Retrieves a boolean value.
+    /// Returns `None` if the value is NULL.
+    pub fn get_value_boolean(&self, value_entry: c_int) -> Result<Option<bool>, Error> {
+        let value: Option<u8> =
+            get_optional_value!(self, libesedb_record_get_value_boolean, value_entry, u8)?;
+
+        Ok(value.map(|value| value != 0))
+    }
+
+    /// Retrieves an 8-bit value.
+    /// Returns `None` if the value is NULL.
+    pub fn get_value_8bit(&self, value_entry: c_int) -> Result<Option<u8>, Error> {
+        get_optional_value!(self, libesedb_record_get_value_8bit, value_entry, u8)
+    }
+
+    /// Retrieves a 16-bit value.
+    /// Returns `None` if the value is NULL.
+    pub fn get_value_16bit(&self, value_entry: c_int) -> Result<Option<u16>, Error> {
+        get_optional_value!(self, libesedb_record_get_value_16bit, value_entry, u16)
+    }
+
+    /// Retrieves a 32-bit value.
+    /// Returns `None` if the value is NULL.
+    pub fn get_value_32bit(&self, value_entry: c_int) -> Result<Option<u32>, Error> {
+        get_optional_value!(self, libesedb_record_get_value_32bit, value_entry, u32)
+    }
+
+    /// Retrieves a 64-bit value.
+    /// Returns `None` if the value is NULL.
+    pub fn get_value_64bit(&self, value_entry: c_int) -> Result<Option<u64>, Error> {
+        get_optional_value!(self, libesedb_record_get_value_64bit, value_entry, u64)
+    }
+
+    /// Retrieves a date and time value stored as a FILETIME.
+    /// Returns `None` if the value is NULL.
+    pub fn get_value_filetime(&self, value_entry: c_int) -> Result<Option<DateTime<Utc>>, Error> {
+        let value: Option<u64> =
+            get_optional_value!(self, libesedb_record_get_value_filetime, value_entry, u64)?;
+
+        Ok(value.map(datetime_from_filetime))
+    }
+
+    /// Retrieves a text value.
+    /// Returns `None` if the value is NULL.
+    pub fn get_value_utf8_string(&self, value_entry: c_int) -> Result<Option<String>, Error> {
+        let data = match self.get_sized_value(
+            value_entry,
+            libesedb_record_get_value_utf8_string_size,
+            libesedb_record_get_value_utf8_string,
+        )? {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+
+        let mut string = String::from_utf8(data).map_err(Error::StringContainsInvalidUTF8)?;
+
+        // Discard nul terminator;
+        string.pop();
+
+        Ok(Some(string))
+    }
+
+    /// Retrieves a binary data value.
+    /// Returns `None` if the value is NULL.
+    pub fn get_value_binary(&self, value_entry: c_int) -> Result<Option<Vec<u8>>, Error> {
+        self.get_sized_value(
+            value_entry,
+            libesedb_record_get_value_binary_data_size,
+            libesedb_record_get_value_binary_data,
+        )
+    }
+
+    fn get_sized_value(
+        &self,
+        value_entry: c_int,
+        get_size: GetValueSize,
+        get_value: GetValue,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let mut size = 0_usize;
+        let mut error = ptr::null_mut();
+
+        match unsafe { get_size(self.as_type_ref(), value_entry, &mut size, &mut error) } {
+            1 => {}
+            0 => return Ok(None),
+            _ => return Err(Error::try_from(error)?),
+        }
+
+        let mut data = vec![0; size];
+
+        if size == 0 {
+            return Ok(Some(data));
+        }
+
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            get_value(
+                self.as_type_ref(),
+                value_entry,
+                data.as_mut_ptr(),
+                data.len(),
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(Some(data))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::*;
+
+    #[test]
+    fn test_read_catalog_values() {
+        let file = sample_database().unwrap();
+        let table = file.get_table_by_name("MSysObjects").unwrap().unwrap();
+        let record = table.get_record(0).unwrap();
+
+        let names: Vec<String> = (0..record.get_number_of_values().unwrap())
+            .map(|idx| record.get_column_name(idx).unwrap())
+            .collect();
+
+        let name_idx = names.iter().position(|name| name == "Name").unwrap() as i32;
+        let type_idx = names.iter().position(|name| name == "Type").unwrap() as i32;
+
+        // The first catalog record describes the catalog table itself.
+        assert_eq!(
+            record.get_value_utf8_string(name_idx).unwrap().unwrap(),
+            "MSysObjects"
+        );
+        assert_eq!(record.get_value_16bit(type_idx).unwrap(), Some(1));
+    }
+}
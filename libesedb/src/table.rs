@@ -0,0 +1,319 @@
+use crate::column::{Column, ColumnRefMut};
+use crate::error::Error;
+use crate::ffi_error::LibesedbErrorRefMut;
+use crate::file::File;
+use crate::record::{Record, RecordRefMut};
+use libyal_rs_common::ffi::AsTypeRef;
+use std::convert::TryFrom;
+use std::fmt::{self, Debug};
+use std::os::raw::c_int;
+use std::ptr;
+
+#[repr(C)]
+pub struct __Table(isize);
+
+pub type TableRefMut = *mut __Table;
+pub type TableRef = *const __Table;
+
+#[repr(C)]
+pub struct Table<'a>(TableRefMut, &'a File);
+
+impl<'a> AsTypeRef for Table<'a> {
+    type Ref = TableRef;
+    type RefMut = TableRefMut;
+
+    #[inline]
+    fn as_type_ref(&self) -> Self::Ref {
+        // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
+        self.0 as *const _
+    }
+
+    #[inline]
+    fn as_type_ref_mut(&mut self) -> Self::RefMut {
+        self.0
+    }
+
+    #[inline]
+    fn as_raw(&mut self) -> *mut Self::RefMut {
+        &mut self.0 as *mut _
+    }
+}
+
+impl<'a> Table<'a> {
+    pub fn wrap_ptr(file: &'a File, ptr: TableRefMut) -> Self {
+        Table(ptr, file)
+    }
+}
+
+impl<'a> Drop for Table<'a> {
+    fn drop(&mut self) {
+        use log::trace;
+
+        let mut error = ptr::null_mut();
+
+        trace!("Calling `libesedb_table_free`");
+
+        unsafe {
+            libesedb_table_free(self.as_raw(), &mut error);
+        }
+
+        debug_assert!(error.is_null(), "`libesedb_table_free` failed!");
+    }
+}
+
+impl<'a> Debug for Table<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("Table")
+            .field("Name", &self.get_name().unwrap_or("".to_string()))
+            .finish()
+    }
+}
+
+/// Passed to the column getters to include the columns of the template table, if any.
+const COLUMN_FLAGS: u8 = 0;
+
+extern "C" {
+    pub fn libesedb_table_free(table: *mut TableRefMut, error: *mut LibesedbErrorRefMut) -> c_int;
+    pub fn libesedb_table_get_identifier(
+        table: TableRef,
+        identifier: *mut u32,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_table_get_utf8_name_size(
+        table: TableRef,
+        utf8_string_size: *mut usize,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_table_get_utf8_name(
+        table: TableRef,
+        utf8_string: *mut u8,
+        utf8_string_size: usize,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_table_get_number_of_columns(
+        table: TableRef,
+        number_of_columns: *mut c_int,
+        flags: u8,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_table_get_column(
+        table: TableRef,
+        column_entry: c_int,
+        column: *mut ColumnRefMut,
+        flags: u8,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_table_get_number_of_records(
+        table: TableRef,
+        number_of_records: *mut c_int,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+    pub fn libesedb_table_get_record(
+        table: TableRef,
+        record_entry: c_int,
+        record: *mut RecordRefMut,
+        error: *mut LibesedbErrorRefMut,
+    ) -> c_int;
+}
+
+pub struct IterColumns<'a: 'b, 'b> {
+    handle: &'b Table<'a>,
+    num_columns: u32,
+    idx: u32,
+}
+
+impl<'a: 'b, 'b> Iterator for IterColumns<'a, 'b> {
+    type Item = Result<Column<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.num_columns {
+            let column = self.handle.get_column(self.idx as i32);
+            self.idx += 1;
+            return Some(column);
+        }
+
+        None
+    }
+}
+
+pub struct IterRecords<'a: 'b, 'b> {
+    handle: &'b Table<'a>,
+    num_records: u32,
+    idx: u32,
+}
+
+impl<'a: 'b, 'b> Iterator for IterRecords<'a, 'b> {
+    type Item = Result<Record<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.num_records {
+            let record = self.handle.get_record(self.idx as i32);
+            self.idx += 1;
+            return Some(record);
+        }
+
+        None
+    }
+}
+
+impl<'a> Table<'a> {
+    /// Retrieves the table identifier.
+    pub fn get_identifier(&self) -> Result<u32, Error> {
+        let mut identifier = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe { libesedb_table_get_identifier(self.as_type_ref(), &mut identifier, &mut error) }
+            != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(identifier)
+        }
+    }
+
+    /// Retrieves the table name.
+    pub fn get_name(&self) -> Result<String, Error> {
+        get_sized_utf8_string!(
+            self,
+            libesedb_table_get_utf8_name_size,
+            libesedb_table_get_utf8_name
+        )
+    }
+
+    /// Retrieves the number of columns.
+    pub fn get_number_of_columns(&self) -> Result<c_int, Error> {
+        let mut number_of_columns = 0;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libesedb_table_get_number_of_columns(
+                self.as_type_ref(),
+                &mut number_of_columns,
+                COLUMN_FLAGS,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(number_of_columns)
+        }
+    }
+
+    /// Retrieves a specific column.
+    pub fn get_column(&self, column_entry: i32) -> Result<Column<'a>, Error> {
+        let mut column = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libesedb_table_get_column(
+                self.as_type_ref(),
+                column_entry,
+                &mut column,
+                COLUMN_FLAGS,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(Column::wrap_ptr(self.1, column))
+        }
+    }
+
+    /// Iterates the columns of the table.
+    pub fn iter_columns<'c>(&'c self) -> Result<IterColumns<'a, 'c>, Error> {
+        let num_columns = self.get_number_of_columns()?;
+
+        Ok(IterColumns {
+            handle: self,
+            num_columns: num_columns as u32,
+            idx: 0,
+        })
+    }
+
+    /// Retrieves the number of records.
+    pub fn get_number_of_records(&self) -> Result<c_int, Error> {
+        let mut number_of_records = 0;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libesedb_table_get_number_of_records(
+                self.as_type_ref(),
+                &mut number_of_records,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(number_of_records)
+        }
+    }
+
+    /// Retrieves a specific record.
+    pub fn get_record(&self, record_entry: i32) -> Result<Record<'a>, Error> {
+        let mut record = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libesedb_table_get_record(self.as_type_ref(), record_entry, &mut record, &mut error)
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(Record::wrap_ptr(self.1, record))
+        }
+    }
+
+    /// Iterates the records of the table.
+    pub fn iter_records<'c>(&'c self) -> Result<IterRecords<'a, 'c>, Error> {
+        let num_records = self.get_number_of_records()?;
+
+        Ok(IterRecords {
+            handle: self,
+            num_records: num_records as u32,
+            idx: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::column::ColumnType;
+    use crate::fixtures::*;
+
+    #[test]
+    fn test_iter_columns() {
+        let file = sample_database().unwrap();
+        let table = file.get_table_by_name("MSysObjects").unwrap().unwrap();
+
+        let columns: Vec<(String, ColumnType)> = table
+            .iter_columns()
+            .unwrap()
+            .map(|c| c.and_then(|c| Ok((c.get_name()?, c.get_type()?))).unwrap())
+            .collect();
+
+        assert!(columns.contains(&("Name".to_string(), ColumnType::Text)));
+        assert!(columns.contains(&("Type".to_string(), ColumnType::Integer16BitSigned)));
+    }
+
+    #[test]
+    fn test_iter_records() {
+        let file = sample_database().unwrap();
+        let table = file.get_table_by_name("MSysObjects").unwrap().unwrap();
+
+        let records = table
+            .iter_records()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(
+            records.len(),
+            table.get_number_of_records().unwrap() as usize
+        );
+        assert!(!records.is_empty());
+    }
+}
@@ -0,0 +1 @@
+#include "libevtx.h"
\ No newline at end of file
@@ -0,0 +1,306 @@
+use crate::error::Error;
+use crate::ffi_error::LibevtxErrorRefMut;
+use crate::record::{Record, RecordRefMut};
+use libbfio_rs::handle::{Handle, HandleRef};
+use libevtx_sys::{
+    LIBEVTX_ACCESS_FLAGS, LIBEVTX_ACCESS_FLAGS_LIBEVTX_ACCESS_FLAG_READ,
+    LIBEVTX_ACCESS_FLAGS_LIBEVTX_ACCESS_FLAG_WRITE,
+};
+use libyal_rs_common::ffi::AsTypeRef;
+use log::error;
+use std::convert::TryFrom;
+use std::ffi::CString;
+use std::os::raw::c_int;
+use std::ptr;
+
+#[repr(C)]
+pub struct __File(isize);
+
+pub type FileRefMut = *mut __File;
+pub type FileRef = *const __File;
+
+#[repr(C)]
+pub struct File(FileRefMut);
+
+impl AsTypeRef for File {
+    type Ref = FileRef;
+    type RefMut = FileRefMut;
+
+    #[inline]
+    fn as_type_ref(&self) -> Self::Ref {
+        // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
+        self.0 as *const _
+    }
+
+    fn as_type_ref_mut(&mut self) -> Self::RefMut {
+        self.0
+    }
+
+    fn as_raw(&mut self) -> *mut Self::RefMut {
+        &mut self.0 as *mut _
+    }
+}
+
+impl File {
+    pub fn wrap_ptr(ptr: FileRefMut) -> File {
+        File(ptr)
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        let mut error = ptr::null_mut();
+
+        if unsafe { libevtx_file_close(self.as_type_ref(), &mut error) } != 0 {
+            error!("`libevtx_file_close` failed!");
+        }
+
+        let mut error = ptr::null_mut();
+        if unsafe { libevtx_file_free(self.as_raw(), &mut error) } != 1 {
+            panic!("`libevtx_file_free` failed!");
+        }
+    }
+}
+
+extern "C" {
+    pub fn libevtx_file_initialize(file: *mut FileRefMut, error: *mut LibevtxErrorRefMut) -> c_int;
+    pub fn libevtx_file_free(file: *mut FileRefMut, error: *mut LibevtxErrorRefMut) -> c_int;
+    pub fn libevtx_file_signal_abort(file: FileRef, error: *mut LibevtxErrorRefMut) -> c_int;
+    pub fn libevtx_file_open(
+        file: FileRef,
+        filename: *const ::std::os::raw::c_char,
+        access_flags: c_int,
+        error: *mut LibevtxErrorRefMut,
+    ) -> c_int;
+    pub fn libevtx_file_open_file_io_handle(
+        file: FileRef,
+        file_io_handle: HandleRef,
+        access_flags: c_int,
+        error: *mut LibevtxErrorRefMut,
+    ) -> c_int;
+    pub fn libevtx_file_close(file: FileRef, error: *mut LibevtxErrorRefMut) -> c_int;
+    pub fn libevtx_file_get_format_version(
+        file: FileRef,
+        major_version: *mut u16,
+        minor_version: *mut u16,
+        error: *mut LibevtxErrorRefMut,
+    ) -> c_int;
+    pub fn libevtx_file_get_number_of_records(
+        file: FileRef,
+        number_of_records: *mut c_int,
+        error: *mut LibevtxErrorRefMut,
+    ) -> c_int;
+    pub fn libevtx_file_get_record_by_index(
+        file: FileRef,
+        record_index: c_int,
+        record: *mut RecordRefMut,
+        error: *mut LibevtxErrorRefMut,
+    ) -> c_int;
+}
+
+pub enum AccessMode {
+    Read,
+    Write,
+}
+
+impl AccessMode {
+    fn as_flag(&self) -> LIBEVTX_ACCESS_FLAGS {
+        match self {
+            AccessMode::Read => LIBEVTX_ACCESS_FLAGS_LIBEVTX_ACCESS_FLAG_READ,
+            AccessMode::Write => LIBEVTX_ACCESS_FLAGS_LIBEVTX_ACCESS_FLAG_WRITE,
+        }
+    }
+}
+
+pub struct IterRecords<'a> {
+    handle: &'a File,
+    num_records: u32,
+    idx: u32,
+}
+
+impl<'a> Iterator for IterRecords<'a> {
+    type Item = Result<Record<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.num_records {
+            let record = self.handle.get_record_by_index(self.idx as i32);
+            self.idx += 1;
+
+            return Some(record);
+        }
+
+        None
+    }
+}
+
+impl File {
+    /// Opens an event log file by filename.
+    pub fn open(filename: impl AsRef<str>, mode: AccessMode) -> Result<Self, Error> {
+        let mut handle = ptr::null_mut();
+
+        let c_string = CString::new(filename.as_ref()).map_err(Error::StringContainsNul)?;
+
+        let mut init_error = ptr::null_mut();
+
+        let retcode = unsafe { libevtx_file_initialize(&mut handle as _, &mut init_error as _) };
+
+        if retcode != 1 {
+            return Err(Error::try_from(init_error)?);
+        }
+
+        let file = File::wrap_ptr(handle);
+
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libevtx_file_open(
+                file.as_type_ref(),
+                c_string.as_ptr(),
+                mode.as_flag() as c_int,
+                &mut error as _,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(file)
+        }
+    }
+
+    /// Opens an event log file using a libbfio handle.
+    pub fn open_file_object(file_handle: &Handle) -> Result<Self, Error> {
+        let mut handle = ptr::null_mut();
+        let mut init_error = ptr::null_mut();
+
+        let retcode = unsafe { libevtx_file_initialize(&mut handle as _, &mut init_error as _) };
+
+        if retcode != 1 {
+            return Err(Error::try_from(init_error)?);
+        }
+
+        let file = File::wrap_ptr(handle);
+
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libevtx_file_open_file_io_handle(
+                file.as_type_ref(),
+                file_handle.as_type_ref(),
+                AccessMode::Read.as_flag() as c_int,
+                &mut error as _,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(file)
+        }
+    }
+
+    /// Retrieves the format version as a (major, minor) tuple.
+    pub fn get_format_version(&self) -> Result<(u16, u16), Error> {
+        let mut major_version = 0_u16;
+        let mut minor_version = 0_u16;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libevtx_file_get_format_version(
+                self.as_type_ref(),
+                &mut major_version,
+                &mut minor_version,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok((major_version, minor_version))
+        }
+    }
+
+    /// Retrieves the number of records.
+    pub fn get_number_of_records(&self) -> Result<c_int, Error> {
+        let mut number_of_records = 0;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libevtx_file_get_number_of_records(
+                self.as_type_ref(),
+                &mut number_of_records,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(number_of_records)
+        }
+    }
+
+    /// Retrieves a specific record.
+    pub fn get_record_by_index(&self, record_index: i32) -> Result<Record, Error> {
+        let mut record = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libevtx_file_get_record_by_index(
+                self.as_type_ref(),
+                record_index,
+                &mut record,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(Record::wrap_ptr(self, record))
+        }
+    }
+
+    pub fn iter_records(&self) -> Result<IterRecords, Error> {
+        Ok(IterRecords {
+            handle: self,
+            num_records: self.get_number_of_records()? as u32,
+            idx: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    #[test]
+    fn test_opens_file_io_works() {
+        let handle = sample_evtx_io_handle().unwrap();
+        let file_from_io = File::open_file_object(&handle).unwrap();
+
+        assert_eq!(
+            sample_evtx().unwrap().get_number_of_records().unwrap(),
+            file_from_io.get_number_of_records().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_first_record_event_identifier() {
+        let file = sample_evtx().unwrap();
+        let record = file.get_record_by_index(0).unwrap();
+
+        assert_eq!(record.get_event_identifier().unwrap(), 7036);
+    }
+
+    #[test]
+    fn test_iter_records() {
+        let file = sample_evtx().unwrap();
+
+        let records: Vec<Record> = file.iter_records().unwrap().map(|r| r.unwrap()).collect();
+        assert_eq!(
+            records.len(),
+            file.get_number_of_records().unwrap() as usize
+        );
+
+        assert_eq!(records[0].get_identifier().unwrap(), 1);
+        assert_eq!(records[0].get_event_level().unwrap(), 4);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::error::Error;
+use crate::ffi_error::LibevtxErrorRefMut;
+use crate::file::File;
+use chrono::{DateTime, Utc};
+use libyal_rs_common::ffi::AsTypeRef;
+use std::convert::TryFrom;
+use std::fmt::{self, Debug};
+use std::os::raw::c_int;
+use std::ptr;
+
+#[repr(C)]
+pub struct __Record(isize);
+
+pub type RecordRefMut = *mut __Record;
+pub type RecordRef = *const __Record;
+
+#[repr(C)]
+pub struct Record<'a>(RecordRefMut, &'a File);
+
+impl<'a> AsTypeRef for Record<'a> {
+    type Ref = RecordRef;
+    type RefMut = RecordRefMut;
+
+    #[inline]
+    fn as_type_ref(&self) -> Self::Ref {
+        // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
+        self.0 as *const _
+    }
+
+    #[inline]
+    fn as_type_ref_mut(&mut self) -> Self::RefMut {
+        self.0
+    }
+
+    #[inline]
+    fn as_raw(&mut self) -> *mut Self::RefMut {
+        &mut self.0 as *mut _
+    }
+}
+
+impl<'a> Record<'a> {
+    pub fn wrap_ptr(file: &'a File, ptr: RecordRefMut) -> Self {
+        Record(ptr, file)
+    }
+}
+
+impl<'a> Drop for Record<'a> {
+    fn drop(&mut self) {
+        use log::trace;
+
+        let mut error = ptr::null_mut();
+
+        trace!("Calling `libevtx_record_free`");
+
+        unsafe {
+            libevtx_record_free(self.as_raw(), &mut error);
+        }
+
+        debug_assert!(error.is_null(), "`libevtx_record_free` failed!");
+    }
+}
+
+impl<'a> Debug for Record<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("Record")
+            .field("Identifier", &self.get_identifier().unwrap_or(0))
+            .field("EventIdentifier", &self.get_event_identifier().unwrap_or(0))
+            .finish()
+    }
+}
+
+extern "C" {
+    pub fn libevtx_record_free(record: *mut RecordRefMut, error: *mut LibevtxErrorRefMut) -> c_int;
+    pub fn libevtx_record_get_offset(
+        record: RecordRef,
+        offset: *mut i64,
+        error: *mut LibevtxErrorRefMut,
+    ) -> c_int;
+    pub fn libevtx_record_get_identifier(
+        record: RecordRef,
+        identifier: *mut u64,
+        error: *mut LibevtxErrorRefMut,
+    ) -> c_int;
+    pub fn libevtx_record_get_written_time(
+        record: RecordRef,
+        filetime: *mut u64,
+        error: *mut LibevtxErrorRefMut,
+    ) -> c_int;
+    pub fn libevtx_record_get_event_identifier(
+        record: RecordRef,
+        event_identifier: *mut u32,
+        error: *mut LibevtxErrorRefMut,
+    ) -> c_int;
+    pub fn libevtx_record_get_event_level(
+        record: RecordRef,
+        event_level: *mut u8,
+        error: *mut LibevtxErrorRefMut,
+    ) -> c_int;
+    pub fn libevtx_record_get_utf8_xml_string_size(
+        record: RecordRef,
+        utf8_string_size: *mut usize,
+        error: *mut LibevtxErrorRefMut,
+    ) -> c_int;
+    pub fn libevtx_record_get_utf8_xml_string(
+        record: RecordRef,
+        utf8_string: *mut u8,
+        utf8_string_size: usize,
+        error: *mut LibevtxErrorRefMut,
+    ) -> c_int;
+}
+
+impl<'a> Record<'a> {
+    /// Retrieves the record identifier.
+    pub fn get_identifier(&self) -> Result<u64, Error> {
+        get_u64_field!(self, libevtx_record_get_identifier)
+    }
+
+    /// Retrieves the written date and time.
+    pub fn get_written_time(&self) -> Result<Option<DateTime<Utc>>, Error> {
+        get_date_field!(self, libevtx_record_get_written_time)
+    }
+
+    /// Retrieves the event identifier.
+    pub fn get_event_identifier(&self) -> Result<u32, Error> {
+        let mut event_identifier = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libevtx_record_get_event_identifier(
+                self.as_type_ref(),
+                &mut event_identifier,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(event_identifier)
+        }
+    }
+
+    /// Retrieves the event level.
+    pub fn get_event_level(&self) -> Result<u8, Error> {
+        let mut event_level = 0_u8;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libevtx_record_get_event_level(self.as_type_ref(), &mut event_level, &mut error)
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(event_level)
+        }
+    }
+
+    /// Retrieves the event XML.
+    pub fn get_xml_string(&self) -> Result<String, Error> {
+        get_sized_utf8_string!(
+            self,
+            libevtx_record_get_utf8_xml_string_size,
+            libevtx_record_get_utf8_xml_string
+        )
+    }
+}
@@ -1,5 +1,5 @@
 use failure::{bail, Error};
-use libyal_rs_common_build::{build_lib, generate_bindings, sync_libs, get_lib_and_copy_to_out_dir};
+use libyal_rs_common_build::{build_lib, generate_bindings, sync_libs, get_lib_and_copy_to_out_dir, emit_static_link_libs};
 use std::env;
 use std::fs::File;
 use std::io::{Write, Read};
@@ -7,31 +7,8 @@ use std::path::PathBuf;
 
 fn build_and_link_static(lib_path: PathBuf) -> PathBuf {
     if cfg!(target_os = "windows") {
-        println!("cargo:rustc-link-lib=static=libfsntfs");
-
-        // Also static-link deps (otherwise we'll get missing symbols at link time).
-        let deps = [
-            "libbfio",
-            "libcdata",
-            "libcerror",
-            "libcfile",
-            "libclocale",
-            "libcnotify",
-            "libcpath",
-            "libcsplit",
-            "libcthreads",
-            "libfcache",
-            "libfdata",
-            "libfdatetime",
-            "libfguid",
-            "libfusn",
-            "libfwnt",
-            "libuna",
-        ];
-
-        for dep in deps.iter() {
-            println!("cargo:rustc-link-lib=static={}", dep);
-        }
+        // Static-link the lib and its deps, dependents first (otherwise we'll get missing symbols at link time).
+        emit_static_link_libs("libfsntfs");
     } else {
         println!("cargo:rustc-link-lib=static=fsntfs");
     }
@@ -0,0 +1,60 @@
+//! Compares the throughput of `FileEntry::read_at` with and without the memory mapping of the
+//! image, by reading the `$MFT` of the sample image in chunks.
+//!
+//! Run with `cargo bench --features mmap --bench mmap_read`.
+
+extern crate libfsntfs_rs;
+
+use libfsntfs_rs::file_entry::FileEntry;
+use libfsntfs_rs::volume::{AccessMode, Volume};
+use std::time::{Duration, Instant};
+
+const SAMPLE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/ntfs-img-kw-1.dd");
+const CHUNK_SIZE: usize = 4096;
+const ITERATIONS: usize = 200;
+
+/// Reads the whole default data stream of `entry` `ITERATIONS` times.
+fn read_repeatedly(entry: &FileEntry) -> (u64, Duration) {
+    let size = entry.get_size().unwrap();
+    let mut buf = vec![0; CHUNK_SIZE];
+    let mut total = 0;
+
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        let mut offset = 0;
+
+        while offset < size {
+            let read_count = entry.read_at(offset, &mut buf).unwrap();
+            offset += read_count as u64;
+        }
+
+        total += offset;
+    }
+
+    (total, start.elapsed())
+}
+
+fn report(label: &str, (bytes, elapsed): (u64, Duration)) {
+    let mib_per_second = bytes as f64 / (1024.0 * 1024.0) / elapsed.as_secs_f64();
+    println!("{:<8} {:>10.1} MiB/s ({:?})", label, mib_per_second, elapsed);
+}
+
+fn main() {
+    let mut volume = Volume::open(SAMPLE, AccessMode::Read).unwrap();
+
+    let mapped = {
+        let mft = volume.get_file_entry_by_mft_idx(0).unwrap();
+        read_repeatedly(&mft)
+    };
+
+    volume.set_memory_mapping(false);
+
+    let unmapped = {
+        let mft = volume.get_file_entry_by_mft_idx(0).unwrap();
+        read_repeatedly(&mft)
+    };
+
+    report("mmap", mapped);
+    report("ffi", unmapped);
+}
@@ -0,0 +1,273 @@
+//! Asynchronous reading of file entry data on a tokio runtime.
+use crate::error::Error;
+use crate::volume::{AccessMode, MftEntryIndex, Volume};
+use std::cmp;
+use std::future::Future;
+use std::io::{self, SeekFrom};
+use std::pin::Pin;
+use std::sync::mpsc;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+use tokio::sync::oneshot;
+use tokio::task;
+
+/// Upper bound on the number of bytes requested from the worker per read.
+const MAX_READ_SIZE: usize = 64 * 1024;
+
+struct ReadRequest {
+    offset: u64,
+    size: usize,
+    reply: oneshot::Sender<io::Result<Vec<u8>>>,
+}
+
+/// The default data stream of a file entry, readable with `tokio::io::AsyncRead`.
+///
+/// Volume and file entry handles are not `Send`, so they are owned by a worker running on the
+/// blocking thread pool which serves the reads one at a time. The worker exits when the
+/// `AsyncFileEntry` is dropped.
+pub struct AsyncFileEntry {
+    requests: mpsc::Sender<ReadRequest>,
+    size: u64,
+    position: u64,
+    pending: Option<oneshot::Receiver<io::Result<Vec<u8>>>>,
+}
+
+fn worker_gone() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "file entry worker has stopped")
+}
+
+fn serve(
+    filename: String,
+    mft_entry_index: MftEntryIndex,
+    ready: oneshot::Sender<Result<u64, Error>>,
+    requests: mpsc::Receiver<ReadRequest>,
+) {
+    let volume = match Volume::open(filename, AccessMode::Read) {
+        Ok(volume) => volume,
+        Err(e) => {
+            let _ = ready.send(Err(e));
+            return;
+        }
+    };
+
+    let file_entry = match volume.get_file_entry_by_mft_idx(mft_entry_index) {
+        Ok(file_entry) => file_entry,
+        Err(e) => {
+            let _ = ready.send(Err(e));
+            return;
+        }
+    };
+
+    match file_entry.get_size() {
+        Ok(size) => {
+            if ready.send(Ok(size)).is_err() {
+                return;
+            }
+        }
+        Err(e) => {
+            let _ = ready.send(Err(e));
+            return;
+        }
+    }
+
+    for request in requests {
+        let mut buf = vec![0; request.size];
+
+        let result = file_entry
+            .read_at(request.offset, &mut buf)
+            .map(|read| {
+                buf.truncate(read);
+                buf
+            })
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()));
+
+        let _ = request.reply.send(result);
+    }
+}
+
+impl AsyncFileEntry {
+    /// Opens the volume at `filename` and the file entry at `mft_entry_index` on the blocking
+    /// thread pool of the current tokio runtime.
+    pub async fn open(
+        filename: impl AsRef<str>,
+        mft_entry_index: MftEntryIndex,
+    ) -> Result<Self, Error> {
+        let filename = filename.as_ref().to_string();
+        let (requests, receiver) = mpsc::channel();
+        let (ready, opened) = oneshot::channel();
+
+        task::spawn_blocking(move || serve(filename, mft_entry_index, ready, receiver));
+
+        let size = opened
+            .await
+            .map_err(|_| Error::Other("File entry worker has stopped".to_string()))??;
+
+        Ok(AsyncFileEntry {
+            requests,
+            size,
+            position: 0,
+            pending: None,
+        })
+    }
+
+    /// Size of the default data stream.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+impl AsyncRead for AsyncFileEntry {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        let receiver = match this.pending {
+            Some(ref mut receiver) => receiver,
+            None => {
+                if buf.remaining() == 0 || this.position >= this.size {
+                    return Poll::Ready(Ok(()));
+                }
+
+                let size = cmp::min(
+                    cmp::min(buf.remaining(), MAX_READ_SIZE) as u64,
+                    this.size - this.position,
+                ) as usize;
+                let (reply, receiver) = oneshot::channel();
+
+                this.requests
+                    .send(ReadRequest {
+                        offset: this.position,
+                        size,
+                        reply,
+                    })
+                    .map_err(|_| worker_gone())?;
+
+                this.pending.get_or_insert(receiver)
+            }
+        };
+
+        let result = match Pin::new(receiver).poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        this.pending = None;
+
+        let data = result.map_err(|_| worker_gone())??;
+
+        // The caller may have passed a smaller buffer than the one the read was issued for.
+        let count = cmp::min(data.len(), buf.remaining());
+        buf.put_slice(&data[..count]);
+        this.position += count as u64;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncSeek for AsyncFileEntry {
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> io::Result<()> {
+        let this = self.get_mut();
+
+        let (base, offset) = match position {
+            SeekFrom::Start(offset) => (0, offset as i128),
+            SeekFrom::End(offset) => (this.size, offset as i128),
+            SeekFrom::Current(offset) => (this.position, offset as i128),
+        };
+
+        let position = base as i128 + offset;
+
+        if position < 0 || position > u64::max_value() as i128 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            ));
+        }
+
+        // A read still in flight refers to the old position, its result is discarded.
+        this.pending = None;
+        this.position = position as u64;
+
+        Ok(())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        Poll::Ready(Ok(self.position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+    use crate::volume::mft_entry_index;
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+    use tokio::runtime::Builder;
+
+    fn sync_read(volume: &Volume) -> (MftEntryIndex, Vec<u8>) {
+        let entry = file_entry(volume).unwrap();
+        let mut data = vec![0; entry.get_size().unwrap() as usize];
+
+        let read = entry.read_at(0, &mut data).unwrap();
+        data.truncate(read);
+
+        (mft_entry_index(entry.get_file_reference().unwrap()), data)
+    }
+
+    #[test]
+    fn test_async_read_matches_sync_read() {
+        let volume = sample_volume().unwrap();
+        let (idx, expected) = sync_read(&volume);
+
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+
+        let data = runtime.block_on(async {
+            let mut entry = AsyncFileEntry::open(sample_volume_path(), idx)
+                .await
+                .unwrap();
+
+            assert_eq!(entry.size(), expected.len() as u64);
+
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).await.unwrap();
+            data
+        });
+
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn test_async_seek() {
+        let volume = sample_volume().unwrap();
+        let (idx, expected) = sync_read(&volume);
+        let tail = cmp::min(expected.len(), 10);
+
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+
+        let data = runtime.block_on(async {
+            let mut entry = AsyncFileEntry::open(sample_volume_path(), idx)
+                .await
+                .unwrap();
+
+            let position = entry.seek(SeekFrom::End(-(tail as i64))).await.unwrap();
+            assert_eq!(position, (expected.len() - tail) as u64);
+
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).await.unwrap();
+            data
+        });
+
+        assert_eq!(data, &expected[expected.len() - tail..]);
+    }
+
+    #[test]
+    fn test_open_missing_volume() {
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+
+        let result = runtime.block_on(AsyncFileEntry::open("does-not-exist.dd", 5));
+
+        assert!(result.is_err());
+    }
+}
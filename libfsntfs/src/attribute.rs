@@ -1,6 +1,9 @@
 use crate::error::Error;
 use crate::ffi_error::{LibfsntfsErrorRef, LibfsntfsErrorRefMut};
 use crate::file_entry::FileEntry;
+use crate::mft::MftRecord;
+use crate::utils::normalize_variant_name;
+use crate::volume::{mft_entry_index, FileReference};
 use chrono::{Date, DateTime, NaiveDateTime, Utc};
 use libfsntfs_sys::size64_t;
 use libyal_rs_common::ffi::AsTypeRef;
@@ -9,6 +12,7 @@ use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::os::raw::c_int;
+use std::str::FromStr;
 use std::{fmt, ptr};
 
 #[repr(C)]
@@ -26,6 +30,7 @@ impl<'a> AsTypeRef for Attribute<'a> {
 
     #[inline]
     fn as_type_ref(&self) -> Self::Ref {
+        debug_assert!(!self.0.is_null(), "`Attribute` was used after it was freed");
         // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
         self.0 as *const _
     }
@@ -61,6 +66,9 @@ impl<'a> Drop for Attribute<'a> {
         }
 
         debug_assert!(error.is_null(), "`libfsntfs_attribute_free` failed!");
+
+        // Cleared so that accessors can catch a handle that is used after being freed.
+        self.0 = ptr::null_mut();
     }
 }
 
@@ -79,7 +87,7 @@ impl<'a> Debug for Attribute<'a> {
     }
 }
 
-#[derive(PartialOrd, PartialEq, Debug, Clone)]
+#[derive(PartialOrd, PartialEq, Eq, Hash, Debug, Clone, Copy)]
 #[repr(C)]
 pub enum AttributeType {
     Unused = 0,
@@ -130,6 +138,160 @@ impl TryFrom<u32> for AttributeType {
     }
 }
 
+const ATTRIBUTE_TYPES: &[AttributeType] = &[
+    AttributeType::Unused,
+    AttributeType::StandardInformation,
+    AttributeType::AttributeList,
+    AttributeType::FileName,
+    AttributeType::ObjectIdentifier,
+    AttributeType::SecurityDescriptor,
+    AttributeType::VolumeName,
+    AttributeType::VolumeInformation,
+    AttributeType::Data,
+    AttributeType::IndexRoot,
+    AttributeType::IndexAllocation,
+    AttributeType::Bitmap,
+    AttributeType::ReparsePoint,
+    AttributeType::ExtendedInformation,
+    AttributeType::Extended,
+    AttributeType::PropertySet,
+    AttributeType::LoggedUtilityStream,
+    AttributeType::EndOfAttributes,
+];
+
+impl fmt::Display for AttributeType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Parses the variant name case-insensitively. The NTFS spelling (e.g. `$FILE_NAME`) is
+/// accepted as well.
+impl FromStr for AttributeType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = normalize_variant_name(s);
+
+        ATTRIBUTE_TYPES
+            .iter()
+            .find(|t| normalize_variant_name(&t.to_string()) == name)
+            .cloned()
+            .ok_or_else(|| Error::UnknownEnumVariantName(s.to_owned(), "AttributeType"))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+#[repr(u32)]
+pub enum ReparsePointTag {
+    MountPoint = 0xa000_0003,
+    Hsm = 0xc000_0004,
+    Hsm2 = 0x8000_0006,
+    Sis = 0x8000_0007,
+    Wim = 0x8000_0008,
+    Csv = 0x8000_0009,
+    Dfs = 0x8000_000a,
+    Symlink = 0xa000_000c,
+    Dfsr = 0x8000_0012,
+    Dedup = 0x8000_0013,
+    Nfs = 0x8000_0014,
+    CloudFiles = 0x9000_001a,
+    AppExecLink = 0x8000_001b,
+    LxSymlink = 0xa000_001d,
+    AfUnix = 0x8000_0023,
+    LxFifo = 0x8000_0024,
+    LxChr = 0x8000_0025,
+    LxBlk = 0x8000_0026,
+}
+
+const REPARSE_POINT_TAGS: &[ReparsePointTag] = &[
+    ReparsePointTag::MountPoint,
+    ReparsePointTag::Hsm,
+    ReparsePointTag::Hsm2,
+    ReparsePointTag::Sis,
+    ReparsePointTag::Wim,
+    ReparsePointTag::Csv,
+    ReparsePointTag::Dfs,
+    ReparsePointTag::Symlink,
+    ReparsePointTag::Dfsr,
+    ReparsePointTag::Dedup,
+    ReparsePointTag::Nfs,
+    ReparsePointTag::CloudFiles,
+    ReparsePointTag::AppExecLink,
+    ReparsePointTag::LxSymlink,
+    ReparsePointTag::AfUnix,
+    ReparsePointTag::LxFifo,
+    ReparsePointTag::LxChr,
+    ReparsePointTag::LxBlk,
+];
+
+impl TryFrom<u32> for ReparsePointTag {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        ReparsePointTag::from_raw(value).map_err(Error::UnknownReparsePointTagEnumVariant)
+    }
+}
+
+impl ReparsePointTag {
+    /// Converts a tag as stored on disk, returning it unchanged if it is not one of the known
+    /// tags, e.g. one defined by a third party filter driver.
+    pub fn from_raw(value: u32) -> Result<ReparsePointTag, u32> {
+        REPARSE_POINT_TAGS
+            .iter()
+            .find(|tag| **tag as u32 == value)
+            .cloned()
+            .ok_or(value)
+    }
+}
+
+impl fmt::Display for ReparsePointTag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Parses the variant name case-insensitively, e.g. `symlink` or `MOUNT_POINT`.
+impl FromStr for ReparsePointTag {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = normalize_variant_name(s);
+
+        REPARSE_POINT_TAGS
+            .iter()
+            .find(|tag| normalize_variant_name(&tag.to_string()) == name)
+            .cloned()
+            .ok_or_else(|| Error::UnknownEnumVariantName(s.to_owned(), "ReparsePointTag"))
+    }
+}
+
+/// The namespace of a `$FILE_NAME` attribute, which tells long names apart from 8.3 names.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+#[repr(u8)]
+pub enum NameNamespace {
+    Posix = 0,
+    Windows = 1,
+    Dos = 2,
+    /// A name that is valid in both the Windows and DOS namespaces, so no separate 8.3 name is
+    /// stored.
+    WindowsAndDos = 3,
+}
+
+impl TryFrom<u8> for NameNamespace {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(NameNamespace::Posix),
+            1 => Ok(NameNamespace::Windows),
+            2 => Ok(NameNamespace::Dos),
+            3 => Ok(NameNamespace::WindowsAndDos),
+            _ => Err(Error::UnknownNameNamespaceEnumVariant(value)),
+        }
+    }
+}
+
 extern "C" {
     pub fn libfsntfs_attribute_free(
         attribute: *mut AttributeRefMut,
@@ -209,6 +371,11 @@ extern "C" {
         filetime: *mut u64,
         error: *mut LibfsntfsErrorRefMut,
     ) -> c_int;
+    pub fn libfsntfs_file_name_attribute_get_name_space(
+        attribute: AttributeRef,
+        name_space: *mut u8,
+        error: *mut LibfsntfsErrorRefMut,
+    ) -> c_int;
     pub fn libfsntfs_file_name_attribute_get_file_attribute_flags(
         attribute: AttributeRef,
         file_attribute_flags: *mut u32,
@@ -424,9 +591,24 @@ pub struct StandardInformation {
     pub access_time: Option<DateTime<Utc>>,
     pub entry_modification_time: Option<DateTime<Utc>>,
     pub file_attribute_flags: u32,
+    /// Identifier of the owner in the quota tracking of `$Quota`, 0 if quotas are disabled.
     pub owner_identifier: u32,
+    /// Identifier of the security descriptor in `$Secure`.
     pub security_descriptor_identifier: u32,
-    pub update_sequence_number: u32,
+    /// Update sequence number (USN) of the last change, 0 if the change journal is disabled.
+    pub update_sequence_number: u64,
+}
+
+impl StandardInformation {
+    /// Identifier of the security descriptor, as yielded by `Volume::iter_security_descriptors`.
+    pub fn security_id(&self) -> u32 {
+        self.security_descriptor_identifier
+    }
+
+    /// Identifier of the owner for quota tracking.
+    pub fn owner_id(&self) -> u32 {
+        self.owner_identifier
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -491,6 +673,21 @@ impl<'a> Attribute<'a> {
         )
     }
 
+    /// Determines if the attribute has a name, e.g. the `$DATA` attribute of an alternate data
+    /// stream.
+    pub fn has_name(&self) -> Result<bool, Error> {
+        let mut name_size = 0_usize;
+        let mut error = ptr::null_mut();
+
+        match unsafe {
+            libfsntfs_attribute_get_utf8_name_size(self.as_type_ref(), &mut name_size, &mut error)
+        } {
+            1 => Ok(name_size > 1),
+            0 => Ok(false),
+            _ => Err(Error::try_from(error)?),
+        }
+    }
+
     pub fn get_data(&self) -> Result<AttributeWithInformation, Error> {
         match self.get_type()? {
             AttributeType::VolumeName => {
@@ -552,6 +749,22 @@ impl<'a> Attribute<'a> {
                     self,
                     libfsntfs_standard_information_attribute_get_entry_modification_time
                 )?;
+                let file_attribute_flags = get_u32_field!(
+                    self,
+                    libfsntfs_standard_information_attribute_get_file_attribute_flags
+                )?;
+                let owner_identifier = get_u32_field!(
+                    self,
+                    libfsntfs_standard_information_attribute_get_owner_identifier
+                )?;
+                let security_descriptor_identifier = get_u32_field!(
+                    self,
+                    libfsntfs_standard_information_attribute_get_security_descriptor_identifier
+                )?;
+                let update_sequence_number = get_u64_field!(
+                    self,
+                    libfsntfs_standard_information_attribute_get_update_sequence_number
+                )?;
 
                 Ok(AttributeWithInformation::StandardInformation(
                     StandardInformation {
@@ -559,10 +772,10 @@ impl<'a> Attribute<'a> {
                         modification_time,
                         access_time,
                         entry_modification_time,
-                        file_attribute_flags: 0,
-                        owner_identifier: 0,
-                        security_descriptor_identifier: 0,
-                        update_sequence_number: 0,
+                        file_attribute_flags,
+                        owner_identifier,
+                        security_descriptor_identifier,
+                        update_sequence_number,
                     },
                 ))
             }
@@ -586,12 +799,106 @@ impl<'a> Attribute<'a> {
             }
             _ => Err(Error::Other(format!(
                 "Unimplemented data type: {:?}",
-                self.get_type().unwrap()
+                self.get_type()?
             ))),
         }
     }
 
+    /// Retrieves the file reference of the file entry the attribute was read from.
+    ///
+    /// For entries with an `$ATTRIBUTE_LIST` this is the base record, even if the attribute is
+    /// stored in an extension record.
+    pub fn owner_file_reference(&self) -> Result<FileReference, Error> {
+        Ok(FileReference(self.1.get_file_reference()?))
+    }
+
+    /// Retrieves the size of the attribute data.
+    pub fn get_data_size(&self) -> Result<u64, Error> {
+        get_u64_field!(self, libfsntfs_attribute_get_data_size)
+    }
+
+    /// Retrieves the tag of a `$REPARSE_POINT` attribute.
+    pub fn get_reparse_point_tag(&self) -> Result<ReparsePointTag, Error> {
+        let mut tag = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libfsntfs_reparse_point_attribute_get_tag(self.as_type_ref(), &mut tag, &mut error)
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(ReparsePointTag::try_from(tag)?)
+        }
+    }
+
+    /// Retrieves the flags of a `$VOLUME_INFORMATION` attribute.
+    pub fn get_volume_information_flags(&self) -> Result<u16, Error> {
+        let mut flags = 0_u16;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libfsntfs_volume_information_attribute_get_flags(
+                self.as_type_ref(),
+                &mut flags,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(flags)
+        }
+    }
+
+    /// Retrieves the namespace of a `$FILE_NAME` attribute.
+    pub fn get_file_name_namespace(&self) -> Result<NameNamespace, Error> {
+        let mut name_space = 0_u8;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libfsntfs_file_name_attribute_get_name_space(
+                self.as_type_ref(),
+                &mut name_space,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(NameNamespace::try_from(name_space)?)
+        }
+    }
+
+    /// Determines if the attribute value is stored inside the MFT record, rather than in
+    /// clusters on disk. Only non-resident attributes have extents.
+    pub fn is_resident(&self) -> Result<bool, Error> {
+        let mut first_vcn = 0_u64;
+        let mut last_vcn = 0_u64;
+        let mut error = ptr::null_mut();
+
+        // libfsntfs only has a VCN range for non-resident attributes.
+        match unsafe {
+            libfsntfs_attribute_get_data_vcn_range(
+                self.as_type_ref(),
+                &mut first_vcn,
+                &mut last_vcn,
+                &mut error,
+            )
+        } {
+            1 => Ok(false),
+            0 => Ok(true),
+            _ => Err(Error::try_from(error)?),
+        }
+    }
+
     pub fn get_type(&self) -> Result<AttributeType, Error> {
+        AttributeType::try_from(self.get_type_code()?)
+    }
+
+    /// Retrieves the numeric type of the attribute, which is also available for types that
+    /// `AttributeType` does not know.
+    pub fn get_type_code(&self) -> Result<u32, Error> {
         let mut type_as_num = 0_u32;
         let mut error = ptr::null_mut();
 
@@ -600,7 +907,202 @@ impl<'a> Attribute<'a> {
         {
             Err(Error::try_from(error)?)
         } else {
-            Ok(AttributeType::try_from(type_as_num)?)
+            Ok(type_as_num)
+        }
+    }
+
+    /// Reads the complete value of the attribute as stored, resident or not, e.g. to inspect
+    /// attribute types that have no typed accessor.
+    ///
+    /// Only attributes stored in the base record of the entry can be read. Non-resident values
+    /// larger than the volume, e.g. of corrupt attributes, are rejected with an error.
+    pub fn raw_value(&self) -> Result<Vec<u8>, Error> {
+        self.raw_value_capped(u64::max_value())
+    }
+
+    /// Reads at most the first `max_size` bytes of the value, like `raw_value`.
+    pub fn raw_value_capped(&self, max_size: u64) -> Result<Vec<u8>, Error> {
+        let type_code = self.get_type_code()?;
+        let name = if self.has_name()? {
+            self.get_name()?
+        } else {
+            String::new()
+        };
+
+        let volume = self.1.get_volume();
+        let record = MftRecord::read(volume, mft_entry_index(self.1.get_file_reference()?))?;
+        let values = record.find_all_named_attributes(type_code, &name)?;
+
+        // Attributes of the same type and name, like the names of hard links, are told apart
+        // by their size.
+        let size = self.get_data_size()?;
+        let value = values
+            .iter()
+            .find(|value| value.size() == size)
+            .or_else(|| values.first())
+            .ok_or_else(|| {
+                Error::not_found(format!(
+                    "attribute 0x{:x} {:?} in the base record",
+                    type_code, name
+                ))
+            })?;
+
+        value.read_capped(volume, max_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "`Attribute` was used after it was freed")]
+    fn test_use_after_free_panics() {
+        let volume = sample_volume().unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(27).unwrap();
+        let attribute = entry.get_attribute_by_index(0).unwrap();
+        let mut attribute = std::mem::ManuallyDrop::new(attribute);
+
+        unsafe { std::mem::ManuallyDrop::drop(&mut attribute) };
+
+        let _ = attribute.get_type();
+    }
+
+    #[test]
+    fn test_owner_file_reference() {
+        let volume = sample_volume().unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(27).unwrap();
+        let file_reference = FileReference(entry.get_file_reference().unwrap());
+
+        let owners: Vec<FileReference> = entry
+            .iter_attributes()
+            .unwrap()
+            .map(|a| a.unwrap().owner_file_reference().unwrap())
+            .collect();
+
+        assert!(!owners.is_empty());
+        assert!(owners.iter().all(|owner| *owner == file_reference));
+        assert_eq!(file_reference.mft_entry_index(), 27);
+    }
+
+    #[test]
+    fn test_raw_value_of_index_root() {
+        let volume = sample_volume().unwrap();
+        let root = volume.get_root_directory().unwrap();
+        let index_root = root
+            .get_attribute_by_name(AttributeType::IndexRoot, "$I30")
+            .unwrap()
+            .unwrap();
+
+        let value = index_root.raw_value().unwrap();
+
+        assert!(!value.is_empty());
+        assert_eq!(value.len() as u64, index_root.get_data_size().unwrap());
+        // The index root starts with the type of the indexed attribute, `$FILE_NAME`.
+        assert_eq!(&value[..4], &(AttributeType::FileName as u32).to_le_bytes());
+
+        assert_eq!(index_root.raw_value_capped(4).unwrap(), &value[..4]);
+    }
+
+    #[test]
+    fn test_security_id_resolves_owner() {
+        let volume = sample_volume().unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(27).unwrap();
+
+        let standard_information = match entry
+            .get_attribute_by_name(AttributeType::StandardInformation, "")
+            .unwrap()
+            .unwrap()
+            .get_data()
+            .unwrap()
+        {
+            AttributeWithInformation::StandardInformation(info) => info,
+            other => panic!("expected $STANDARD_INFORMATION, got {:?}", other),
+        };
+
+        let (_, descriptor) = volume
+            .iter_security_descriptors()
+            .unwrap()
+            .map(|d| d.unwrap())
+            .find(|(id, _)| *id == standard_information.security_id())
+            .unwrap();
+
+        assert_eq!(
+            descriptor.data,
+            entry.get_security_descriptor_data().unwrap().unwrap()
+        );
+        assert!(descriptor.owner.unwrap().to_string().starts_with("S-1-"));
+    }
+
+    #[test]
+    fn test_parse_attribute_type() {
+        assert_eq!(
+            "FileName".parse::<AttributeType>().unwrap(),
+            AttributeType::FileName
+        );
+        assert_eq!(
+            "DATA".parse::<AttributeType>().unwrap(),
+            AttributeType::Data
+        );
+        assert_eq!(
+            "$STANDARD_INFORMATION".parse::<AttributeType>().unwrap(),
+            AttributeType::StandardInformation
+        );
+
+        for attribute_type in ATTRIBUTE_TYPES {
+            assert_eq!(
+                &attribute_type.to_string().parse::<AttributeType>().unwrap(),
+                attribute_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_unknown_attribute_type_fails() {
+        let error = "NotAnAttribute".parse::<AttributeType>().unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "AttributeType has no variant named \"NotAnAttribute\""
+        );
+    }
+
+    #[test]
+    fn test_name_namespace_from_u8() {
+        assert_eq!(NameNamespace::try_from(2).unwrap(), NameNamespace::Dos);
+        assert_eq!(
+            NameNamespace::try_from(3).unwrap(),
+            NameNamespace::WindowsAndDos
+        );
+        assert!(NameNamespace::try_from(4).is_err());
+    }
+
+    #[test]
+    fn test_parse_reparse_point_tag() {
+        assert_eq!(
+            "symlink".parse::<ReparsePointTag>().unwrap(),
+            ReparsePointTag::Symlink
+        );
+        assert_eq!(
+            "MOUNT_POINT".parse::<ReparsePointTag>().unwrap(),
+            ReparsePointTag::MountPoint
+        );
+        assert!("junction2".parse::<ReparsePointTag>().is_err());
+
+        for tag in REPARSE_POINT_TAGS {
+            assert_eq!(&tag.to_string().parse::<ReparsePointTag>().unwrap(), tag);
+            assert_eq!(&ReparsePointTag::try_from(*tag as u32).unwrap(), tag);
         }
     }
+
+    #[test]
+    fn test_reparse_point_tag_from_raw() {
+        assert_eq!(
+            ReparsePointTag::from_raw(0xa000_0003),
+            Ok(ReparsePointTag::MountPoint)
+        );
+        assert_eq!(ReparsePointTag::from_raw(0x0000_1234), Err(0x0000_1234));
+    }
 }
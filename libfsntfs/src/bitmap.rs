@@ -0,0 +1,132 @@
+//! Cluster allocation status, from the `$Bitmap` system file.
+use crate::error::Error;
+use crate::volume::{MftEntryIndex, Volume};
+use std::ops::Range;
+
+/// MFT entry index of `$Bitmap`.
+const BITMAP_MFT_ENTRY_INDEX: MftEntryIndex = 6;
+
+/// The allocation status of every cluster of a volume.
+#[derive(Debug, Clone)]
+pub struct ClusterBitmap {
+    data: Vec<u8>,
+    number_of_clusters: u64,
+}
+
+impl ClusterBitmap {
+    pub(crate) fn new(data: Vec<u8>, number_of_clusters: u64) -> Self {
+        // `$Bitmap` is padded to 8 bytes, the padding does not describe clusters.
+        let number_of_clusters = number_of_clusters.min(data.len() as u64 * 8);
+
+        ClusterBitmap {
+            data,
+            number_of_clusters,
+        }
+    }
+
+    pub fn number_of_clusters(&self) -> u64 {
+        self.number_of_clusters
+    }
+
+    /// Determines if a cluster is allocated. Clusters past the end of the volume are reported as
+    /// unallocated.
+    pub fn is_allocated(&self, cluster: u64) -> bool {
+        cluster < self.number_of_clusters
+            && self.data[(cluster / 8) as usize] & (1 << (cluster % 8)) != 0
+    }
+
+    pub fn count_free_clusters(&self) -> u64 {
+        self.free_ranges()
+            .map(|range| range.end - range.start)
+            .sum()
+    }
+
+    /// Iterates the ranges of consecutive unallocated clusters.
+    pub fn free_ranges(&self) -> FreeRanges {
+        FreeRanges {
+            bitmap: self,
+            cluster: 0,
+        }
+    }
+}
+
+pub struct FreeRanges<'a> {
+    bitmap: &'a ClusterBitmap,
+    cluster: u64,
+}
+
+impl<'a> Iterator for FreeRanges<'a> {
+    type Item = Range<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.bitmap.number_of_clusters;
+
+        while self.cluster < end && self.bitmap.is_allocated(self.cluster) {
+            // Skip fully allocated bytes at once.
+            if self.cluster % 8 == 0 && self.bitmap.data[(self.cluster / 8) as usize] == 0xff {
+                self.cluster += 8;
+            } else {
+                self.cluster += 1;
+            }
+        }
+
+        if self.cluster >= end {
+            return None;
+        }
+
+        let start = self.cluster;
+
+        while self.cluster < end && !self.bitmap.is_allocated(self.cluster) {
+            if self.cluster % 8 == 0 && self.bitmap.data[(self.cluster / 8) as usize] == 0 {
+                self.cluster = (self.cluster + 8).min(end);
+            } else {
+                self.cluster += 1;
+            }
+        }
+
+        Some(start..self.cluster)
+    }
+}
+
+impl Volume {
+    /// Reads the allocation status of all clusters from `$Bitmap`.
+    pub fn cluster_allocation_bitmap(&self) -> Result<ClusterBitmap, Error> {
+        let mut bitmap = self.get_file_entry_by_mft_idx(BITMAP_MFT_ENTRY_INDEX)?;
+        let data = bitmap.read_all()?;
+
+        let number_of_clusters = self.get_size()? / self.get_cluster_block_size()? as u64;
+
+        Ok(ClusterBitmap::new(data, number_of_clusters))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    #[test]
+    fn test_free_ranges() {
+        let bitmap = ClusterBitmap::new(vec![0b0000_0110, 0xff, 0x00], 20);
+
+        let ranges: Vec<Range<u64>> = bitmap.free_ranges().collect();
+
+        assert_eq!(ranges, vec![0..1, 3..8, 16..20]);
+        assert_eq!(bitmap.count_free_clusters(), 10);
+        assert!(!bitmap.is_allocated(20));
+    }
+
+    #[test]
+    fn test_cluster_allocation_bitmap() {
+        let volume = sample_volume().unwrap();
+        let bitmap = volume.cluster_allocation_bitmap().unwrap();
+
+        assert_eq!(bitmap.number_of_clusters(), 16064);
+        assert!(bitmap.is_allocated(0));
+        assert!(!bitmap.is_allocated(532));
+
+        let free = bitmap.count_free_clusters();
+        assert!(free > 0 && free < bitmap.number_of_clusters());
+        assert_eq!(free, 9648);
+    }
+}
@@ -0,0 +1,154 @@
+//! Parsing of the boot sector stored in `$Boot`, independently of libfsntfs.
+use crate::error::Error;
+use crate::mft::{read_u16, read_u64};
+use crate::volume::{SerialNumber, Volume};
+
+const BOOT_SECTOR_SIZE: usize = 512;
+const BOOT_SECTOR_SIGNATURE: u16 = 0xaa55;
+
+/// The fields of an NTFS boot sector that describe the layout of the volume.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootSector {
+    /// Should be `NTFS    `.
+    pub oem_id: [u8; 8],
+    pub bytes_per_sector: u16,
+    /// Decoded from its on-disk form, which stores large values as a negative power of two.
+    pub sectors_per_cluster: u32,
+    /// Logical cluster number of the first cluster of `$MFT`.
+    pub mft_lcn: u64,
+    /// Logical cluster number of the first cluster of `$MFTMirr`.
+    pub mft_mirr_lcn: u64,
+    /// As stored, a negative value `-n` means MFT records of `2^n` bytes.
+    pub clusters_per_mft_record: i8,
+    pub serial_number: SerialNumber,
+    pub total_sectors: u64,
+}
+
+impl BootSector {
+    /// Parses and validates the first sector of a volume.
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        let invalid = |reason: &str| Error::Other(format!("Invalid boot sector: {}", reason));
+
+        if data.len() < BOOT_SECTOR_SIZE {
+            return Err(invalid("truncated"));
+        }
+
+        if read_u16(data, 0x1fe) != Some(BOOT_SECTOR_SIGNATURE) {
+            return Err(invalid("missing signature"));
+        }
+
+        let mut oem_id = [0; 8];
+        oem_id.copy_from_slice(&data[0x03..0x0b]);
+
+        let bytes_per_sector = read_u16(data, 0x0b).ok_or_else(|| invalid("truncated"))?;
+
+        if !bytes_per_sector.is_power_of_two() || bytes_per_sector < 256 {
+            return Err(invalid("unsupported bytes per sector"));
+        }
+
+        let sectors_per_cluster = match data[0x0d] {
+            0 => return Err(invalid("zero sectors per cluster")),
+            value @ 0x01..=0x80 => u32::from(value),
+            // Clusters of 128 sectors or more are stored as a negative power of two.
+            value => 1_u32
+                .checked_shl(u32::from(value.wrapping_neg()))
+                .ok_or_else(|| invalid("unsupported sectors per cluster"))?,
+        };
+
+        let clusters_per_mft_record = data[0x40] as i8;
+
+        if clusters_per_mft_record == 0 {
+            return Err(invalid("zero clusters per MFT record"));
+        }
+
+        Ok(BootSector {
+            oem_id,
+            bytes_per_sector,
+            sectors_per_cluster,
+            mft_lcn: read_u64(data, 0x30).ok_or_else(|| invalid("truncated"))?,
+            mft_mirr_lcn: read_u64(data, 0x38).ok_or_else(|| invalid("truncated"))?,
+            clusters_per_mft_record,
+            serial_number: read_u64(data, 0x48).ok_or_else(|| invalid("truncated"))?,
+            total_sectors: read_u64(data, 0x28).ok_or_else(|| invalid("truncated"))?,
+        })
+    }
+
+    /// Size of a cluster in bytes.
+    pub fn cluster_size(&self) -> u64 {
+        u64::from(self.bytes_per_sector) * u64::from(self.sectors_per_cluster)
+    }
+
+    /// Size of an MFT record in bytes.
+    pub fn mft_record_size(&self) -> Option<u64> {
+        if self.clusters_per_mft_record > 0 {
+            self.cluster_size()
+                .checked_mul(self.clusters_per_mft_record as u64)
+        } else {
+            1_u64.checked_shl(-i32::from(self.clusters_per_mft_record) as u32)
+        }
+    }
+}
+
+impl Volume {
+    /// Reads and parses the boot sector from `$Boot`, e.g. to cross-check the values libfsntfs
+    /// reports.
+    pub fn boot_sector(&self) -> Result<BootSector, Error> {
+        BootSector::parse(&self.read_boot_sector()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    #[test]
+    fn test_boot_sector() {
+        let volume = sample_volume().unwrap();
+        let boot_sector = volume.boot_sector().unwrap();
+
+        assert_eq!(&boot_sector.oem_id, b"NTFS    ");
+        assert_eq!(
+            boot_sector.serial_number,
+            volume.get_serial_number().unwrap()
+        );
+        assert_eq!(
+            boot_sector.cluster_size(),
+            volume.get_cluster_block_size().unwrap() as u64
+        );
+        assert_eq!(
+            boot_sector.mft_record_size(),
+            Some(u64::from(volume.get_mft_entry_size().unwrap()))
+        );
+        assert_eq!(
+            boot_sector.total_sectors * u64::from(boot_sector.bytes_per_sector),
+            volume.get_size().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_boot_sectors() {
+        let volume = sample_volume().unwrap();
+        let mut data = volume.read_boot_sector().unwrap();
+
+        assert!(BootSector::parse(&data[..511]).is_err());
+
+        data[0x0d] = 0;
+        assert!(BootSector::parse(&data).is_err());
+
+        data[0x1fe] = 0;
+        assert!(BootSector::parse(&data).is_err());
+    }
+
+    #[test]
+    fn test_large_clusters_are_decoded() {
+        let volume = sample_volume().unwrap();
+        let mut data = volume.read_boot_sector().unwrap();
+
+        // 2^12 sectors per cluster.
+        data[0x0d] = 0xf4;
+        let boot_sector = BootSector::parse(&data).unwrap();
+
+        assert_eq!(boot_sector.sectors_per_cluster, 4096);
+    }
+}
@@ -0,0 +1,183 @@
+//! Caching of names and parent references, for resolving many paths that share ancestors.
+use crate::error::Error;
+use crate::volume::{FileReference, ReferenceResolution, Volume, ROOT_DIRECTORY_MFT_ENTRY_INDEX};
+use lru::LruCache;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Number of entries cached by `CachedVolume::new`.
+pub const DEFAULT_CACHE_SIZE: usize = 4096;
+
+/// Hit and miss counts of the cache of a `CachedVolume`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// A volume that keeps the names and parent references of recently resolved file references in
+/// an LRU cache, so that resolving paths with common ancestors does not reopen them.
+///
+/// Unlike a `PathIndex`, which needs a pass over the whole MFT up front, entries are only read
+/// when first looked up.
+pub struct CachedVolume {
+    volume: Volume,
+    cache: RefCell<LruCache<FileReference, (String, FileReference)>>,
+    stats: Cell<CacheStats>,
+}
+
+impl CachedVolume {
+    /// Wraps `volume` with a cache of `DEFAULT_CACHE_SIZE` entries.
+    pub fn new(volume: Volume) -> Self {
+        CachedVolume {
+            volume,
+            cache: RefCell::new(LruCache::new(DEFAULT_CACHE_SIZE)),
+            stats: Cell::new(CacheStats::default()),
+        }
+    }
+
+    /// Wraps `volume` with a cache of `cache_size` entries.
+    pub fn with_cache_size(volume: Volume, cache_size: usize) -> Result<Self, Error> {
+        let mut cached = CachedVolume::new(volume);
+        cached.set_cache_size(cache_size)?;
+
+        Ok(cached)
+    }
+
+    pub fn volume(&self) -> &Volume {
+        &self.volume
+    }
+
+    pub fn into_inner(self) -> Volume {
+        self.volume
+    }
+
+    pub fn cache_size(&self) -> usize {
+        self.cache.borrow().cap()
+    }
+
+    /// Sets the number of cached entries, evicting the least recently used ones if it shrinks.
+    pub fn set_cache_size(&mut self, cache_size: usize) -> Result<(), Error> {
+        if cache_size == 0 {
+            return Err(Error::Other("Cache size must be greater than 0".to_owned()));
+        }
+
+        self.cache.get_mut().resize(cache_size);
+
+        Ok(())
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats.get()
+    }
+
+    /// Retrieves the name and parent reference of the file `reference` points to, from the cache
+    /// if possible.
+    ///
+    /// Fails with a not found error if the referenced file was deleted, i.e. its MFT entry is
+    /// free or was reused by another file.
+    pub fn lookup(&self, reference: FileReference) -> Result<(String, FileReference), Error> {
+        let mut stats = self.stats.get();
+
+        if let Some(cached) = self.cache.borrow_mut().get(&reference) {
+            stats.hits += 1;
+            self.stats.set(stats);
+
+            return Ok(cached.clone());
+        }
+
+        stats.misses += 1;
+        self.stats.set(stats);
+
+        let entry = match self.volume.resolve_reference(reference)? {
+            ReferenceResolution::Live(entry) => entry,
+            _ => {
+                return Err(Error::not_found(format!(
+                    "file reference {}-{}",
+                    reference.mft_entry_index(),
+                    reference.sequence_number()
+                )))
+            }
+        };
+
+        let value = (
+            entry.get_name()?,
+            FileReference::from(entry.get_parent_file_reference()?),
+        );
+
+        self.cache.borrow_mut().put(reference, value.clone());
+
+        Ok(value)
+    }
+
+    /// Resolves the path of the file `reference` points to, relative to the root directory,
+    /// like `FileEntry::full_path`. Fails if an ancestor was deleted.
+    pub fn cached_full_path(&self, reference: FileReference) -> Result<PathBuf, Error> {
+        let mut names = Vec::new();
+        let mut visited = HashSet::new();
+        let mut current = reference;
+
+        while current.mft_entry_index() != ROOT_DIRECTORY_MFT_ENTRY_INDEX {
+            if !visited.insert(current.mft_entry_index()) {
+                return Err(Error::Other(format!(
+                    "Parent references of entry {} contain a cycle",
+                    current.mft_entry_index()
+                )));
+            }
+
+            let (name, parent) = self.lookup(current)?;
+            names.push(name);
+            current = parent;
+        }
+
+        Ok(names.iter().rev().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+    use crate::name_match::MatchMode;
+
+    #[test]
+    fn test_second_lookup_is_cached() {
+        let volume = CachedVolume::new(sample_volume().unwrap());
+        let entry = volume
+            .volume()
+            .get_file_entry_by_path("\\$Extend\\$ObjId", MatchMode::Exact)
+            .unwrap();
+        let reference = FileReference::from(entry.get_file_reference().unwrap());
+
+        let path = volume.cached_full_path(reference).unwrap();
+        assert_eq!(path, entry.full_path().unwrap());
+        assert_eq!(volume.stats(), CacheStats { hits: 0, misses: 2 });
+
+        let path = volume.cached_full_path(reference).unwrap();
+        assert_eq!(path, entry.full_path().unwrap());
+        assert_eq!(volume.stats(), CacheStats { hits: 2, misses: 2 });
+    }
+
+    #[test]
+    fn test_stale_reference_is_not_found() {
+        let volume = CachedVolume::new(sample_volume().unwrap());
+
+        // MFT entry 34 held a file that was deleted.
+        let error = volume
+            .cached_full_path(FileReference(34 | 3 << 48))
+            .unwrap_err();
+
+        assert!(error.is_not_found());
+    }
+
+    #[test]
+    fn test_cache_size() {
+        let mut volume = CachedVolume::with_cache_size(sample_volume().unwrap(), 16).unwrap();
+        assert_eq!(volume.cache_size(), 16);
+
+        assert!(volume.set_cache_size(0).is_err());
+        volume.set_cache_size(1).unwrap();
+        assert_eq!(volume.cache_size(), 1);
+    }
+}
@@ -0,0 +1,140 @@
+//! The physical layout of the default data stream, for carvers and imagers.
+use crate::error::Error;
+use crate::extent::Extent;
+use crate::file_entry::FileEntry;
+use std::fmt;
+
+/// A run of the default data stream mapped to its location on the volume, see
+/// `FileEntry::data_run_map`.
+///
+/// Formats as `VCN:LCN:length` in clusters, with an LCN of -1 for sparse runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DataRun {
+    /// Offset of the run in bytes, relative to the start of the data stream.
+    pub logical_offset: u64,
+    /// Offset of the run in bytes, relative to the start of the volume. 0 for sparse runs.
+    pub physical_offset: u64,
+    /// Size of the run in bytes.
+    pub length: u64,
+    pub is_sparse: bool,
+    /// Size of a cluster of the volume in bytes.
+    pub cluster_size: u64,
+}
+
+impl DataRun {
+    /// Virtual cluster number of the start of the run.
+    pub fn vcn(&self) -> u64 {
+        self.logical_offset / self.cluster_size
+    }
+
+    /// Logical cluster number of the start of the run, `None` for sparse runs.
+    pub fn lcn(&self) -> Option<u64> {
+        if self.is_sparse {
+            None
+        } else {
+            Some(self.physical_offset / self.cluster_size)
+        }
+    }
+
+    /// Number of clusters in the run.
+    pub fn number_of_clusters(&self) -> u64 {
+        (self.length + self.cluster_size - 1) / self.cluster_size
+    }
+}
+
+impl fmt::Display for DataRun {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.lcn() {
+            Some(lcn) => write!(f, "{}:{}:{}", self.vcn(), lcn, self.number_of_clusters()),
+            None => write!(f, "{}:-1:{}", self.vcn(), self.number_of_clusters()),
+        }
+    }
+}
+
+impl<'a> FileEntry<'a> {
+    /// Maps the runs of the default data stream to their locations on the volume, in the order
+    /// they appear in the stream.
+    pub fn data_run_map(&self) -> Result<Vec<DataRun>, Error> {
+        let cluster_size = self.get_volume().get_cluster_block_size()? as u64;
+
+        map_extents(&self.get_extents()?, cluster_size)
+    }
+}
+
+fn map_extents(extents: &[Extent], cluster_size: u64) -> Result<Vec<DataRun>, Error> {
+    let mut logical_offset = 0_u64;
+    let mut runs = Vec::with_capacity(extents.len());
+
+    for extent in extents {
+        let physical_range = extent.physical_range()?;
+
+        runs.push(DataRun {
+            logical_offset,
+            physical_offset: physical_range.map_or(0, |range| range.start),
+            length: extent.size,
+            is_sparse: extent.is_sparse(),
+            cluster_size,
+        });
+
+        logical_offset = logical_offset.checked_add(extent.size).ok_or_else(|| {
+            Error::Other("Extents of the data stream exceed the maximum size".to_owned())
+        })?;
+    }
+
+    Ok(runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    const CLUSTER_SIZE: u64 = 512;
+
+    #[test]
+    fn test_data_run_map_of_fragmented_file() {
+        let volume = sample_volume().unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(35).unwrap();
+
+        let runs = entry.data_run_map().unwrap();
+
+        assert_eq!(runs.len(), 2);
+        assert!(runs
+            .windows(2)
+            .all(|pair| pair[0].logical_offset < pair[1].logical_offset));
+
+        assert_eq!(runs[0].logical_offset, 0);
+        assert_eq!(runs[0].physical_offset, 8056 * CLUSTER_SIZE);
+        assert_eq!(runs[1].logical_offset, runs[0].length);
+        assert_eq!(runs[1].physical_offset, 8072 * CLUSTER_SIZE);
+        assert!(runs.iter().all(|run| !run.is_sparse));
+
+        let map: Vec<String> = runs.iter().map(DataRun::to_string).collect();
+        assert_eq!(map, vec!["0:8056:3", "3:8072:3"]);
+    }
+
+    #[test]
+    fn test_map_extents_overflow() {
+        let extents = [
+            Extent::from_raw(0, u64::MAX - 1, 0).unwrap(),
+            Extent::from_raw(0, 2, 0).unwrap(),
+        ];
+
+        assert!(map_extents(&extents[..1], CLUSTER_SIZE).is_ok());
+        assert!(map_extents(&extents, CLUSTER_SIZE).is_err());
+    }
+
+    #[test]
+    fn test_sparse_run_format() {
+        let run = DataRun {
+            logical_offset: 4 * CLUSTER_SIZE,
+            physical_offset: 0,
+            length: 16 * CLUSTER_SIZE,
+            is_sparse: true,
+            cluster_size: CLUSTER_SIZE,
+        };
+
+        assert_eq!(run.lcn(), None);
+        assert_eq!(run.to_string(), "4:-1:16");
+    }
+}
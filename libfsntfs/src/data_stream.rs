@@ -0,0 +1,318 @@
+use crate::error::Error;
+use crate::ffi_error::LibfsntfsErrorRefMut;
+use crate::file_entry::FileEntry;
+use libfsntfs_sys::{off64_t, size64_t, SEEK_CUR, SEEK_END, SEEK_SET};
+use libyal_rs_common::ffi::AsTypeRef;
+use std::convert::TryFrom;
+use std::ffi::c_void;
+use std::fmt::{self, Debug, Formatter};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::ops::Deref;
+use std::os::raw::c_int;
+use std::ptr;
+
+#[repr(C)]
+pub struct __DataStream(isize);
+
+pub type DataStreamRefMut = *mut __DataStream;
+pub type DataStreamRef = *const __DataStream;
+
+/// An alternate (named) data stream of a file entry.
+///
+/// libfsntfs has no data stream handle for the default (unnamed) data stream. A `DataStream`
+/// of it, see `FileEntry::open_data_stream_by_attribute_index`, holds a null pointer and reads
+/// through the file entry instead, keeping its own offset in the last field.
+#[repr(C)]
+pub struct DataStream<'a>(DataStreamRefMut, Parent<'a>, u64);
+
+/// The file entry of a data stream, which the stream owns if it was opened by path, see
+/// `Volume::get_data_stream_by_path`.
+enum Parent<'a> {
+    Borrowed(&'a FileEntry<'a>),
+    Owned(FileEntry<'a>),
+}
+
+impl<'a> Deref for Parent<'a> {
+    type Target = FileEntry<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Parent::Borrowed(file_entry) => file_entry,
+            Parent::Owned(file_entry) => file_entry,
+        }
+    }
+}
+
+impl<'a> AsTypeRef for DataStream<'a> {
+    type Ref = DataStreamRef;
+    type RefMut = DataStreamRefMut;
+
+    #[inline]
+    fn as_type_ref(&self) -> Self::Ref {
+        // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
+        self.0 as *const _
+    }
+
+    #[inline]
+    fn as_type_ref_mut(&mut self) -> Self::RefMut {
+        self.0
+    }
+
+    #[inline]
+    fn as_raw(&mut self) -> *mut Self::RefMut {
+        &mut self.0 as *mut _
+    }
+}
+
+impl<'a> DataStream<'a> {
+    pub fn wrap_ptr(file_entry: &'a FileEntry<'a>, ptr: DataStreamRefMut) -> Self {
+        DataStream(ptr, Parent::Borrowed(file_entry), 0)
+    }
+
+    /// The default data stream of `file_entry`.
+    pub(crate) fn default_of(file_entry: &'a FileEntry<'a>) -> Self {
+        DataStream(ptr::null_mut(), Parent::Borrowed(file_entry), 0)
+    }
+
+    /// A data stream that keeps `file_entry` open for as long as it is, the default data stream
+    /// if `ptr` is null.
+    pub(crate) fn owning(file_entry: FileEntry<'a>, ptr: DataStreamRefMut) -> Self {
+        DataStream(ptr, Parent::Owned(file_entry), 0)
+    }
+
+    /// Whether this is the default data stream, rather than an alternate data stream.
+    pub fn is_default(&self) -> bool {
+        self.0.is_null()
+    }
+}
+
+impl<'a> Drop for DataStream<'a> {
+    fn drop(&mut self) {
+        use log::trace;
+
+        if self.is_default() {
+            return;
+        }
+
+        let mut error = ptr::null_mut();
+
+        trace!("Calling `libfsntfs_data_stream_free`");
+
+        unsafe {
+            libfsntfs_data_stream_free(self.as_raw(), &mut error);
+        }
+
+        debug_assert!(error.is_null(), "`libfsntfs_data_stream_free` failed!");
+    }
+}
+
+impl<'a> Debug for DataStream<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("DataStream")
+            .field("Name", &self.get_name().unwrap_or("".to_string()))
+            .field("Size", &self.get_size().unwrap_or(0))
+            .finish()
+    }
+}
+
+extern "C" {
+    pub fn libfsntfs_data_stream_free(
+        data_stream: *mut DataStreamRefMut,
+        error: *mut LibfsntfsErrorRefMut,
+    ) -> c_int;
+    pub fn libfsntfs_data_stream_get_utf8_name_size(
+        data_stream: DataStreamRef,
+        utf8_string_size: *mut usize,
+        error: *mut LibfsntfsErrorRefMut,
+    ) -> c_int;
+    pub fn libfsntfs_data_stream_get_utf8_name(
+        data_stream: DataStreamRef,
+        utf8_string: *mut u8,
+        utf8_string_size: usize,
+        error: *mut LibfsntfsErrorRefMut,
+    ) -> c_int;
+    pub fn libfsntfs_data_stream_read_buffer(
+        data_stream: DataStreamRef,
+        buffer: *mut c_void,
+        buffer_size: usize,
+        error: *mut LibfsntfsErrorRefMut,
+    ) -> isize;
+    pub fn libfsntfs_data_stream_read_buffer_at_offset(
+        data_stream: DataStreamRef,
+        buffer: *mut c_void,
+        buffer_size: usize,
+        offset: off64_t,
+        error: *mut LibfsntfsErrorRefMut,
+    ) -> isize;
+    pub fn libfsntfs_data_stream_seek_offset(
+        data_stream: DataStreamRef,
+        offset: off64_t,
+        whence: c_int,
+        error: *mut LibfsntfsErrorRefMut,
+    ) -> off64_t;
+    pub fn libfsntfs_data_stream_get_size(
+        data_stream: DataStreamRef,
+        size: *mut size64_t,
+        error: *mut LibfsntfsErrorRefMut,
+    ) -> c_int;
+}
+
+impl<'a> Read for DataStream<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, io::Error> {
+        if self.is_default() {
+            let read_count = self
+                .1
+                .read_at(self.2, buf)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("{}", e)))?;
+            self.2 += read_count as u64;
+
+            return Ok(read_count);
+        }
+
+        let mut error = ptr::null_mut();
+        let read_count = unsafe {
+            libfsntfs_data_stream_read_buffer(
+                self.as_type_ref(),
+                buf.as_mut_ptr() as *mut c_void,
+                buf.len(),
+                &mut error,
+            )
+        };
+
+        if read_count <= -1 {
+            let io_err = match Error::try_from(error) {
+                Ok(e) => io::Error::new(io::ErrorKind::Other, format!("{}", e)),
+                Err(_) => io::Error::new(
+                    io::ErrorKind::Other,
+                    "error while getting error information".to_string(),
+                ),
+            };
+
+            Err(io_err)
+        } else {
+            Ok(read_count as usize)
+        }
+    }
+}
+
+impl<'a> Seek for DataStream<'a> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, io::Error> {
+        if self.is_default() {
+            let size = self
+                .get_size()
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("{}", e)))?;
+
+            let (base, offset) = match pos {
+                SeekFrom::Start(offset) => (0, offset as i128),
+                SeekFrom::End(offset) => (size, offset as i128),
+                SeekFrom::Current(offset) => (self.2, offset as i128),
+            };
+
+            let position = base as i128 + offset;
+
+            if position < 0 || position > i64::max_value() as i128 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                ));
+            }
+
+            self.2 = position as u64;
+
+            return Ok(self.2);
+        }
+
+        let mut error = ptr::null_mut();
+
+        let (offset, whence) = match pos {
+            SeekFrom::Start(offset) => (offset as i64, SEEK_SET),
+            SeekFrom::End(offset) => (offset, SEEK_END),
+            SeekFrom::Current(offset) => (offset, SEEK_CUR),
+        };
+
+        let seek_pos = unsafe {
+            libfsntfs_data_stream_seek_offset(self.as_type_ref(), offset, whence as i32, &mut error)
+        };
+
+        if seek_pos <= -1 {
+            let io_err = match Error::try_from(error) {
+                Ok(e) => io::Error::new(io::ErrorKind::Other, format!("{}", e)),
+                Err(_) => io::Error::new(
+                    io::ErrorKind::Other,
+                    "error while getting error information".to_string(),
+                ),
+            };
+
+            Err(io_err)
+        } else {
+            Ok(seek_pos as u64)
+        }
+    }
+}
+
+impl<'a> DataStream<'a> {
+    /// Seeks back to the start of the data stream, to read it again. Equivalent to
+    /// `seek(SeekFrom::Start(0))`, like `std::io::Seek::rewind`.
+    pub fn rewind(&mut self) -> Result<(), Error> {
+        self.seek(SeekFrom::Start(0))
+            .map_err(|e| Error::Other(format!("Failed to rewind: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Retrieves the name of the data stream.
+    /// The default data stream has an empty name.
+    pub fn get_name(&self) -> Result<String, Error> {
+        if self.is_default() {
+            return Ok(String::new());
+        }
+
+        get_sized_utf8_string!(
+            self,
+            libfsntfs_data_stream_get_utf8_name_size,
+            libfsntfs_data_stream_get_utf8_name
+        )
+    }
+
+    /// Retrieves the size of the data stream.
+    pub fn get_size(&self) -> Result<u64, Error> {
+        if self.is_default() {
+            return self.1.get_size();
+        }
+
+        let mut size = 0;
+        let mut error = ptr::null_mut();
+
+        if unsafe { libfsntfs_data_stream_get_size(self.as_type_ref(), &mut size, &mut error) } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(size)
+        }
+    }
+
+    /// Reads data at a specific offset, without moving the current offset of the data stream.
+    pub fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<usize, Error> {
+        if self.is_default() {
+            return self.1.read_at(offset, buf);
+        }
+
+        let mut error = ptr::null_mut();
+
+        let read_count = unsafe {
+            libfsntfs_data_stream_read_buffer_at_offset(
+                self.as_type_ref(),
+                buf.as_mut_ptr() as *mut c_void,
+                buf.len(),
+                offset as off64_t,
+                &mut error,
+            )
+        };
+
+        if read_count <= -1 {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(read_count as usize)
+        }
+    }
+}
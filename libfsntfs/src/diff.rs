@@ -0,0 +1,218 @@
+//! Comparison of two images of the same volume, e.g. taken before and after an incident.
+use crate::error::Error;
+use crate::metadata::FileEntryMetadata;
+use crate::path_index::PathIndex;
+use crate::volume::{FileReference, MftEntryIndex, Volume};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// An entry that only exists in one of the compared volumes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffEntry {
+    pub file_reference: FileReference,
+    /// Path of the entry, `None` if it could not be resolved.
+    pub path: Option<PathBuf>,
+}
+
+/// An entry that exists in both volumes, but with a different size or timestamps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModifiedEntry {
+    /// Path of the entry in the second volume, `None` if it could not be resolved.
+    pub path: Option<PathBuf>,
+    pub before: FileEntryMetadata,
+    pub after: FileEntryMetadata,
+}
+
+/// The differences between two volumes, see `Volume::diff`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VolumeDiff {
+    pub added: Vec<DiffEntry>,
+    pub removed: Vec<DiffEntry>,
+    pub modified: Vec<ModifiedEntry>,
+}
+
+impl VolumeDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+type Snapshot = BTreeMap<MftEntryIndex, FileEntryMetadata>;
+
+fn is_modified(before: &FileEntryMetadata, after: &FileEntryMetadata) -> bool {
+    before.size != after.size
+        || before.creation_time != after.creation_time
+        || before.modification_time != after.modification_time
+        || before.access_time != after.access_time
+        || before.entry_modification_time != after.entry_modification_time
+}
+
+/// Matches the entries of two snapshots by file reference. An MFT entry that was reused for
+/// another file (i.e. its sequence number changed) counts as removed and added.
+fn diff_snapshots<'s>(
+    before: &'s Snapshot,
+    after: &'s Snapshot,
+) -> (
+    Vec<&'s FileEntryMetadata>,
+    Vec<&'s FileEntryMetadata>,
+    Vec<(&'s FileEntryMetadata, &'s FileEntryMetadata)>,
+) {
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    let mut modified = Vec::new();
+
+    for (idx, old) in before {
+        match after.get(idx) {
+            Some(new) if new.file_reference == old.file_reference => {
+                if is_modified(old, new) {
+                    modified.push((old, new));
+                }
+            }
+            Some(new) => {
+                removed.push(old);
+                added.push(new);
+            }
+            None => removed.push(old),
+        }
+    }
+
+    for (idx, new) in after {
+        if !before.contains_key(idx) {
+            added.push(new);
+        }
+    }
+
+    added.sort_by_key(|metadata| metadata.file_reference.mft_entry_index());
+
+    (added, removed, modified)
+}
+
+impl Volume {
+    /// Compares the allocated entries of this volume to those of `other`, matching them by file
+    /// reference, and reports which entries were added, removed or modified in `other`.
+    ///
+    /// Entries are considered modified when their size or `$STANDARD_INFORMATION` timestamps
+    /// differ. Entries that cannot be read are left out.
+    pub fn diff(&self, other: &Volume) -> Result<VolumeDiff, Error> {
+        let before = self.snapshot()?;
+        let after = other.snapshot()?;
+
+        let before_paths = self.build_path_index()?;
+        let after_paths = other.build_path_index()?;
+
+        let diff_entry = |metadata: &FileEntryMetadata, paths: &PathIndex| DiffEntry {
+            file_reference: metadata.file_reference,
+            path: paths.resolve(metadata.file_reference.0),
+        };
+
+        let (added, removed, modified) = diff_snapshots(&before, &after);
+
+        Ok(VolumeDiff {
+            added: added
+                .into_iter()
+                .map(|metadata| diff_entry(metadata, &after_paths))
+                .collect(),
+            removed: removed
+                .into_iter()
+                .map(|metadata| diff_entry(metadata, &before_paths))
+                .collect(),
+            modified: modified
+                .into_iter()
+                .map(|(old, new)| ModifiedEntry {
+                    path: after_paths.resolve(new.file_reference.0),
+                    before: old.clone(),
+                    after: new.clone(),
+                })
+                .collect(),
+        })
+    }
+
+    fn snapshot(&self) -> Result<Snapshot, Error> {
+        let mut snapshot = Snapshot::new();
+
+        for entry in self.iter_entries()? {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(_) => continue,
+            };
+
+            if let Ok(true) = entry.is_allocated() {
+                if let Ok(metadata) = FileEntryMetadata::from_entry(&entry) {
+                    snapshot.insert(metadata.file_reference.mft_entry_index(), metadata);
+                }
+            }
+        }
+
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    fn metadata(idx: u64, sequence: u64, size: u64) -> FileEntryMetadata {
+        FileEntryMetadata {
+            file_reference: FileReference(idx | (sequence << 48)),
+            size,
+            file_attribute_flags: 0,
+            is_dir: false,
+            creation_time: None,
+            modification_time: None,
+            access_time: None,
+            entry_modification_time: None,
+            attribute_types: vec![],
+        }
+    }
+
+    fn snapshot(entries: Vec<FileEntryMetadata>) -> Snapshot {
+        entries
+            .into_iter()
+            .map(|metadata| (metadata.file_reference.mft_entry_index(), metadata))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_sample_volume_against_itself() {
+        let before = sample_volume().unwrap();
+        let after = sample_volume().unwrap();
+
+        assert!(before.diff(&after).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let before = snapshot(vec![
+            metadata(64, 1, 10),
+            metadata(65, 1, 10),
+            metadata(66, 1, 10),
+            metadata(67, 1, 10),
+        ]);
+        let after = snapshot(vec![
+            metadata(64, 1, 10),
+            metadata(65, 1, 20),
+            metadata(66, 2, 10),
+            metadata(68, 1, 10),
+        ]);
+
+        let (added, removed, modified) = diff_snapshots(&before, &after);
+
+        let references = |entries: Vec<&FileEntryMetadata>| {
+            entries
+                .iter()
+                .map(|metadata| metadata.file_reference)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            references(added),
+            vec![after[&66].file_reference, after[&68].file_reference]
+        );
+        assert_eq!(
+            references(removed),
+            vec![before[&66].file_reference, before[&67].file_reference]
+        );
+        assert_eq!(modified, vec![(&before[&65], &after[&65])]);
+    }
+}
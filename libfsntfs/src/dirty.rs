@@ -0,0 +1,150 @@
+//! Detection of volumes that were not cleanly unmounted, from the `$Volume` system file.
+use crate::attribute::AttributeType;
+use crate::error::Error;
+use crate::volume::{AccessMode, MftEntryIndex, Volume};
+use bitflags::bitflags;
+use std::fmt::{self, Display, Formatter};
+
+/// MFT entry index of `$Volume`.
+const VOLUME_MFT_ENTRY_INDEX: MftEntryIndex = 3;
+
+bitflags! {
+    /// Flags of `$VOLUME_INFORMATION`, see `Volume::get_volume_flags`.
+    pub struct VolumeFlags: u16 {
+        /// Set while the volume is mounted, or when it needs `chkdsk` to recover.
+        const DIRTY = 0x0001;
+        const RESIZE_LOG_FILE = 0x0002;
+        const UPGRADE_ON_MOUNT = 0x0004;
+        const MOUNTED_ON_NT4 = 0x0008;
+        const DELETE_USN_UNDERWAY = 0x0010;
+        const REPAIR_OBJECT_IDS = 0x0020;
+        const CHKDSK_UNDERWAY = 0x4000;
+        const MODIFIED_BY_CHKDSK = 0x8000;
+    }
+}
+
+const FLAG_NAMES: &[(VolumeFlags, &str)] = &[
+    (VolumeFlags::DIRTY, "Dirty"),
+    (VolumeFlags::RESIZE_LOG_FILE, "ResizeLogFile"),
+    (VolumeFlags::UPGRADE_ON_MOUNT, "UpgradeOnMount"),
+    (VolumeFlags::MOUNTED_ON_NT4, "MountedOnNT4"),
+    (VolumeFlags::DELETE_USN_UNDERWAY, "DeleteUsnUnderway"),
+    (VolumeFlags::REPAIR_OBJECT_IDS, "RepairObjectIds"),
+    (VolumeFlags::CHKDSK_UNDERWAY, "ChkdskUnderway"),
+    (VolumeFlags::MODIFIED_BY_CHKDSK, "ModifiedByChkdsk"),
+];
+
+impl VolumeFlags {
+    pub fn is_dirty(&self) -> bool {
+        self.contains(VolumeFlags::DIRTY)
+    }
+}
+
+/// Renders the set flags as `Dirty, ChkdskUnderway`, in ascending order of their values.
+impl Display for VolumeFlags {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        let names: Vec<&str> = FLAG_NAMES
+            .iter()
+            .filter(|(flag, _)| self.contains(*flag))
+            .map(|(_, name)| *name)
+            .collect();
+
+        write!(f, "{}", names.join(", "))
+    }
+}
+
+impl Volume {
+    /// Opens a volume like `open`, but fails with `Error::DirtyVolume` if the dirty bit of the
+    /// volume is set, i.e. if its metadata may be inconsistent. Pass `allow_dirty` to open it
+    /// regardless.
+    pub fn open_strict(
+        filename: impl AsRef<str>,
+        mode: AccessMode,
+        allow_dirty: bool,
+    ) -> Result<Self, Error> {
+        let volume = Volume::open(filename, mode)?;
+
+        if !allow_dirty && volume.is_dirty()? {
+            return Err(Error::DirtyVolume);
+        }
+
+        Ok(volume)
+    }
+
+    /// Determines if the dirty bit of the volume is set, i.e. it was not cleanly unmounted.
+    pub fn is_dirty(&self) -> Result<bool, Error> {
+        Ok(self.get_volume_flags()?.is_dirty())
+    }
+
+    /// Retrieves the flags of `$VOLUME_INFORMATION`. Flags unknown to `VolumeFlags` are dropped.
+    pub fn get_volume_flags(&self) -> Result<VolumeFlags, Error> {
+        let volume_entry = self.get_file_entry_by_mft_idx(VOLUME_MFT_ENTRY_INDEX)?;
+
+        for attribute in volume_entry.iter_attributes()? {
+            let attribute = attribute?;
+
+            if attribute.get_type()? == AttributeType::VolumeInformation {
+                return Ok(VolumeFlags::from_bits_truncate(
+                    attribute.get_volume_information_flags()?,
+                ));
+            }
+        }
+
+        Err(Error::not_found("$VOLUME_INFORMATION"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+    use std::fs;
+    use tempdir::TempDir;
+
+    /// Offset of the flags of `$VOLUME_INFORMATION` in the sample image.
+    const SAMPLE_VOLUME_FLAGS_OFFSET: usize = 2_745_330;
+
+    #[test]
+    fn test_clean_volume() {
+        let volume = sample_volume().unwrap();
+        assert!(!volume.is_dirty().unwrap());
+        assert!(!volume.get_volume_flags().unwrap().is_dirty());
+
+        assert!(Volume::open_strict(sample_volume_path(), AccessMode::Read, false).is_ok());
+    }
+
+    #[test]
+    fn test_dirty_volume() {
+        let dir = TempDir::new("dirty_volume").unwrap();
+        let path = dir.path().join("dirty.dd");
+
+        let mut image = fs::read(sample_volume_path()).unwrap();
+        image[SAMPLE_VOLUME_FLAGS_OFFSET] |= VolumeFlags::DIRTY.bits() as u8;
+        fs::write(&path, image).unwrap();
+
+        let path = path.to_str().unwrap();
+        let volume = Volume::open(path, AccessMode::Read).unwrap();
+
+        assert!(volume.is_dirty().unwrap());
+        assert!(volume
+            .get_volume_flags()
+            .unwrap()
+            .contains(VolumeFlags::DIRTY));
+
+        match Volume::open_strict(path, AccessMode::Read, false) {
+            Err(Error::DirtyVolume) => {}
+            other => panic!("expected a dirty volume error, got {:?}", other.err()),
+        }
+
+        assert!(Volume::open_strict(path, AccessMode::Read, true).is_ok());
+    }
+
+    #[test]
+    fn test_display_volume_flags() {
+        let flags = VolumeFlags::DIRTY | VolumeFlags::CHKDSK_UNDERWAY;
+
+        assert!(flags.is_dirty());
+        assert_eq!(flags.to_string(), "Dirty, ChkdskUnderway");
+        assert_eq!(VolumeFlags::empty().to_string(), "");
+    }
+}
@@ -1,4 +1,5 @@
 use libyal_rs_common::ffi::AsTypeRef;
+use crate::error_core::FfiError;
 use crate::ffi_error::{LibfsntfsError, LibfsntfsErrorRef};
 use libfsntfs_sys::*;
 use failure::Fail;
@@ -11,20 +12,99 @@ use std::os::raw::c_char;
 use std::str::Utf8Error;
 use std::string::FromUtf8Error;
 
+pub use crate::error_core::{codes, ErrorCode};
+
 #[derive(Fail, Debug)]
 pub enum Error {
     #[fail(display = "Failed to convert date {}", _0)]
     FailedToConvertDate(#[cause] chrono::ParseError),
     #[fail(display = "AttributeType has no variant {}", _0)]
     UnknownAttributeEnumVariant(u32),
+    #[fail(display = "ReparsePointTag has no variant {:#x}", _0)]
+    UnknownReparsePointTagEnumVariant(u32),
+    #[fail(display = "NameNamespace has no variant {}", _0)]
+    UnknownNameNamespaceEnumVariant(u8),
+    #[fail(display = "{} has no variant named {:?}", _1, _0)]
+    UnknownEnumVariantName(String, &'static str),
     #[fail(display = "String is invalid UTF-8: {}", _0)]
     StringContainsInvalidUTF8(#[cause] FromUtf8Error),
     #[fail(display = "String is invalid UTF-8: {}", _0)]
     FailedToConvertFromBytes(#[cause] FromBytesWithNulError),
     #[fail(display = "String contains NUL where is it not allowed: {}", _0)]
     StringContainsNul(#[cause] NulError),
+    #[fail(display = "{} was not found", _0)]
+    NotFound(String),
+    #[fail(display = "The volume is dirty and may be inconsistent")]
+    DirtyVolume,
+    #[fail(
+        display = "Access to {} was denied, administrator privileges are required",
+        _0
+    )]
+    PermissionDenied(String),
     #[fail(display = "An FFI error has occurred: {}", _0)]
-    FFI(String),
+    FFI(String, ErrorCode),
     #[fail(display = "An unexpected error has occurred: {}", _0)]
     Other(String),
 }
+
+impl From<FfiError> for Error {
+    fn from(error: FfiError) -> Self {
+        Error::FFI(error.message(), error.code)
+    }
+}
+
+impl Error {
+    /// Retrieves the libcerror `(domain, code)` of errors raised by libfsntfs, see `codes`.
+    pub fn code(&self) -> Option<(u32, i32)> {
+        match self {
+            Error::FFI(_, code) => Some((code.domain, code.code)),
+            _ => None,
+        }
+    }
+
+    pub fn not_found(name: impl Into<String>) -> Self {
+        Error::NotFound(name.into())
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            Error::NotFound(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Lists the messages of each level of nested errors, innermost first.
+    ///
+    /// The backtrace of an FFI error holds a message per function it was passed through, other
+    /// errors are followed by their causes.
+    pub fn full_chain(&self) -> Vec<String> {
+        if let Error::FFI(backtrace, _) = self {
+            return backtrace
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_owned)
+                .collect();
+        }
+
+        let mut chain = vec![self.to_string()];
+        let mut cause = self.cause();
+
+        while let Some(error) = cause {
+            chain.push(error.to_string());
+            cause = error.cause();
+        }
+
+        chain
+    }
+
+    /// Whether the error stems from converting a string between encodings, as opposed to e.g.
+    /// failing to read it.
+    pub fn is_conversion_error(&self) -> bool {
+        match self {
+            Error::FFI(_, code) => code.domain == codes::LIBCERROR_ERROR_DOMAIN_CONVERSION,
+            Error::StringContainsInvalidUTF8(_) => true,
+            _ => false,
+        }
+    }
+}
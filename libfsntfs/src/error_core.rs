@@ -0,0 +1,231 @@
+//! The representation of libcerror errors behind `Error::FFI`, which only needs `core` and
+//! `alloc`, so that it also builds without `std`. Displaying an `FfiError` needs the `std`
+//! feature.
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ffi::{c_char, c_int, c_void};
+#[cfg(feature = "std")]
+use std::fmt::{self, Display, Formatter};
+
+/// Numeric libcerror codes, for matching on `Error::code`.
+pub mod codes {
+    pub const LIBCERROR_ERROR_DOMAIN_ARGUMENTS: u32 = b'a' as u32;
+    pub const LIBCERROR_ERROR_DOMAIN_CONVERSION: u32 = b'c' as u32;
+    pub const LIBCERROR_ERROR_DOMAIN_COMPRESSION: u32 = b'C' as u32;
+    pub const LIBCERROR_ERROR_DOMAIN_IO: u32 = b'I' as u32;
+    pub const LIBCERROR_ERROR_DOMAIN_INPUT: u32 = b'i' as u32;
+    pub const LIBCERROR_ERROR_DOMAIN_MEMORY: u32 = b'm' as u32;
+    pub const LIBCERROR_ERROR_DOMAIN_OUTPUT: u32 = b'o' as u32;
+    pub const LIBCERROR_ERROR_DOMAIN_RUNTIME: u32 = b'r' as u32;
+
+    pub const LIBCERROR_ARGUMENT_ERROR_GENERIC: i32 = 0;
+    pub const LIBCERROR_ARGUMENT_ERROR_INVALID_VALUE: i32 = 1;
+    pub const LIBCERROR_ARGUMENT_ERROR_VALUE_LESS_THAN_ZERO: i32 = 2;
+    pub const LIBCERROR_ARGUMENT_ERROR_VALUE_ZERO_OR_LESS: i32 = 3;
+    pub const LIBCERROR_ARGUMENT_ERROR_VALUE_EXCEEDS_MAXIMUM: i32 = 4;
+    pub const LIBCERROR_ARGUMENT_ERROR_VALUE_TOO_SMALL: i32 = 5;
+    pub const LIBCERROR_ARGUMENT_ERROR_VALUE_TOO_LARGE: i32 = 6;
+    pub const LIBCERROR_ARGUMENT_ERROR_VALUE_OUT_OF_BOUNDS: i32 = 7;
+    pub const LIBCERROR_ARGUMENT_ERROR_UNSUPPORTED_VALUE: i32 = 8;
+    pub const LIBCERROR_ARGUMENT_ERROR_CONFLICTING_VALUE: i32 = 9;
+
+    pub const LIBCERROR_IO_ERROR_GENERIC: i32 = 0;
+    pub const LIBCERROR_IO_ERROR_OPEN_FAILED: i32 = 1;
+    pub const LIBCERROR_IO_ERROR_CLOSE_FAILED: i32 = 2;
+    pub const LIBCERROR_IO_ERROR_SEEK_FAILED: i32 = 3;
+    pub const LIBCERROR_IO_ERROR_READ_FAILED: i32 = 4;
+    pub const LIBCERROR_IO_ERROR_WRITE_FAILED: i32 = 5;
+    pub const LIBCERROR_IO_ERROR_ACCESS_DENIED: i32 = 6;
+    pub const LIBCERROR_IO_ERROR_INVALID_RESOURCE: i32 = 7;
+    pub const LIBCERROR_IO_ERROR_IOCTL_FAILED: i32 = 8;
+    pub const LIBCERROR_IO_ERROR_UNLINK_FAILED: i32 = 9;
+
+    pub const LIBCERROR_RUNTIME_ERROR_GENERIC: i32 = 0;
+    pub const LIBCERROR_RUNTIME_ERROR_VALUE_MISSING: i32 = 1;
+    pub const LIBCERROR_RUNTIME_ERROR_VALUE_ALREADY_SET: i32 = 2;
+    pub const LIBCERROR_RUNTIME_ERROR_INITIALIZE_FAILED: i32 = 3;
+    pub const LIBCERROR_RUNTIME_ERROR_RESIZE_FAILED: i32 = 4;
+    pub const LIBCERROR_RUNTIME_ERROR_FINALIZE_FAILED: i32 = 5;
+    pub const LIBCERROR_RUNTIME_ERROR_GET_FAILED: i32 = 6;
+    pub const LIBCERROR_RUNTIME_ERROR_SET_FAILED: i32 = 7;
+    pub const LIBCERROR_RUNTIME_ERROR_APPEND_FAILED: i32 = 8;
+    pub const LIBCERROR_RUNTIME_ERROR_COPY_FAILED: i32 = 9;
+    pub const LIBCERROR_RUNTIME_ERROR_REMOVE_FAILED: i32 = 10;
+    pub const LIBCERROR_RUNTIME_ERROR_PRINT_FAILED: i32 = 11;
+    pub const LIBCERROR_RUNTIME_ERROR_VALUE_OUT_OF_BOUNDS: i32 = 12;
+    pub const LIBCERROR_RUNTIME_ERROR_VALUE_EXCEEDS_MAXIMUM: i32 = 13;
+    pub const LIBCERROR_RUNTIME_ERROR_UNSUPPORTED_VALUE: i32 = 14;
+    pub const LIBCERROR_RUNTIME_ERROR_ABORT_REQUESTED: i32 = 15;
+}
+
+/// Domain and code of the libcerror error behind an `Error::FFI`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorCode {
+    pub domain: u32,
+    pub code: i32,
+}
+
+impl ErrorCode {
+    /// Reads the domain and code of a libcerror error.
+    ///
+    /// libcerror does not export accessors, but its error structure starts with the domain and
+    /// code.
+    ///
+    /// # Safety
+    ///
+    /// `error` must point to a libcerror error.
+    pub unsafe fn from_raw(error: *const c_void) -> Self {
+        let fields = error as *const c_int;
+
+        ErrorCode {
+            domain: *fields as u32,
+            code: *fields.add(1),
+        }
+    }
+}
+
+/// A libcerror error copied out of libfsntfs, before it is converted into an `Error`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FfiError {
+    pub code: ErrorCode,
+    /// The backtrace printed by libcerror, without the terminating NUL, `None` if it could not
+    /// be printed.
+    pub backtrace: Option<Vec<u8>>,
+}
+
+impl FfiError {
+    pub fn new(code: ErrorCode, backtrace: Option<Vec<u8>>) -> Self {
+        FfiError { code, backtrace }
+    }
+
+    /// The backtrace decoded as UTF-8, with invalid sequences replaced.
+    pub fn backtrace_lossy(&self) -> Option<String> {
+        self.backtrace
+            .as_ref()
+            .map(|backtrace| String::from_utf8_lossy(backtrace).into_owned())
+    }
+
+    /// The backtrace, or a placeholder if it could not be printed.
+    pub(crate) fn message(&self) -> String {
+        self.backtrace_lossy()
+            .unwrap_or_else(|| String::from("Failed to print error"))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Display for FfiError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.message())
+    }
+}
+
+/// Size of the buffer errors are first printed into.
+const INITIAL_ERROR_BUFFER_SIZE: usize = 1024;
+
+/// Size past which printing an error is given up on.
+const MAX_ERROR_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Calls a libcerror style `sprint` function with buffers of growing size, since it fails when
+/// the string does not fit without reporting the size it needs.
+pub(crate) fn print_to_buffer(mut print: impl FnMut(&mut [c_char]) -> c_int) -> Option<Vec<u8>> {
+    let mut buffer = vec![0; INITIAL_ERROR_BUFFER_SIZE];
+
+    loop {
+        if print(&mut buffer) != -1 {
+            return Some(
+                buffer
+                    .iter()
+                    .take_while(|c| **c != 0)
+                    .map(|c| *c as u8)
+                    .collect(),
+            );
+        }
+
+        if buffer.len() >= MAX_ERROR_BUFFER_SIZE {
+            return None;
+        }
+
+        buffer = vec![0; buffer.len() * 2];
+    }
+}
+
+// Except for `Display`, these must pass with `cargo test --no-default-features` too.
+#[cfg(test)]
+mod tests {
+    use super::codes::*;
+    use super::*;
+
+    #[test]
+    fn test_ffi_error_from_raw() {
+        let fields: [c_int; 2] = [
+            LIBCERROR_ERROR_DOMAIN_IO as c_int,
+            LIBCERROR_IO_ERROR_READ_FAILED,
+        ];
+        let code = unsafe { ErrorCode::from_raw(fields.as_ptr() as *const c_void) };
+
+        let backtrace = print_to_buffer(|buffer| {
+            for (dst, src) in buffer.iter_mut().zip(b"unable to read") {
+                *dst = *src as c_char;
+            }
+
+            14
+        });
+
+        let error = FfiError::new(code, backtrace);
+
+        assert_eq!(
+            error.code,
+            ErrorCode {
+                domain: LIBCERROR_ERROR_DOMAIN_IO,
+                code: LIBCERROR_IO_ERROR_READ_FAILED,
+            }
+        );
+        assert_eq!(error.backtrace_lossy().unwrap(), "unable to read");
+        assert_eq!(FfiError::new(code, None).backtrace_lossy(), None);
+    }
+
+    #[test]
+    fn test_print_to_buffer_grows_buffer() {
+        let message = [b'x'; 5000];
+        let mut calls = 0;
+
+        let printed = print_to_buffer(|buffer| {
+            calls += 1;
+
+            if buffer.len() <= message.len() {
+                return -1;
+            }
+
+            for (dst, src) in buffer.iter_mut().zip(message.iter()) {
+                *dst = *src as c_char;
+            }
+
+            message.len() as c_int
+        });
+
+        assert_eq!(printed.unwrap(), &message[..]);
+        assert_eq!(calls, 4);
+        assert_eq!(print_to_buffer(|_| -1), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_display_ffi_error() {
+        use std::string::ToString;
+
+        let code = ErrorCode {
+            domain: LIBCERROR_ERROR_DOMAIN_IO,
+            code: LIBCERROR_IO_ERROR_READ_FAILED,
+        };
+
+        assert_eq!(
+            FfiError::new(code, Some(b"unable to read".to_vec())).to_string(),
+            "unable to read"
+        );
+        assert_eq!(
+            FfiError::new(code, None).to_string(),
+            "Failed to print error"
+        );
+    }
+}
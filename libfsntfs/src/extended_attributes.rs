@@ -0,0 +1,79 @@
+use crate::error::Error;
+use crate::mft::{read_u16, read_u32};
+
+/// Parses the value of an `$EA` attribute into name/value pairs.
+pub(crate) fn parse_extended_attributes(data: &[u8]) -> Result<Vec<(String, Vec<u8>)>, Error> {
+    let malformed = |offset: usize| Error::Other(format!("$EA entry at {} is malformed", offset));
+    let mut extended_attributes = Vec::new();
+    let mut offset = 0;
+
+    while offset < data.len() {
+        let next_entry_offset = read_u32(data, offset).ok_or_else(|| malformed(offset))? as usize;
+        let name_size = *data.get(offset + 5).ok_or_else(|| malformed(offset))? as usize;
+        let value_size = read_u16(data, offset + 6).ok_or_else(|| malformed(offset))? as usize;
+
+        let name_start = offset + 8;
+        // The name is followed by a NUL terminator.
+        let value_start = name_start + name_size + 1;
+
+        let name = data
+            .get(name_start..name_start + name_size)
+            .ok_or_else(|| malformed(offset))?;
+        let value = data
+            .get(value_start..value_start + value_size)
+            .ok_or_else(|| malformed(offset))?;
+
+        let name = String::from_utf8(name.to_vec()).map_err(Error::StringContainsInvalidUTF8)?;
+        extended_attributes.push((name, value.to_vec()));
+
+        if next_entry_offset == 0 {
+            break;
+        }
+
+        offset += next_entry_offset;
+    }
+
+    Ok(extended_attributes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_extended_attributes() {
+        let mut data = vec![];
+
+        // $LXUID = 1000
+        data.extend_from_slice(&20_u32.to_le_bytes());
+        data.extend_from_slice(&[0, 6]);
+        data.extend_from_slice(&4_u16.to_le_bytes());
+        data.extend_from_slice(b"$LXUID\0");
+        data.extend_from_slice(&1000_u32.to_le_bytes());
+        data.push(0);
+
+        // $LXGID = 1000, last entry
+        data.extend_from_slice(&0_u32.to_le_bytes());
+        data.extend_from_slice(&[0, 6]);
+        data.extend_from_slice(&4_u16.to_le_bytes());
+        data.extend_from_slice(b"$LXGID\0");
+        data.extend_from_slice(&1000_u32.to_le_bytes());
+
+        let extended_attributes = parse_extended_attributes(&data).unwrap();
+
+        assert_eq!(
+            extended_attributes,
+            vec![
+                ("$LXUID".to_string(), 1000_u32.to_le_bytes().to_vec()),
+                ("$LXGID".to_string(), 1000_u32.to_le_bytes().to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_truncated_extended_attributes_fails() {
+        let data = [12, 0, 0, 0, 0, 6, 4, 0, b'$', b'L'];
+
+        assert!(parse_extended_attributes(&data).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use crate::error::Error;
+use bitflags::bitflags;
+use std::ops::Range;
+
+bitflags! {
+    /// Flags of a data extent, as reported by libfsntfs.
+    pub struct ExtentFlags: u32 {
+        const IS_SPARSE = 0x0000_0001;
+        const IS_COMPRESSED = 0x0000_0002;
+    }
+}
+
+/// A run of the default data stream of a file entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Extent {
+    /// Offset of the extent in bytes, relative to the start of the volume.
+    pub offset: u64,
+    /// Size of the extent in bytes.
+    pub size: u64,
+    pub flags: ExtentFlags,
+}
+
+impl Extent {
+    /// Creates an extent from the values reported by libfsntfs, which stores offsets as `off64_t`.
+    /// Fails if the offset is negative, or if the extent ends beyond `u64::MAX`.
+    pub fn from_raw(offset: i64, size: u64, flags: u32) -> Result<Self, Error> {
+        if offset < 0 {
+            return Err(Error::Other(format!(
+                "Extent has a negative offset {}",
+                offset
+            )));
+        }
+
+        let extent = Extent {
+            offset: offset as u64,
+            size,
+            flags: ExtentFlags::from_bits_truncate(flags),
+        };
+
+        extent.physical_range()?;
+
+        Ok(extent)
+    }
+
+    /// Range of the extent in bytes, relative to the start of the volume. `None` for sparse
+    /// extents, which are not backed by clusters.
+    pub fn physical_range(&self) -> Result<Option<Range<u64>>, Error> {
+        if self.is_sparse() {
+            return Ok(None);
+        }
+
+        match self.offset.checked_add(self.size) {
+            Some(end) => Ok(Some(self.offset..end)),
+            None => Err(Error::Other(format!(
+                "Extent of {} bytes at offset {} overflows",
+                self.size, self.offset
+            ))),
+        }
+    }
+
+    pub fn is_sparse(&self) -> bool {
+        self.flags.contains(ExtentFlags::IS_SPARSE)
+    }
+
+    pub fn is_compressed(&self) -> bool {
+        self.flags.contains(ExtentFlags::IS_COMPRESSED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_raw() {
+        let extent = Extent::from_raw(4096, 1024, 0).unwrap();
+
+        assert_eq!(extent.physical_range().unwrap(), Some(4096..5120));
+        assert!(Extent::from_raw(0, u64::MAX, 0).is_ok());
+    }
+
+    #[test]
+    fn test_from_raw_rejects_invalid_values() {
+        assert!(Extent::from_raw(-1, 512, 0).is_err());
+        assert!(Extent::from_raw(i64::MIN, 0, 0).is_err());
+        assert!(Extent::from_raw(i64::MAX, u64::MAX, 0).is_err());
+        assert!(Extent::from_raw(1, u64::MAX, 0).is_err());
+    }
+
+    #[test]
+    fn test_physical_range_overflow() {
+        let extent = Extent {
+            offset: u64::MAX - 1,
+            size: 2,
+            flags: ExtentFlags::empty(),
+        };
+        assert!(extent.physical_range().is_err());
+
+        // Sparse extents have no physical location, regardless of their offset.
+        let sparse = Extent {
+            flags: ExtentFlags::IS_SPARSE,
+            ..extent
+        };
+        assert_eq!(sparse.physical_range().unwrap(), None);
+    }
+}
@@ -0,0 +1,212 @@
+//! Bulk extraction of file content to the host file system.
+use crate::error::Error;
+use crate::file_entry::escape_path_component;
+use crate::volume::{MftEntryIndex, Volume};
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+
+/// Outcome of extracting a single entry with `Volume::extract_entries`.
+#[derive(Debug)]
+pub struct ExtractResult {
+    pub mft_entry_index: MftEntryIndex,
+    /// The written file, or why the entry could not be extracted.
+    pub result: Result<PathBuf, Error>,
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> Error {
+    Error::Other(format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Builds the destination of an entry below `out_dir` from the names of its ancestors.
+///
+/// The names come straight from the image, so separators in them are escaped, and names that
+/// would not stay a single component below `out_dir`, such as `..`, are rejected.
+fn destination(
+    idx: MftEntryIndex,
+    components: &[OsString],
+    out_dir: &Path,
+) -> Result<PathBuf, Error> {
+    let mut path = out_dir.to_path_buf();
+
+    for component in components {
+        let name = escape_path_component(&component.to_string_lossy());
+        let mut parsed = Path::new(&name).components();
+
+        match (parsed.next(), parsed.next()) {
+            (Some(Component::Normal(_)), None) => path.push(&name),
+            _ => {
+                return Err(Error::Other(format!(
+                    "MFT entry {} has an unsafe path component {:?}",
+                    idx, component
+                )))
+            }
+        }
+    }
+
+    if components.is_empty() || !path.starts_with(out_dir) {
+        return Err(Error::Other(format!(
+            "MFT entry {} does not resolve to a path below {}",
+            idx,
+            out_dir.display()
+        )));
+    }
+
+    Ok(path)
+}
+
+impl Volume {
+    /// Writes the default data stream of each entry in `indices` below `out_dir`, at the path of
+    /// the entry relative to the root directory.
+    ///
+    /// If the destination already exists, or was written earlier in the same batch, the MFT
+    /// entry index is appended to the file name. Failures are reported per entry and do not stop
+    /// the extraction of the remaining entries.
+    pub fn extract_entries(
+        &self,
+        indices: &[MftEntryIndex],
+        out_dir: &Path,
+    ) -> Result<Vec<ExtractResult>, Error> {
+        fs::create_dir_all(out_dir).map_err(|e| io_error(out_dir, e))?;
+
+        let mut written = HashSet::with_capacity(indices.len());
+
+        Ok(indices
+            .iter()
+            .map(|&mft_entry_index| ExtractResult {
+                mft_entry_index,
+                result: self.extract_entry(mft_entry_index, out_dir, &mut written),
+            })
+            .collect())
+    }
+
+    fn extract_entry(
+        &self,
+        idx: MftEntryIndex,
+        out_dir: &Path,
+        written: &mut HashSet<PathBuf>,
+    ) -> Result<PathBuf, Error> {
+        let entry = self.get_file_entry_by_mft_idx(idx)?;
+
+        if !entry.has_default_data_stream()? {
+            return Err(Error::Other(format!(
+                "MFT entry {} has no default data stream",
+                idx
+            )));
+        }
+
+        let mut path = destination(idx, &entry.full_path_components()?, out_dir)?;
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| Error::Other(format!("MFT entry {} has no name", idx)))?
+            .to_string_lossy()
+            .into_owned();
+
+        if written.contains(&path) || path.exists() {
+            path.set_file_name(format!("{}_{}", file_name, idx));
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+        }
+
+        let mut file = File::create(&path).map_err(|e| io_error(&path, e))?;
+        entry.stream_default_data(|chunk| file.write_all(chunk).map_err(|e| io_error(&path, e)))?;
+
+        written.insert(path.clone());
+
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_extract_entries() {
+        let volume = sample_volume().unwrap();
+        let out_dir = TempDir::new("extract").unwrap();
+
+        let results = volume.extract_entries(&[27, 33], out_dir.path()).unwrap();
+
+        let sizes: Vec<u64> = results
+            .into_iter()
+            .map(|r| fs::metadata(r.result.unwrap()).unwrap().len())
+            .collect();
+
+        assert_eq!(sizes, vec![120, 2000]);
+        assert!(out_dir.path().join("file-r-1.dat").exists());
+    }
+
+    #[test]
+    fn test_extract_entries_reports_failures_per_entry() {
+        let volume = sample_volume().unwrap();
+        let out_dir = TempDir::new("extract").unwrap();
+
+        // The root directory has no default data stream.
+        let results = volume
+            .extract_entries(&[5, 27, 27], out_dir.path())
+            .unwrap();
+
+        assert!(results[0].result.is_err());
+
+        let first = results[1].result.as_ref().unwrap();
+        let second = results[2].result.as_ref().unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(second.file_name().unwrap(), "file-r-1.dat_27");
+    }
+
+    #[test]
+    fn test_extract_entries_rejects_parent_directory_names() {
+        let mut from = vec![12, 3];
+        from.extend(utf16_bytes("file-r-1.dat"));
+        let mut to = vec![2, 3];
+        to.extend(utf16_bytes(".."));
+        to.resize(from.len(), 0);
+
+        let volume = patched_sample_volume("extract-parent-name", &from, &to).unwrap();
+        let out_dir = TempDir::new("extract").unwrap();
+        let target = out_dir.path().join("target");
+
+        assert_eq!(
+            volume
+                .get_file_entry_by_mft_idx(27)
+                .unwrap()
+                .get_name()
+                .unwrap(),
+            ".."
+        );
+
+        let results = volume.extract_entries(&[27], &target).unwrap();
+
+        assert!(results[0].result.is_err());
+        assert_eq!(fs::read_dir(out_dir.path()).unwrap().count(), 1);
+        assert_eq!(fs::read_dir(&target).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_extract_entries_escapes_separators_in_names() {
+        let volume = patched_sample_volume(
+            "extract-separator-name",
+            &utf16_bytes("file-r-1.dat"),
+            &utf16_bytes("../../x1.dat"),
+        )
+        .unwrap();
+        let out_dir = TempDir::new("extract").unwrap();
+        let target = out_dir.path().join("target");
+
+        let results = volume.extract_entries(&[27], &target).unwrap();
+
+        assert_eq!(
+            results[0].result.as_ref().unwrap(),
+            &target.join("..%2F..%2Fx1.dat")
+        );
+        assert_eq!(fs::read_dir(out_dir.path()).unwrap().count(), 1);
+    }
+}
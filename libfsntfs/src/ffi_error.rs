@@ -1,4 +1,5 @@
-use crate::error::Error;
+use crate::error::{Error, ErrorCode};
+use crate::error_core::{print_to_buffer, FfiError};
 use libyal_rs_common::ffi::AsTypeRef;
 use libfsntfs_sys::FILE;
 use log::trace;
@@ -6,6 +7,8 @@ use std::convert::TryFrom;
 use std::ffi::{c_void, CStr};
 use std::fmt::{self, Display, Formatter};
 use std::mem;
+use std::os::raw::{c_char, c_int};
+use std::ptr;
 
 #[repr(C)]
 pub struct __LibfsntfsError(isize);
@@ -74,22 +77,63 @@ impl TryFrom<*mut __LibfsntfsError> for Error {
             return Err(Error::Other("Error pointer cannot be NULL".to_owned()));
         }
 
-        let mut buffer = vec![0; 1024];
+        let code = unsafe { ErrorCode::from_raw(err as *const c_void) };
 
-        let retcode =
-            unsafe { libfsntfs_error_backtrace_sprint(err as *const _, buffer.as_mut_ptr(), buffer.len()) };
+        let backtrace = print_to_buffer(|buffer| unsafe {
+            libfsntfs_error_backtrace_sprint(err as *const _, buffer.as_mut_ptr(), buffer.len())
+        });
 
-        if retcode == -1 {
-            Err(Error::FFI("Failed to print error".to_owned()))
+        let error = FfiError::new(code, backtrace);
+
+        if error.backtrace.is_some() {
+            Ok(Error::from(error))
         } else {
-            let repr = unsafe { CStr::from_ptr(buffer.as_ptr()) };
-            Ok(Error::FFI(repr.to_string_lossy().to_string()))
+            Err(Error::from(error))
+        }
+    }
+}
+
+/// A reusable error out-parameter for FFI calls made in hot loops.
+///
+/// The error is only converted into an `Error` (and freed) when a call actually fails, after
+/// which the scratch can be passed to the next call again.
+pub(crate) struct ErrorScratch(LibfsntfsErrorRefMut);
+
+impl ErrorScratch {
+    pub(crate) fn new() -> Self {
+        ErrorScratch(ptr::null_mut())
+    }
+
+    /// Pointer to pass as the `error` argument of an FFI call.
+    pub(crate) fn as_out_ptr(&mut self) -> *mut LibfsntfsErrorRefMut {
+        debug_assert!(self.0.is_null(), "pending error was not taken");
+        &mut self.0
+    }
+
+    /// Converts the error set by the last failed call, leaving the scratch empty.
+    pub(crate) fn take_error(&mut self) -> Error {
+        let error = mem::replace(&mut self.0, ptr::null_mut());
+        let converted = Error::try_from(error).unwrap_or_else(|e| e);
+
+        if !error.is_null() {
+            drop(LibfsntfsError(error));
+        }
+
+        converted
+    }
+}
+
+impl Drop for ErrorScratch {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            drop(LibfsntfsError(self.0));
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::error::Error;
     use crate::fixtures::*;
     use crate::volume::{AccessMode, Volume};
@@ -100,7 +144,7 @@ mod tests {
         assert!(result.is_err());
 
         if let Err(e) = result {
-            if let Error::FFI(s) = e {
+            if let Error::FFI(s, _) = e {
                 dbg!(&s);
                 assert!(
                     s.find("libfsntfs_volume_open").is_some(),
@@ -117,4 +161,68 @@ mod tests {
 
         panic!("Test should not reach here!");
     }
+
+    #[test]
+    fn test_full_chain_of_nested_error() {
+        let error = Volume::open("non-existent", AccessMode::Read)
+            .err()
+            .unwrap();
+        let chain = error.full_chain();
+
+        assert!(chain.len() > 1, "{:?}", chain);
+        assert!(chain
+            .iter()
+            .any(|message| message.contains("libfsntfs_volume_open")));
+        assert!(chain.iter().all(|message| !message.is_empty()));
+    }
+
+    #[test]
+    fn test_error_code_of_argument_error() {
+        use crate::error::codes::*;
+        use crate::volume::libfsntfs_volume_get_serial_number;
+        use libyal_rs_common::ffi::AsTypeRef;
+        use std::convert::TryFrom;
+        use std::ptr;
+
+        let volume = sample_volume().unwrap();
+        let mut error = ptr::null_mut();
+
+        let retcode = unsafe {
+            libfsntfs_volume_get_serial_number(volume.as_type_ref(), ptr::null_mut(), &mut error)
+        };
+        assert_eq!(retcode, -1);
+
+        let error = Error::try_from(error).unwrap();
+        assert_eq!(
+            error.code(),
+            Some((
+                LIBCERROR_ERROR_DOMAIN_ARGUMENTS,
+                LIBCERROR_ARGUMENT_ERROR_INVALID_VALUE
+            ))
+        );
+        assert_eq!(Error::Other("not an FFI error".to_owned()).code(), None);
+        assert!(!error.is_conversion_error());
+    }
+
+    #[test]
+    fn test_is_conversion_error() {
+        use crate::error::codes::*;
+        use crate::error::ErrorCode;
+
+        let conversion = ErrorCode {
+            domain: LIBCERROR_ERROR_DOMAIN_CONVERSION,
+            code: 0,
+        };
+        let io = ErrorCode {
+            domain: LIBCERROR_ERROR_DOMAIN_IO,
+            code: LIBCERROR_IO_ERROR_READ_FAILED,
+        };
+
+        assert!(Error::FFI("conversion".to_owned(), conversion).is_conversion_error());
+        assert!(!Error::FFI("read".to_owned(), io).is_conversion_error());
+        assert!(
+            Error::StringContainsInvalidUTF8(String::from_utf8(vec![0xff]).unwrap_err())
+                .is_conversion_error()
+        );
+    }
 }
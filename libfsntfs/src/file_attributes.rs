@@ -0,0 +1,104 @@
+use bitflags::bitflags;
+use std::fmt::{self, Display, Formatter};
+
+bitflags! {
+    /// File attribute flags, as stored in `$STANDARD_INFORMATION` and `$FILE_NAME`.
+    pub struct FileAttributeFlags: u32 {
+        const READ_ONLY = 0x0000_0001;
+        const HIDDEN = 0x0000_0002;
+        const SYSTEM = 0x0000_0004;
+        const DIRECTORY = 0x0000_0010;
+        const ARCHIVE = 0x0000_0020;
+        const DEVICE = 0x0000_0040;
+        const NORMAL = 0x0000_0080;
+        const TEMPORARY = 0x0000_0100;
+        const SPARSE_FILE = 0x0000_0200;
+        const REPARSE_POINT = 0x0000_0400;
+        const COMPRESSED = 0x0000_0800;
+        const OFFLINE = 0x0000_1000;
+        const NOT_CONTENT_INDEXED = 0x0000_2000;
+        const ENCRYPTED = 0x0000_4000;
+        const INTEGRITY_STREAM = 0x0000_8000;
+        const VIRTUAL = 0x0001_0000;
+        const NO_SCRUB_DATA = 0x0002_0000;
+        const HAS_FILE_NAME_INDEX = 0x1000_0000;
+        const HAS_VIEW_INDEX = 0x2000_0000;
+    }
+}
+
+const FLAG_NAMES: &[(FileAttributeFlags, &str)] = &[
+    (FileAttributeFlags::READ_ONLY, "ReadOnly"),
+    (FileAttributeFlags::HIDDEN, "Hidden"),
+    (FileAttributeFlags::SYSTEM, "System"),
+    (FileAttributeFlags::DIRECTORY, "Directory"),
+    (FileAttributeFlags::ARCHIVE, "Archive"),
+    (FileAttributeFlags::DEVICE, "Device"),
+    (FileAttributeFlags::NORMAL, "Normal"),
+    (FileAttributeFlags::TEMPORARY, "Temporary"),
+    (FileAttributeFlags::SPARSE_FILE, "SparseFile"),
+    (FileAttributeFlags::REPARSE_POINT, "ReparsePoint"),
+    (FileAttributeFlags::COMPRESSED, "Compressed"),
+    (FileAttributeFlags::OFFLINE, "Offline"),
+    (FileAttributeFlags::NOT_CONTENT_INDEXED, "NotContentIndexed"),
+    (FileAttributeFlags::ENCRYPTED, "Encrypted"),
+    (FileAttributeFlags::INTEGRITY_STREAM, "IntegrityStream"),
+    (FileAttributeFlags::VIRTUAL, "Virtual"),
+    (FileAttributeFlags::NO_SCRUB_DATA, "NoScrubData"),
+    (FileAttributeFlags::HAS_FILE_NAME_INDEX, "HasFileNameIndex"),
+    (FileAttributeFlags::HAS_VIEW_INDEX, "HasViewIndex"),
+];
+
+impl FileAttributeFlags {
+    /// Names of the set flags, in ascending order of their values.
+    pub fn to_string_list(&self) -> Vec<&'static str> {
+        FLAG_NAMES
+            .iter()
+            .filter(|(flag, _)| self.contains(*flag))
+            .map(|(_, name)| *name)
+            .collect()
+    }
+}
+
+/// Renders the set flags as `ReadOnly, Hidden, System`. With the alternate flag (`{:#}`),
+/// renders the short form used by `attrib`, e.g. `RHS`.
+impl Display for FileAttributeFlags {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        if f.alternate() {
+            let short_names = [
+                (FileAttributeFlags::READ_ONLY, 'R'),
+                (FileAttributeFlags::HIDDEN, 'H'),
+                (FileAttributeFlags::SYSTEM, 'S'),
+                (FileAttributeFlags::ARCHIVE, 'A'),
+            ];
+
+            for (flag, letter) in short_names.iter() {
+                if self.contains(*flag) {
+                    write!(f, "{}", letter)?;
+                }
+            }
+
+            return Ok(());
+        }
+
+        write!(f, "{}", self.to_string_list().join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_string_list() {
+        let flags = FileAttributeFlags::READ_ONLY | FileAttributeFlags::HIDDEN;
+
+        assert_eq!(flags.to_string_list(), vec!["ReadOnly", "Hidden"]);
+        assert_eq!(flags.to_string(), "ReadOnly, Hidden");
+        assert_eq!(format!("{:#}", flags), "RH");
+    }
+
+    #[test]
+    fn test_empty_renders_nothing() {
+        assert_eq!(FileAttributeFlags::empty().to_string(), "");
+    }
+}
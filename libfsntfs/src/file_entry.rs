@@ -1,31 +1,69 @@
 use chrono::prelude::*;
 
-use crate::attribute::{Attribute, AttributeRef, AttributeRefMut};
+use crate::attribute::{
+    Attribute, AttributeRef, AttributeRefMut, AttributeType, NameNamespace, ReparsePointTag,
+};
+use crate::data_stream::{DataStream, DataStreamRefMut};
 use crate::error::Error;
-use crate::ffi_error::{LibfsntfsError, LibfsntfsErrorRef, LibfsntfsErrorRefMut};
-use crate::volume::{Volume, VolumeRef};
+use crate::extended_attributes::parse_extended_attributes;
+use crate::extent::{Extent, ExtentFlags};
+use crate::ffi_error::{ErrorScratch, LibfsntfsError, LibfsntfsErrorRef, LibfsntfsErrorRefMut};
+use crate::file_attributes::FileAttributeFlags;
+use crate::filetime::{filetime_to_system_time, filetime_to_unix_nanos};
+use crate::mft::{read_u16, read_u32, MftRecord, RawAttributeValue};
+#[cfg(feature = "mmap")]
+use crate::mmap::StreamLayout;
+use crate::name_match::MatchMode;
+use crate::security::SecurityDescriptor;
+use crate::volume::{
+    mft_entry_index, FileReference, Volume, VolumeRef, ROOT_DIRECTORY_MFT_ENTRY_INDEX,
+};
 use libfsntfs_sys::{
     libfsntfs_attribute_t, libfsntfs_data_stream_t, off64_t, size64_t, SEEK_CUR, SEEK_END, SEEK_SET,
 };
 use libyal_rs_common::ffi::AsTypeRef;
+use log::warn;
+#[cfg(feature = "mmap")]
+use once_cell::unsync::OnceCell;
+use std::cmp::min;
+use std::collections::HashSet;
 use std::convert::TryFrom;
-use std::ffi::c_void;
+use std::ffi::{c_void, OsString};
 use std::fmt::{Debug, Formatter};
 use std::fs::read;
 use std::io::{BufRead, Read, Seek, SeekFrom};
 use std::marker::PhantomData;
 use std::option::Iter;
 use std::os::raw::c_int;
+use std::path::PathBuf;
+use std::time::SystemTime;
 use std::{fmt, io, mem, ptr};
 
+/// Default upper bound on the size of a single read issued by `FileEntry::read_all`.
+pub const DEFAULT_READ_ALL_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+/// Capacity recommended for a `BufReader` over a `FileEntry`, one compression unit of a volume
+/// with 4 KiB clusters. `Read::read` already passes buffers of any size to libfsntfs in a single
+/// call, so a larger buffer mostly saves calls for small reads.
+pub const RECOMMENDED_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Size of the reads issued while streaming a data stream, see `stream_default_data`.
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
 #[repr(C)]
 pub struct __FileEntry(isize);
 
 pub type FileEntryRefMut = *mut __FileEntry;
 pub type FileEntryRef = *const __FileEntry;
 
+/// The layout of the default data stream, looked up by the first `read_at` and reused after.
+#[cfg(feature = "mmap")]
+type StreamLayoutCache = OnceCell<Option<StreamLayout>>;
+#[cfg(not(feature = "mmap"))]
+type StreamLayoutCache = ();
+
 #[repr(C)]
-pub struct FileEntry<'a>(FileEntryRefMut, &'a Volume);
+pub struct FileEntry<'a>(FileEntryRefMut, &'a Volume, StreamLayoutCache);
 
 impl<'a> AsTypeRef for FileEntry<'a> {
     type Ref = FileEntryRef;
@@ -33,6 +71,7 @@ impl<'a> AsTypeRef for FileEntry<'a> {
 
     #[inline]
     fn as_type_ref(&self) -> Self::Ref {
+        debug_assert!(!self.0.is_null(), "`FileEntry` was used after it was freed");
         // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
         self.0 as *const _
     }
@@ -50,7 +89,12 @@ impl<'a> AsTypeRef for FileEntry<'a> {
 
 impl<'a> FileEntry<'a> {
     pub fn wrap_ptr(volume: &'a Volume, ptr: FileEntryRefMut) -> Self {
-        FileEntry(ptr, volume)
+        FileEntry(ptr, volume, StreamLayoutCache::default())
+    }
+
+    /// Retrieves the volume the entry belongs to.
+    pub fn get_volume(&self) -> &'a Volume {
+        self.1
     }
 }
 
@@ -64,10 +108,13 @@ impl<'a> Drop for FileEntry<'a> {
         trace!("Calling `libfsntfs_file_entry_free`");
 
         unsafe {
-            libfsntfs_file_entry_free(&mut self.as_type_ref_mut() as *mut _, &mut error);
+            libfsntfs_file_entry_free(self.as_raw(), &mut error);
         }
 
         debug_assert!(error.is_null(), "`libfsntfs_file_entry_free` failed!");
+
+        // Cleared so that accessors can catch a handle that is used after being freed.
+        self.0 = ptr::null_mut();
     }
 }
 
@@ -79,6 +126,15 @@ impl<'a> Debug for FileEntry<'a> {
     }
 }
 
+/// Two handles are equal when `same_entry_as` holds, or when they are the same handle in case
+/// the file references cannot be read.
+impl<'a> PartialEq for FileEntry<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.same_entry_as(other)
+            .unwrap_or_else(|_| ptr::eq(self.as_type_ref(), other.as_type_ref()))
+    }
+}
+
 extern "C" {
     pub fn libfsntfs_file_entry_free(
         file_entry: *mut FileEntryRefMut,
@@ -278,7 +334,7 @@ extern "C" {
     pub fn libfsntfs_file_entry_get_alternate_data_stream_by_index(
         file_entry: FileEntryRef,
         alternate_data_stream_index: c_int,
-        alternate_data_stream: *mut *mut libfsntfs_data_stream_t,
+        alternate_data_stream: *mut DataStreamRefMut,
         error: *mut LibfsntfsErrorRefMut,
     ) -> c_int;
     pub fn libfsntfs_file_entry_has_alternate_data_stream_by_utf8_name(
@@ -297,14 +353,14 @@ extern "C" {
         file_entry: FileEntryRef,
         utf8_string: *const u8,
         utf8_string_length: usize,
-        alternate_data_stream: *mut *mut libfsntfs_data_stream_t,
+        alternate_data_stream: *mut DataStreamRefMut,
         error: *mut LibfsntfsErrorRefMut,
     ) -> c_int;
     pub fn libfsntfs_file_entry_get_alternate_data_stream_by_utf16_name(
         file_entry: FileEntryRef,
         utf16_string: *const u16,
         utf16_string_length: usize,
-        alternate_data_stream: *mut *mut libfsntfs_data_stream_t,
+        alternate_data_stream: *mut DataStreamRefMut,
         error: *mut LibfsntfsErrorRefMut,
     ) -> c_int;
     pub fn libfsntfs_file_entry_get_number_of_sub_file_entries(
@@ -419,6 +475,9 @@ impl<'a: 'b, 'b> Iterator for IterSubEntries<'a, 'b> {
 }
 
 impl<'a> Read for FileEntry<'a> {
+    /// Reads as much of `buf` as the data stream holds with a single call to libfsntfs, which
+    /// reads the clusters spanned by `buf` at once, so each call has a fixed overhead. Wrap the
+    /// entry in a `BufReader` of `RECOMMENDED_BUFFER_SIZE` for many small reads.
     fn read(&mut self, buf: &mut [u8]) -> Result<usize, io::Error> {
         let mut error = ptr::null_mut();
         let read_count = unsafe {
@@ -449,7 +508,31 @@ impl<'a> Read for FileEntry<'a> {
 }
 
 impl<'a> Seek for FileEntry<'a> {
+    /// Seeks to before the start of the data stream, or beyond what libfsntfs can address, fail
+    /// with `io::ErrorKind::InvalidInput` without reaching libfsntfs.
     fn seek(&mut self, pos: SeekFrom) -> Result<u64, io::Error> {
+        let to_io_error = |e: Error| io::Error::new(io::ErrorKind::Other, format!("{}", e));
+
+        // Only seeks by a negative offset can end before the start, which is the only case in
+        // which the base position needs to be known.
+        let position = match pos {
+            SeekFrom::Start(offset) => i128::from(offset),
+            SeekFrom::End(offset) if offset < 0 => {
+                i128::from(self.get_size().map_err(to_io_error)?) + i128::from(offset)
+            }
+            SeekFrom::Current(offset) if offset < 0 => {
+                i128::from(self.get_offset().map_err(to_io_error)?) + i128::from(offset)
+            }
+            SeekFrom::End(_) | SeekFrom::Current(_) => 0,
+        };
+
+        if position < 0 || position > i128::from(i64::max_value()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            ));
+        }
+
         let mut error = ptr::null_mut();
 
         let seek_pos = match pos {
@@ -498,9 +581,25 @@ impl<'a> Seek for FileEntry<'a> {
 }
 
 impl<'a> FileEntry<'a> {
-    /// Returns the access date and time.
-    pub fn get_access_time(&self) -> Option<DateTime<Utc>> {
-        unimplemented!();
+    /// Retrieves the access date and time.
+    pub fn get_access_time(&self) -> Result<Option<DateTime<Utc>>, Error> {
+        get_date_field!(self, libfsntfs_file_entry_get_access_time)
+    }
+
+    /// Retrieves the access date and time as a FILETIME value.
+    pub fn get_access_time_as_integer(&self) -> Result<u64, Error> {
+        get_u64_field!(self, libfsntfs_file_entry_get_access_time)
+    }
+
+    /// Retrieves the access date and time as a `SystemTime`.
+    pub fn get_access_time_systemtime(&self) -> Result<SystemTime, Error> {
+        system_time(self.get_access_time_as_integer()?, "Access")
+    }
+
+    /// Retrieves the access date and time as nanoseconds since the Unix epoch, which are
+    /// negative before 1970.
+    pub fn get_access_time_unix_nanos(&self) -> Result<i64, Error> {
+        unix_nanos(self.get_access_time_as_integer()?, "Access")
     }
 
     pub fn get_size(&self) -> Result<u64, Error> {
@@ -515,14 +614,223 @@ impl<'a> FileEntry<'a> {
         }
     }
 
+    /// Retrieves the current offset in the default data stream, as used by `Read`.
+    pub fn get_offset(&self) -> Result<u64, Error> {
+        let mut offset = 0;
+        let mut error = ptr::null_mut();
+
+        if unsafe { libfsntfs_file_entry_get_offset(self.as_type_ref(), &mut offset, &mut error) }
+            != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(offset as u64)
+        }
+    }
+
+    /// Reads data of the default data stream at a specific offset, without moving the current
+    /// offset of the file entry.
+    ///
+    /// Offsets within compressed data need not fall on a compression unit, libfsntfs decompresses
+    /// the units around them. With the `mmap` feature enabled and a volume opened by filename,
+    /// uncompressed non-resident data is copied directly from a memory mapping of the image.
+    pub fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<usize, Error> {
+        #[cfg(feature = "mmap")]
+        {
+            if let Some(mapping) = self.1.mapping() {
+                if let Some(layout) = self.stream_layout()? {
+                    if let Some(read_count) = mapping.read_at(layout, offset, buf) {
+                        return Ok(read_count);
+                    }
+                }
+            }
+        }
+
+        self.read_at_ffi(offset, buf)
+    }
+
+    /// Retrieves the extents and sizes of the non-resident default data stream, as read from the
+    /// base record of the entry. Returns `None` for resident data, which is left to libfsntfs.
+    #[cfg(feature = "mmap")]
+    pub(crate) fn stream_layout(&self) -> Result<Option<&StreamLayout>, Error> {
+        self.2
+            .get_or_try_init(|| {
+                let record = MftRecord::read(self.1, mft_entry_index(self.get_file_reference()?))?;
+
+                match record.find_named_attribute(AttributeType::Data, "")? {
+                    Some(RawAttributeValue::NonResident {
+                        valid_data_size, ..
+                    }) => Ok(Some(StreamLayout {
+                        extents: self.get_extents()?,
+                        size: self.get_size()?,
+                        valid_data_size,
+                    })),
+                    _ => Ok(None),
+                }
+            })
+            .map(Option::as_ref)
+    }
+
+    /// Seeks back to the start of the default data stream, to read it again. Equivalent to
+    /// `seek(SeekFrom::Start(0))`, like `std::io::Seek::rewind`.
+    pub fn rewind(&mut self) -> Result<(), Error> {
+        self.seek(SeekFrom::Start(0))
+            .map_err(|e| Error::Other(format!("Failed to rewind: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Reads the entire default data stream.
+    pub fn read_all(&mut self) -> Result<Vec<u8>, Error> {
+        self.read_all_with_chunk_size(DEFAULT_READ_ALL_CHUNK_SIZE)
+    }
+
+    /// Reads the entire default data stream, issuing reads of at most `chunk_size` bytes.
+    pub fn read_all_with_chunk_size(&mut self, chunk_size: usize) -> Result<Vec<u8>, Error> {
+        if chunk_size == 0 {
+            return Err(Error::Other("Chunk size must be greater than 0".to_owned()));
+        }
+
+        let size = self.get_size()? as usize;
+        let mut data = Vec::with_capacity(min(size, chunk_size));
+
+        while data.len() < size {
+            let offset = data.len();
+            data.resize(offset + min(size - offset, chunk_size), 0);
+
+            let read_count = self.read_at(offset as u64, &mut data[offset..])?;
+            data.truncate(offset + read_count);
+
+            if read_count == 0 {
+                break;
+            }
+        }
+
+        Ok(data)
+    }
+
+    /// Feeds the default data stream to `consume` chunk by chunk, without holding all of it in
+    /// memory. Sparse ranges are passed as zeros, like libfsntfs returns them.
+    pub(crate) fn stream_default_data(
+        &self,
+        mut consume: impl FnMut(&[u8]) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let size = self.get_size()?;
+        let mut buf = vec![0; min(size, STREAM_CHUNK_SIZE as u64) as usize];
+        let mut offset = 0_u64;
+
+        while offset < size {
+            let read_count = self.read_at(offset, &mut buf)?;
+
+            if read_count == 0 {
+                return Err(Error::Other(format!(
+                    "Unexpected end of data at offset {} of {}",
+                    offset, size
+                )));
+            }
+
+            consume(&buf[..read_count])?;
+            offset += read_count as u64;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn read_at_ffi(&self, offset: u64, buf: &mut [u8]) -> Result<usize, Error> {
+        let mut error = ptr::null_mut();
+
+        let read_count = unsafe {
+            libfsntfs_file_entry_read_buffer_at_offset(
+                self.as_type_ref(),
+                buf.as_mut_ptr() as *mut c_void,
+                buf.len(),
+                offset as off64_t,
+                &mut error,
+            )
+        };
+
+        if read_count <= -1 {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(read_count as usize)
+        }
+    }
+
     /// Retrieves a specific alternate data stream.
-    pub fn get_alternate_data_stream(&self, alternate_data_stream_index: isize) {
-        unimplemented!();
+    pub fn get_alternate_data_stream(
+        &self,
+        alternate_data_stream_index: i32,
+    ) -> Result<DataStream, Error> {
+        let mut data_stream = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libfsntfs_file_entry_get_alternate_data_stream_by_index(
+                self.as_type_ref(),
+                alternate_data_stream_index,
+                &mut data_stream,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(DataStream::wrap_ptr(self, data_stream))
+        }
     }
 
     /// Retrieves an alternate data stream specified by the name.
-    pub fn get_alternate_data_stream_by_name(&self, name: isize) {
-        unimplemented!();
+    pub fn get_alternate_data_stream_by_name(&self, name: &str) -> Result<DataStream, Error> {
+        Ok(DataStream::wrap_ptr(
+            self,
+            self.get_alternate_data_stream_ptr_by_name(name)?,
+        ))
+    }
+
+    /// Opens the handle of an alternate data stream, which must not outlive the file entry.
+    pub(crate) fn get_alternate_data_stream_ptr_by_name(
+        &self,
+        name: &str,
+    ) -> Result<DataStreamRefMut, Error> {
+        let mut data_stream = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        match unsafe {
+            libfsntfs_file_entry_get_alternate_data_stream_by_utf8_name(
+                self.as_type_ref(),
+                name.as_ptr(),
+                name.len(),
+                &mut data_stream,
+                &mut error,
+            )
+        } {
+            1 => Ok(data_stream),
+            0 => Err(Error::not_found(name)),
+            _ => Err(Error::try_from(error)?),
+        }
+    }
+
+    /// Opens the data stream stored in the `$DATA` attribute at `attribute_index`, which is the
+    /// default data stream for an unnamed attribute and an alternate data stream otherwise.
+    pub fn open_data_stream_by_attribute_index(
+        &self,
+        attribute_index: i32,
+    ) -> Result<DataStream, Error> {
+        let attribute = self.get_attribute_by_index(attribute_index)?;
+        let attribute_type = attribute.get_type()?;
+
+        if attribute_type != AttributeType::Data {
+            return Err(Error::Other(format!(
+                "Attribute {} is a {} attribute, not a Data attribute",
+                attribute_index, attribute_type
+            )));
+        }
+
+        if attribute.has_name()? {
+            self.get_alternate_data_stream_by_name(&attribute.get_name()?)
+        } else {
+            Ok(DataStream::default_of(self))
+        }
     }
 
     pub fn iter_attributes(&self) -> Result<IterAttributes, Error> {
@@ -545,6 +853,18 @@ impl<'a> FileEntry<'a> {
         })
     }
 
+    /// Retrieves the types of all attributes, in the order they are stored.
+    pub fn get_attribute_types(&self) -> Result<Vec<AttributeType>, Error> {
+        let mut attribute_types =
+            Vec::with_capacity(self.get_number_of_attributes()?.max(0) as usize);
+
+        for attribute in self.iter_attributes()? {
+            attribute_types.push(attribute?.get_type()?);
+        }
+
+        Ok(attribute_types)
+    }
+
     pub fn get_number_of_attributes(&self) -> Result<c_int, Error> {
         let mut num_attributes = 0_i32;
         let mut error = ptr::null_mut();
@@ -582,12 +902,141 @@ impl<'a> FileEntry<'a> {
         }
     }
 
+    /// Retrieves the first attribute of the given type with the given name.
+    /// Unnamed attributes have an empty name.
+    pub fn get_attribute_by_name(
+        &self,
+        attribute_type: AttributeType,
+        name: &str,
+    ) -> Result<Option<Attribute>, Error> {
+        for attribute in self.iter_attributes()? {
+            let attribute = attribute?;
+
+            if attribute.get_type()? == attribute_type && attribute.get_name()? == name {
+                return Ok(Some(attribute));
+            }
+        }
+
+        Ok(None)
+    }
+
     pub fn get_name(&self) -> Result<String, Error> {
-        get_sized_utf8_string!(
-            self,
-            libfsntfs_file_entry_get_utf8_name_size,
-            libfsntfs_file_entry_get_utf8_name
-        )
+        let mut name = String::new();
+        self.get_name_into(&mut name)?;
+
+        Ok(name)
+    }
+
+    /// Retrieves the name into `buf`, replacing its contents. The buffer is only reallocated if
+    /// the name does not fit into its capacity, so it can be reused across many entries.
+    ///
+    /// Names that cannot be converted to UTF-8, e.g. because they contain unpaired surrogates,
+    /// are converted lossily from UTF-16 instead. If the name cannot be retrieved, `buf` is left
+    /// empty, but keeps its capacity.
+    pub fn get_name_into(&self, buf: &mut String) -> Result<(), Error> {
+        let error = match self.get_utf8_name_into(buf) {
+            Err(e) if e.is_conversion_error() => e,
+            result => return result,
+        };
+
+        // Report the original error if the UTF-16 name is unavailable as well.
+        let name = self.get_name_utf16().map_err(|_| error)?;
+
+        warn!(
+            "Name of entry {:?} cannot be converted to UTF-8, replacing invalid characters",
+            self.get_file_reference().ok().map(mft_entry_index)
+        );
+
+        buf.clear();
+        buf.push_str(&String::from_utf16_lossy(&name));
+
+        Ok(())
+    }
+
+    fn get_utf8_name_into(&self, buf: &mut String) -> Result<(), Error> {
+        let mut name_size = 0_usize;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libfsntfs_file_entry_get_utf8_name_size(self.as_type_ref(), &mut name_size, &mut error)
+        } != 1
+        {
+            return Err(Error::try_from(error)?);
+        }
+
+        let mut name = mem::replace(buf, String::new()).into_bytes();
+        name.clear();
+
+        if name_size == 0 {
+            *buf = emptied(name);
+            return Ok(());
+        }
+
+        name.resize(name_size, 0);
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libfsntfs_file_entry_get_utf8_name(
+                self.as_type_ref(),
+                name.as_mut_ptr(),
+                name.len(),
+                &mut error,
+            )
+        } != 1
+        {
+            *buf = emptied(name);
+            return Err(Error::try_from(error)?);
+        }
+
+        // Discard nul terminator.
+        name.pop();
+
+        match String::from_utf8(name) {
+            Ok(name) => *buf = name,
+            Err(e) => {
+                *buf = emptied(e.clone().into_bytes());
+                return Err(Error::StringContainsInvalidUTF8(e));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retrieves the name as UTF-16 code units, without a nul terminator.
+    pub fn get_name_utf16(&self) -> Result<Vec<u16>, Error> {
+        let mut name_size = 0_usize;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libfsntfs_file_entry_get_utf16_name_size(self.as_type_ref(), &mut name_size, &mut error)
+        } != 1
+        {
+            return Err(Error::try_from(error)?);
+        }
+
+        if name_size == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut name = vec![0_u16; name_size];
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libfsntfs_file_entry_get_utf16_name(
+                self.as_type_ref(),
+                name.as_mut_ptr(),
+                name.len(),
+                &mut error,
+            )
+        } != 1
+        {
+            return Err(Error::try_from(error)?);
+        }
+
+        // Discard nul terminator.
+        name.pop();
+
+        Ok(name)
     }
 
     pub fn get_sub_file_entry(&self, sub_file_entry_index: i32) -> Result<FileEntry<'a>, Error> {
@@ -609,6 +1058,78 @@ impl<'a> FileEntry<'a> {
         }
     }
 
+    pub(crate) fn get_sub_file_entry_with(
+        &self,
+        sub_file_entry_index: i32,
+        scratch: &mut ErrorScratch,
+    ) -> Result<FileEntry<'a>, Error> {
+        let mut sub_entry = ptr::null_mut();
+
+        if unsafe {
+            libfsntfs_file_entry_get_sub_file_entry_by_index(
+                self.as_type_ref(),
+                sub_file_entry_index,
+                &mut sub_entry,
+                scratch.as_out_ptr(),
+            )
+        } != 1
+        {
+            Err(scratch.take_error())
+        } else {
+            Ok(FileEntry::wrap_ptr(self.1, sub_entry))
+        }
+    }
+
+    /// Retrieves the sub file entry specified by the name.
+    pub fn get_sub_file_entry_by_name(&self, name: &str) -> Result<FileEntry<'a>, Error> {
+        let mut sub_entry = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        match unsafe {
+            libfsntfs_file_entry_get_sub_file_entry_by_utf8_name(
+                self.as_type_ref(),
+                name.as_ptr(),
+                name.len(),
+                &mut sub_entry,
+                &mut error,
+            )
+        } {
+            1 => Ok(FileEntry::wrap_ptr(self.1, sub_entry)),
+            0 => Err(Error::not_found(name)),
+            _ => Err(Error::try_from(error)?),
+        }
+    }
+
+    /// Looks up a direct child by name, returning `None` if there is no such child.
+    ///
+    /// libfsntfs only finds exact matches, so for the other modes the names of the children are
+    /// compared one by one when there is no exact match.
+    pub fn find_sub_entry(
+        &self,
+        name: &str,
+        mode: MatchMode,
+    ) -> Result<Option<FileEntry<'a>>, Error> {
+        match self.get_sub_file_entry_by_name(name) {
+            Ok(sub_entry) => return Ok(Some(sub_entry)),
+            Err(Error::NotFound(_)) if mode == MatchMode::Exact => return Ok(None),
+            Err(Error::NotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+
+        let mut sub_entry_name = String::new();
+
+        for sub_entry in self.iter_sub_entries()? {
+            let sub_entry = sub_entry?;
+            sub_entry.get_name_into(&mut sub_entry_name)?;
+
+            if mode.matches(&sub_entry_name, name) {
+                return Ok(Some(sub_entry));
+            }
+        }
+
+        Ok(None)
+    }
+
     pub fn get_number_of_sub_file_entries(&self) -> Result<c_int, Error> {
         let mut number_of_file_entries = 0;
         let mut error = ptr::null_mut();
@@ -663,140 +1184,1128 @@ impl<'a> FileEntry<'a> {
         }
     }
 
-    pub fn get_base_record_file_reference(&self) {
-        unimplemented!();
-    }
+    /// Determines if both handles refer to the same MFT entry of the same volume, e.g. an entry
+    /// reached through two different hard links.
+    ///
+    /// The file references compared include the sequence number, so an entry whose MFT slot has
+    /// been reused for another file is not the same entry.
+    pub fn same_entry_as(&self, other: &FileEntry) -> Result<bool, Error> {
+        if !ptr::eq(self.1, other.1) {
+            return Ok(false);
+        }
 
-    pub fn get_creation_time(&self) {
-        unimplemented!();
+        Ok(self.get_file_reference()? == other.get_file_reference()?)
     }
 
-    pub fn get_creation_time_as_integer(&self) {
-        unimplemented!();
+    /// Reconstructs the path of the file entry relative to the root directory by following
+    /// the parent file references.
+    ///
+    /// Names are joined with the separator of the host, so on Unix a name containing `/`, which
+    /// the POSIX namespace of NTFS allows, is split into several components. Use
+    /// `full_path_components` or `full_path_display` where such names must be kept intact.
+    pub fn full_path(&self) -> Result<PathBuf, Error> {
+        Ok(self.full_path_components()?.iter().collect())
     }
 
-    pub fn get_entry_modification_time(&self) {
-        unimplemented!();
-    }
+    /// Retrieves the names of the ancestors of the file entry and of the entry itself, starting
+    /// below the root directory. Each name is a single component, whatever characters it holds.
+    pub fn full_path_components(&self) -> Result<Vec<OsString>, Error> {
+        let mut idx = mft_entry_index(self.get_file_reference()?);
 
-    pub fn get_entry_modification_time_as_integer(&self) {
-        unimplemented!();
-    }
+        if idx == ROOT_DIRECTORY_MFT_ENTRY_INDEX {
+            return Ok(Vec::new());
+        }
 
-    pub fn get_extent(&self, extent_index: isize) {
-        unimplemented!();
-    }
+        let mut names = vec![OsString::from(self.get_name()?)];
+        let mut visited = HashSet::new();
+        let mut parent_reference = self.get_parent_file_reference()?;
 
-    pub fn get_file_attribute_flags(&self) {
-        unimplemented!();
-    }
+        visited.insert(idx);
 
-    pub fn get_journal_sequence_number(&self) {
-        unimplemented!();
-    }
+        loop {
+            idx = mft_entry_index(parent_reference);
 
-    pub fn get_modification_time(&self) {
-        unimplemented!();
-    }
+            if idx == ROOT_DIRECTORY_MFT_ENTRY_INDEX {
+                break;
+            }
 
-    pub fn get_modification_time_as_integer(&self) {
-        unimplemented!();
-    }
+            if !visited.insert(idx) {
+                return Err(Error::Other(format!(
+                    "Parent references of entry {} contain a cycle",
+                    idx
+                )));
+            }
+
+            let parent = self.1.get_file_entry_by_mft_idx(idx)?;
+            names.push(OsString::from(parent.get_name()?));
+            parent_reference = parent.get_parent_file_reference()?;
+        }
+
+        names.reverse();
 
-    pub fn get_name_attribute_index(&self) {
-        unimplemented!();
+        Ok(names)
     }
 
-    pub fn get_name_by_attribute_index(&self, attribute_index: isize) {
-        unimplemented!();
+    /// Renders the path of the file entry relative to the root directory with `separator`
+    /// between its components, e.g. `\` for the form Windows uses. Separators within names are
+    /// not escaped.
+    pub fn full_path_with_separator(&self, separator: &str) -> Result<String, Error> {
+        let components: Vec<String> = self
+            .full_path_components()?
+            .iter()
+            .map(|component| component.to_string_lossy().into_owned())
+            .collect();
+
+        Ok(components.join(separator))
     }
 
-    pub fn get_number_of_alternate_data_streams(&self) {
-        unimplemented!();
+    /// Renders the path of the file entry relative to the root directory with `\` between its
+    /// components, percent-encoding `\`, `/` and `%` within names, so that every component can
+    /// be told apart and recovered.
+    pub fn full_path_display(&self) -> Result<String, Error> {
+        let components: Vec<String> = self
+            .full_path_components()?
+            .iter()
+            .map(|component| escape_path_component(&component.to_string_lossy()))
+            .collect();
+
+        Ok(components.join("\\"))
     }
 
-    pub fn get_number_of_extents(&self) {
-        unimplemented!();
+    /// Resolves a path relative to this entry. Components can be separated by `\` or `/`, and
+    /// `..` follows the parent reference (the root directory is its own parent).
+    pub fn get_sub_entry_by_path(&self, rel_path: &str) -> Result<FileEntry<'a>, Error> {
+        let mut current = self
+            .1
+            .get_file_entry_by_mft_idx(mft_entry_index(self.get_file_reference()?))?;
+
+        for component in rel_path.split(|c| c == '\\' || c == '/') {
+            current = match component {
+                "" | "." => continue,
+                ".." => {
+                    if mft_entry_index(current.get_file_reference()?)
+                        == ROOT_DIRECTORY_MFT_ENTRY_INDEX
+                    {
+                        continue;
+                    }
+
+                    let parent_idx = mft_entry_index(current.get_parent_file_reference()?);
+                    self.1.get_file_entry_by_mft_idx(parent_idx)?
+                }
+                name => current.get_sub_file_entry_by_name(name)?,
+            };
+        }
+
+        Ok(current)
     }
 
-    pub fn get_parent_file_reference_by_attribute_index(&self, attribute_index: isize) {
-        unimplemented!();
+    /// Retrieves the file reference of the base record, which is 0 if this is a base record.
+    pub fn get_base_record_file_reference(&self) -> Result<u64, Error> {
+        get_u64_field!(self, libfsntfs_file_entry_get_base_record_file_reference)
     }
 
-    pub fn get_reparse_point_print_name(&self) {
-        unimplemented!();
+    /// Retrieves the creation date and time.
+    pub fn get_creation_time(&self) -> Result<Option<DateTime<Utc>>, Error> {
+        get_date_field!(self, libfsntfs_file_entry_get_creation_time)
     }
 
-    pub fn get_reparse_point_substitute_name(&self) {
-        unimplemented!();
+    /// Retrieves the creation date and time as a FILETIME value.
+    pub fn get_creation_time_as_integer(&self) -> Result<u64, Error> {
+        get_u64_field!(self, libfsntfs_file_entry_get_creation_time)
     }
 
-    pub fn get_security_descriptor_data(&self) {
-        unimplemented!();
+    /// Retrieves the creation date and time as a `SystemTime`.
+    pub fn get_creation_time_systemtime(&self) -> Result<SystemTime, Error> {
+        system_time(self.get_creation_time_as_integer()?, "Creation")
     }
 
-    pub fn has_alternate_data_stream_by_name(&self, name: isize) {
-        unimplemented!();
+    /// Retrieves the creation date and time as nanoseconds since the Unix epoch, which are
+    /// negative before 1970.
+    pub fn get_creation_time_unix_nanos(&self) -> Result<i64, Error> {
+        unix_nanos(self.get_creation_time_as_integer()?, "Creation")
     }
 
-    pub fn has_default_data_stream(&self) {
-        unimplemented!();
+    /// Retrieves the entry modification date and time.
+    pub fn get_entry_modification_time(&self) -> Result<Option<DateTime<Utc>>, Error> {
+        get_date_field!(self, libfsntfs_file_entry_get_entry_modification_time)
     }
 
-    pub fn has_directory_entries_index(&self) {
-        unimplemented!();
+    /// Retrieves the entry modification date and time as a FILETIME value.
+    pub fn get_entry_modification_time_as_integer(&self) -> Result<u64, Error> {
+        get_u64_field!(self, libfsntfs_file_entry_get_entry_modification_time)
     }
 
-    pub fn is_empty(&self) {
-        unimplemented!();
+    /// Retrieves the entry modification date and time as a `SystemTime`.
+    pub fn get_entry_modification_time_systemtime(&self) -> Result<SystemTime, Error> {
+        system_time(
+            self.get_entry_modification_time_as_integer()?,
+            "Entry modification",
+        )
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::fixtures::*;
-    use log::{info, trace};
-    use std::path::PathBuf;
+    /// Retrieves the entry modification date and time as nanoseconds since the Unix epoch, which
+    /// are negative before 1970.
+    pub fn get_entry_modification_time_unix_nanos(&self) -> Result<i64, Error> {
+        unix_nanos(
+            self.get_entry_modification_time_as_integer()?,
+            "Entry modification",
+        )
+    }
 
-    #[test]
-    fn test_iter_attributes() {
-        let volume = sample_volume().unwrap();
-        let file_attribute = volume.get_file_entry_by_mft_idx(0).unwrap();
+    /// Retrieves a specific extent of the default data stream.
+    pub fn get_extent(&self, extent_index: i32) -> Result<Extent, Error> {
+        let mut offset = 0;
+        let mut size = 0;
+        let mut flags = 0;
+        let mut error = ptr::null_mut();
 
-        for attribute in file_attribute
-            .iter_attributes()
-            .unwrap()
-            .map(|a| a.unwrap())
+        if unsafe {
+            libfsntfs_file_entry_get_extent_by_index(
+                self.as_type_ref(),
+                extent_index,
+                &mut offset,
+                &mut size,
+                &mut flags,
+                &mut error,
+            )
+        } != 1
         {
-            println!("{:?}", attribute.get_name().unwrap());
-            println!("{:?}", attribute.get_type().unwrap());
+            return Err(Error::try_from(error)?);
         }
+
+        Extent::from_raw(offset, size, flags)
+            .map_err(|e| Error::Other(format!("Extent {} is invalid: {}", extent_index, e)))
     }
 
-    #[test]
-    fn test_read() {
-        let volume = sample_volume().unwrap();
-        let mut entry = file_entry(&volume).unwrap();
+    /// Retrieves all extents of the default data stream.
+    pub fn get_extents(&self) -> Result<Vec<Extent>, Error> {
+        let number_of_extents = self.get_number_of_extents()?;
+        let mut extents = Vec::with_capacity(number_of_extents.max(0) as usize);
 
-        let mut buf = vec![0; 10];
-        entry.read(&mut buf).unwrap();
+        for idx in 0..number_of_extents {
+            extents.push(self.get_extent(idx)?);
+        }
 
-        assert_eq!(buf, [70, 73, 76, 69, 48, 0, 3, 0, 181, 104]);
+        Ok(extents)
     }
 
-    #[test]
-    fn test_seek() {
-        let volume = sample_volume().unwrap();
-        let mut entry = file_entry(&volume).unwrap();
+    /// Retrieves the file attribute flags.
+    pub fn get_file_attribute_flags(&self) -> Result<u32, Error> {
+        let mut file_attribute_flags = 0_u32;
+        let mut error = ptr::null_mut();
 
-        let mut buf = vec![0; 10];
-        entry.seek(SeekFrom::Start(10)).unwrap();
+        if unsafe {
+            libfsntfs_file_entry_get_file_attribute_flags(
+                self.as_type_ref(),
+                &mut file_attribute_flags,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(file_attribute_flags)
+        }
+    }
+
+    /// Retrieves the file attribute flags, with unknown bits dropped.
+    pub fn get_file_attributes(&self) -> Result<FileAttributeFlags, Error> {
+        Ok(FileAttributeFlags::from_bits_truncate(
+            self.get_file_attribute_flags()?,
+        ))
+    }
+
+    /// Describes the entry by its name, file reference, size and file attribute flags, e.g.
+    /// `file-r-1.dat (27-1, 11 bytes, Archive)`.
+    ///
+    /// Unlike the `Debug` output, which falls back to an empty name, any error reading these
+    /// values is returned.
+    pub fn describe(&self) -> Result<String, Error> {
+        let file_reference = FileReference::from(self.get_file_reference()?);
+
+        Ok(format!(
+            "{} ({}-{}, {} bytes, {})",
+            self.get_name()?,
+            file_reference.mft_entry_index(),
+            file_reference.sequence_number(),
+            self.get_size()?,
+            self.get_file_attributes()?
+        ))
+    }
+
+    /// Retrieves the update sequence number (USN) of the last change.
+    /// Returns `None` if the entry has no `$STANDARD_INFORMATION` attribute.
+    pub fn get_journal_sequence_number(&self) -> Result<Option<u64>, Error> {
+        let mut journal_sequence_number = 0;
+        let mut error = ptr::null_mut();
+
+        match unsafe {
+            libfsntfs_file_entry_get_journal_sequence_number(
+                self.as_type_ref(),
+                &mut journal_sequence_number,
+                &mut error,
+            )
+        } {
+            1 => Ok(Some(journal_sequence_number)),
+            0 => Ok(None),
+            _ => Err(Error::try_from(error)?),
+        }
+    }
+
+    /// Retrieves the modification date and time.
+    pub fn get_modification_time(&self) -> Result<Option<DateTime<Utc>>, Error> {
+        get_date_field!(self, libfsntfs_file_entry_get_modification_time)
+    }
+
+    /// Retrieves the modification date and time as a FILETIME value.
+    pub fn get_modification_time_as_integer(&self) -> Result<u64, Error> {
+        get_u64_field!(self, libfsntfs_file_entry_get_modification_time)
+    }
+
+    /// Retrieves the modification date and time as a `SystemTime`.
+    pub fn get_modification_time_systemtime(&self) -> Result<SystemTime, Error> {
+        system_time(self.get_modification_time_as_integer()?, "Modification")
+    }
+
+    /// Retrieves the modification date and time as nanoseconds since the Unix epoch, which are
+    /// negative before 1970.
+    pub fn get_modification_time_unix_nanos(&self) -> Result<i64, Error> {
+        unix_nanos(self.get_modification_time_as_integer()?, "Modification")
+    }
+
+    /// Retrieves the index of the `$FILE_NAME` attribute that `get_name` is read from.
+    /// Returns `None` if the entry has no name.
+    pub fn get_name_attribute_index(&self) -> Result<Option<c_int>, Error> {
+        let mut attribute_index = 0;
+        let mut error = ptr::null_mut();
+
+        match unsafe {
+            libfsntfs_file_entry_get_name_attribute_index(
+                self.as_type_ref(),
+                &mut attribute_index,
+                &mut error,
+            )
+        } {
+            1 => Ok(Some(attribute_index)),
+            0 => Ok(None),
+            _ => Err(Error::try_from(error)?),
+        }
+    }
+
+    /// Retrieves the name stored in the `$FILE_NAME` attribute at `attribute_index`.
+    pub fn get_name_by_attribute_index(&self, attribute_index: c_int) -> Result<String, Error> {
+        get_sized_utf8_string_by_index!(
+            self,
+            libfsntfs_file_entry_get_utf8_name_size_by_attribute_index,
+            libfsntfs_file_entry_get_utf8_name_by_attribute_index,
+            attribute_index
+        )
+    }
+
+    /// Retrieves the names of all `$FILE_NAME` attributes along with their namespace, e.g. both
+    /// the long and the 8.3 name of an entry, or the names of its hard links.
+    pub fn iter_names(
+        &self,
+    ) -> Result<impl Iterator<Item = Result<(NameNamespace, String), Error>> + '_, Error> {
+        Ok(self
+            .iter_attributes()?
+            .enumerate()
+            .filter_map(move |(attribute_index, attribute)| {
+                let attribute = match attribute {
+                    Ok(attribute) => attribute,
+                    Err(e) => return Some(Err(e)),
+                };
+
+                match attribute.get_type() {
+                    Ok(AttributeType::FileName) => {}
+                    Ok(_) => return None,
+                    Err(e) => return Some(Err(e)),
+                }
+
+                Some(attribute.get_file_name_namespace().and_then(|namespace| {
+                    let name = self.get_name_by_attribute_index(attribute_index as c_int)?;
+
+                    Ok((namespace, name))
+                }))
+            }))
+    }
+
+    /// Retrieves the number of alternate data streams.
+    /// This is cheaper than enumerating the streams when only their presence is of interest.
+    pub fn get_number_of_alternate_data_streams(&self) -> Result<c_int, Error> {
+        let mut number_of_alternate_data_streams = 0;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libfsntfs_file_entry_get_number_of_alternate_data_streams(
+                self.as_type_ref(),
+                &mut number_of_alternate_data_streams,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(number_of_alternate_data_streams)
+        }
+    }
+
+    /// Retrieves the number of extents of the default data stream.
+    pub fn get_number_of_extents(&self) -> Result<c_int, Error> {
+        let mut number_of_extents = 0;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libfsntfs_file_entry_get_number_of_extents(
+                self.as_type_ref(),
+                &mut number_of_extents,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(number_of_extents)
+        }
+    }
+
+    /// Retrieves the parent file reference stored in the `$FILE_NAME` attribute at
+    /// `attribute_index`.
+    pub fn get_parent_file_reference_by_attribute_index(
+        &self,
+        attribute_index: c_int,
+    ) -> Result<u64, Error> {
+        let mut parent_file_reference = 0;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libfsntfs_file_entry_get_parent_file_reference_by_attribute_index(
+                self.as_type_ref(),
+                attribute_index,
+                &mut parent_file_reference,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(parent_file_reference)
+        }
+    }
+
+    /// Retrieves the reparse point print name.
+    /// Returns `None` if the file entry is not a reparse point or the name is not available.
+    pub fn get_reparse_point_print_name(&self) -> Result<Option<String>, Error> {
+        self.get_reparse_point_name(
+            libfsntfs_file_entry_get_utf8_reparse_point_print_name_size,
+            libfsntfs_file_entry_get_utf8_reparse_point_print_name,
+        )
+    }
+
+    /// Retrieves the reparse point substitute name.
+    /// Returns `None` if the file entry is not a reparse point or the name is not available.
+    pub fn get_reparse_point_substitute_name(&self) -> Result<Option<String>, Error> {
+        self.get_reparse_point_name(
+            libfsntfs_file_entry_get_utf8_reparse_point_substitute_name_size,
+            libfsntfs_file_entry_get_utf8_reparse_point_substitute_name,
+        )
+    }
+
+    /// Retrieves the tag and the data of the `$REPARSE_POINT` attribute, which hold formats
+    /// libfsntfs does not parse, e.g. those of cloud file placeholders or deduplicated files.
+    /// Returns `None` if the file entry is not a reparse point.
+    ///
+    /// Vendors define their own tags, so tags that are not known are returned as stored in the
+    /// `Err` variant, see `ReparsePointTag::from_raw`. The attribute is looked up in the
+    /// extension records of the entry too.
+    pub fn get_reparse_point_data(
+        &self,
+    ) -> Result<Option<(Result<ReparsePointTag, u32>, Vec<u8>)>, Error> {
+        let idx = mft_entry_index(self.get_file_reference()?);
+
+        let value = MftRecord::read_attribute_value(self.1, idx, AttributeType::ReparsePoint)?;
+
+        let value = match value {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        let truncated = || Error::Other("$REPARSE_POINT is truncated".to_owned());
+
+        let tag = read_u32(&value, 0).ok_or_else(truncated)?;
+        let data_size = read_u16(&value, 4).ok_or_else(truncated)? as usize;
+        let data = value.get(8..8 + data_size).ok_or_else(truncated)?;
+
+        Ok(Some((ReparsePointTag::from_raw(tag), data.to_vec())))
+    }
+
+    fn get_reparse_point_name(
+        &self,
+        get_size: unsafe extern "C" fn(
+            FileEntryRef,
+            *mut usize,
+            *mut LibfsntfsErrorRefMut,
+        ) -> c_int,
+        get_name: unsafe extern "C" fn(
+            FileEntryRef,
+            *mut u8,
+            usize,
+            *mut LibfsntfsErrorRefMut,
+        ) -> c_int,
+    ) -> Result<Option<String>, Error> {
+        let mut name_size = 0_usize;
+        let mut error = ptr::null_mut();
+
+        match unsafe { get_size(self.as_type_ref(), &mut name_size, &mut error) } {
+            1 => {}
+            0 => return Ok(None),
+            _ => return Err(Error::try_from(error)?),
+        }
+
+        if name_size == 0 {
+            return Ok(None);
+        }
+
+        let mut name = vec![0; name_size];
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            get_name(
+                self.as_type_ref(),
+                name.as_mut_ptr(),
+                name.len(),
+                &mut error,
+            )
+        } != 1
+        {
+            return Err(Error::try_from(error)?);
+        }
+
+        // Discard nul terminator;
+        name.pop();
+
+        Ok(Some(
+            String::from_utf8(name).map_err(Error::StringContainsInvalidUTF8)?,
+        ))
+    }
+
+    /// Retrieves the security descriptor of the file entry, from either `$Secure` or the
+    /// `$SECURITY_DESCRIPTOR` attribute. Returns `None` if the entry has none.
+    pub fn get_security_descriptor_data(&self) -> Result<Option<Vec<u8>>, Error> {
+        let mut size = 0_usize;
+        let mut error = ptr::null_mut();
+
+        match unsafe {
+            libfsntfs_file_entry_get_security_descriptor_size(
+                self.as_type_ref(),
+                &mut size,
+                &mut error,
+            )
+        } {
+            1 => {}
+            0 => return Ok(None),
+            _ => return Err(Error::try_from(error)?),
+        }
+
+        if size == 0 {
+            return Ok(None);
+        }
+
+        let mut data = vec![0; size];
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libfsntfs_file_entry_get_security_descriptor(
+                self.as_type_ref(),
+                data.as_mut_ptr(),
+                data.len(),
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(Some(data))
+        }
+    }
+
+    /// Retrieves and parses the security descriptor of the file entry.
+    pub fn get_security_descriptor(&self) -> Result<Option<SecurityDescriptor>, Error> {
+        match self.get_security_descriptor_data()? {
+            Some(data) => Ok(Some(SecurityDescriptor::from_bytes(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Determines if the file entry has an alternate data stream specified by the name.
+    pub fn has_alternate_data_stream_by_name(&self, name: &str) -> Result<bool, Error> {
+        let mut error = ptr::null_mut();
+
+        match unsafe {
+            libfsntfs_file_entry_has_alternate_data_stream_by_utf8_name(
+                self.as_type_ref(),
+                name.as_ptr(),
+                name.len(),
+                &mut error,
+            )
+        } {
+            1 => Ok(true),
+            0 => Ok(false),
+            _ => Err(Error::try_from(error)?),
+        }
+    }
+
+    /// Determines if the file entry has the default data stream (nameless `$DATA` attribute).
+    pub fn has_default_data_stream(&self) -> Result<bool, Error> {
+        let mut error = ptr::null_mut();
+
+        match unsafe {
+            libfsntfs_file_entry_has_default_data_stream(self.as_type_ref(), &mut error)
+        } {
+            1 => Ok(true),
+            0 => Ok(false),
+            _ => Err(Error::try_from(error)?),
+        }
+    }
+
+    /// Determines if the file entry has a directory entries (`$I30`) index.
+    pub fn has_directory_entries_index(&self) -> Result<bool, Error> {
+        let mut error = ptr::null_mut();
+
+        match unsafe {
+            libfsntfs_file_entry_has_directory_entries_index(self.as_type_ref(), &mut error)
+        } {
+            1 => Ok(true),
+            0 => Ok(false),
+            _ => Err(Error::try_from(error)?),
+        }
+    }
+
+    /// Determines if the file entry is empty, i.e. has no attributes.
+    pub fn is_empty(&self) -> Result<bool, Error> {
+        let mut error = ptr::null_mut();
+
+        match unsafe { libfsntfs_file_entry_is_empty(self.as_type_ref(), &mut error) } {
+            1 => Ok(true),
+            0 => Ok(false),
+            _ => Err(Error::try_from(error)?),
+        }
+    }
+
+    /// Retrieves the name/value pairs stored in the `$EA` attribute.
+    /// Returns an empty vector if the entry has no extended attributes.
+    pub fn get_extended_attributes(&self) -> Result<Vec<(String, Vec<u8>)>, Error> {
+        let record = MftRecord::read(self.1, mft_entry_index(self.get_file_reference()?))?;
+
+        match record.find_attribute(AttributeType::Extended)? {
+            Some(RawAttributeValue::Resident(data)) => parse_extended_attributes(data),
+            Some(RawAttributeValue::NonResident { .. }) => Err(Error::Other(
+                "Non-resident $EA attributes are not supported".to_owned(),
+            )),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Reads the value of the `$LOGGED_UTILITY_STREAM` attribute named `name`, e.g. `$EFS` for
+    /// encrypted files or `$TXF_DATA` for files changed by transactional NTFS.
+    /// Returns `None` if the entry has no such attribute.
+    ///
+    /// Only attributes stored in the base record of the entry are found.
+    pub fn get_logged_utility_stream(&self, name: &str) -> Result<Option<Vec<u8>>, Error> {
+        let record = MftRecord::read(self.1, mft_entry_index(self.get_file_reference()?))?;
+
+        match record.find_named_attribute(AttributeType::LoggedUtilityStream, name)? {
+            Some(value) => Ok(Some(value.read(self.1)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Determines if the file entry is allocated (in use).
+    pub fn is_allocated(&self) -> Result<bool, Error> {
+        let mut error = ptr::null_mut();
+
+        match unsafe { libfsntfs_file_entry_is_allocated(self.as_type_ref(), &mut error) } {
+            1 => Ok(true),
+            0 => Ok(false),
+            _ => Err(Error::try_from(error)?),
+        }
+    }
+}
+
+/// Empties `bytes` and turns them back into a `String`, keeping the allocation for reuse.
+fn emptied(mut bytes: Vec<u8>) -> String {
+    bytes.clear();
+    String::from_utf8(bytes).unwrap_or_default()
+}
+
+/// Percent-encodes the characters of a name that would be taken for separators, see
+/// `FileEntry::full_path_display`.
+pub(crate) fn escape_path_component(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+
+    for c in name.chars() {
+        match c {
+            '%' => escaped.push_str("%25"),
+            '/' => escaped.push_str("%2F"),
+            '\\' => escaped.push_str("%5C"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn system_time(filetime: u64, name: &str) -> Result<SystemTime, Error> {
+    filetime_to_system_time(filetime)
+        .ok_or_else(|| Error::Other(format!("{} time is not set", name)))
+}
+
+fn unix_nanos(filetime: u64, name: &str) -> Result<i64, Error> {
+    filetime_to_unix_nanos(filetime).ok_or_else(|| {
+        Error::Other(format!(
+            "{} time is not set or out of range for nanoseconds since the Unix epoch",
+            name
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+    use log::{info, trace};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_iter_attributes() {
+        let volume = sample_volume().unwrap();
+        let file_attribute = volume.get_file_entry_by_mft_idx(0).unwrap();
+
+        for attribute in file_attribute
+            .iter_attributes()
+            .unwrap()
+            .map(|a| a.unwrap())
+        {
+            println!("{:?}", attribute.get_name().unwrap());
+            println!("{:?}", attribute.get_type().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_get_attribute_by_name() {
+        let volume = sample_volume().unwrap();
+        let secure = volume
+            .get_file_entry_by_path("\\$Secure", MatchMode::Exact)
+            .unwrap();
+
+        let attribute = secure
+            .get_attribute_by_name(AttributeType::Data, "$SDS")
+            .unwrap()
+            .unwrap();
+        let stream = secure.get_alternate_data_stream_by_name("$SDS").unwrap();
+
+        assert!(attribute.get_data_size().unwrap() > 0);
+        assert_eq!(
+            attribute.get_data_size().unwrap(),
+            stream.get_size().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_attribute_by_name_missing() {
+        let volume = sample_volume().unwrap();
+        let secure = volume
+            .get_file_entry_by_path("\\$Secure", MatchMode::Exact)
+            .unwrap();
+
+        assert!(secure
+            .get_attribute_by_name(AttributeType::Data, "$NOPE")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_attribute_is_resident() {
+        let volume = sample_volume().unwrap();
+        let upcase = volume
+            .get_file_entry_by_path("\\$UpCase", MatchMode::Exact)
+            .unwrap();
+
+        let standard_information = upcase
+            .get_attribute_by_name(AttributeType::StandardInformation, "")
+            .unwrap()
+            .unwrap();
+        let data = upcase
+            .get_attribute_by_name(AttributeType::Data, "")
+            .unwrap()
+            .unwrap();
+
+        assert!(standard_information.is_resident().unwrap());
+        assert!(!data.is_resident().unwrap());
+    }
+
+    #[test]
+    fn test_get_sub_entry_by_path() {
+        let volume = sample_volume().unwrap();
+        let root = volume.get_root_directory().unwrap();
+
+        let entry = root.get_sub_entry_by_path("$Extend/$ObjId").unwrap();
+
+        assert_eq!(entry.get_name().unwrap(), "$ObjId");
+        assert_eq!(
+            entry.full_path().unwrap(),
+            PathBuf::from("$Extend").join("$ObjId")
+        );
+        assert_eq!(entry.full_path_display().unwrap(), "$Extend\\$ObjId");
+        assert_eq!(
+            entry.full_path_with_separator("/").unwrap(),
+            "$Extend/$ObjId"
+        );
+    }
+
+    #[test]
+    fn test_full_path_keeps_separators_in_names() {
+        let utf16 = |s: &str| {
+            s.encode_utf16()
+                .flat_map(|unit| unit.to_le_bytes().to_vec())
+                .collect::<Vec<u8>>()
+        };
+
+        let volume = patched_sample_volume(
+            "separator-in-name",
+            &utf16("file-r-1.dat"),
+            &utf16("file/r%1.dat"),
+        )
+        .unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(27).unwrap();
+
+        assert_eq!(
+            entry.full_path_components().unwrap(),
+            vec![OsString::from("file/r%1.dat")]
+        );
+        assert_eq!(entry.full_path_display().unwrap(), "file%2Fr%251.dat");
+        assert_eq!(
+            entry.full_path_with_separator("\\").unwrap(),
+            "file/r%1.dat"
+        );
+    }
+
+    #[test]
+    fn test_get_sub_entry_by_path_to_sibling() {
+        let volume = sample_volume().unwrap();
+        let object_ids = volume
+            .get_file_entry_by_path("\\$Extend\\$ObjId", MatchMode::Exact)
+            .unwrap();
+
+        let sibling = object_ids.get_sub_entry_by_path("..\\$Quota").unwrap();
+        assert_eq!(sibling.get_name().unwrap(), "$Quota");
+
+        let root = object_ids.get_sub_entry_by_path("../../..").unwrap();
+        assert_eq!(
+            mft_entry_index(root.get_file_reference().unwrap()),
+            ROOT_DIRECTORY_MFT_ENTRY_INDEX
+        );
+
+        assert!(object_ids
+            .get_sub_entry_by_path("../$Missing")
+            .unwrap_err()
+            .is_not_found());
+    }
+
+    #[test]
+    fn test_find_sub_entry() {
+        let volume = sample_volume().unwrap();
+        let root = volume.get_root_directory().unwrap();
+
+        let extend = root
+            .find_sub_entry("$Extend", MatchMode::Exact)
+            .unwrap()
+            .unwrap();
+        assert_eq!(extend.get_name().unwrap(), "$Extend");
+
+        assert!(root
+            .find_sub_entry("does-not-exist", MatchMode::Exact)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_find_sub_entry_case_insensitive() {
+        let volume = sample_volume().unwrap();
+        let root = volume.get_root_directory().unwrap();
+
+        for &mode in &[
+            MatchMode::CaseInsensitive,
+            MatchMode::NormalizedCaseInsensitive,
+        ] {
+            let entry = root
+                .find_sub_entry("SYSTEM VOLUME INFORMATION", mode)
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(entry.get_name().unwrap(), "System Volume Information");
+        }
+    }
+
+    #[test]
+    fn test_get_name_into_reuses_buffer() {
+        let volume = sample_volume().unwrap();
+        let long_name = volume
+            .get_file_entry_by_path(
+                "\\System Volume Information\\tracking.log",
+                MatchMode::Exact,
+            )
+            .unwrap();
+        let short_name = volume.get_file_entry_by_mft_idx(0).unwrap();
+
+        let mut buf = String::new();
+
+        long_name.get_name_into(&mut buf).unwrap();
+        assert_eq!(buf, long_name.get_name().unwrap());
+
+        let (result, allocations) = count_allocations(|| short_name.get_name_into(&mut buf));
+        result.unwrap();
+
+        assert_eq!(buf, "$MFT");
+        assert_eq!(allocations, 0);
+    }
+
+    #[test]
+    fn test_get_name_with_unpaired_surrogate() {
+        let utf16 = |s: &str| {
+            s.encode_utf16()
+                .flat_map(|unit| unit.to_le_bytes().to_vec())
+                .collect::<Vec<u8>>()
+        };
+
+        // Replace the `r` of `file-r-1.dat` with a lone high surrogate, in both its MFT entry
+        // and the root directory index.
+        let mut name = utf16("file-r-1.dat");
+        name[10..12].copy_from_slice(&0xd800_u16.to_le_bytes());
+
+        let volume =
+            patched_sample_volume("unpaired-surrogate", &utf16("file-r-1.dat"), &name).unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(27).unwrap();
+
+        let name = entry.get_name().unwrap();
+        assert!(name.starts_with("file-"));
+        assert!(name.ends_with("-1.dat"));
+    }
+
+    #[test]
+    fn test_get_attribute_types_allocates_once() {
+        let volume = sample_volume().unwrap();
+        let entry = file_entry(&volume).unwrap();
+
+        let (attribute_types, allocations) = count_allocations(|| entry.get_attribute_types());
+        let attribute_types = attribute_types.unwrap();
+
+        assert_eq!(
+            attribute_types.len(),
+            entry.get_number_of_attributes().unwrap() as usize
+        );
+        assert!(attribute_types.contains(&AttributeType::Data));
+        assert_eq!(allocations, 1);
+    }
+
+    #[test]
+    fn test_read() {
+        let volume = sample_volume().unwrap();
+        let mut entry = file_entry(&volume).unwrap();
+
+        let mut buf = vec![0; 10];
+        entry.read(&mut buf).unwrap();
+
+        assert_eq!(buf, [70, 73, 76, 69, 48, 0, 3, 0, 181, 104]);
+    }
+
+    #[test]
+    fn test_seek() {
+        let volume = sample_volume().unwrap();
+        let mut entry = file_entry(&volume).unwrap();
+
+        let mut buf = vec![0; 10];
+        entry.seek(SeekFrom::Start(10)).unwrap();
         entry.read(&mut buf).unwrap();
 
         assert_eq!(buf, [16, 0, 0, 0, 0, 0, 1, 0, 1, 0]);
     }
 
+    /// Counts the calls to `read` of the reader it wraps.
+    struct CountingReads<R> {
+        inner: R,
+        reads: usize,
+    }
+
+    impl<R: Read> Read for CountingReads<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.reads += 1;
+            self.inner.read(buf)
+        }
+    }
+
+    /// Reads all of `reader` in small chunks.
+    fn read_in_chunks(reader: &mut impl Read) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut chunk = [0; 4096];
+
+        loop {
+            match reader.read(&mut chunk).unwrap() {
+                0 => return data,
+                n => data.extend_from_slice(&chunk[..n]),
+            }
+        }
+    }
+
+    #[test]
+    fn test_buffered_reader_makes_fewer_reads() {
+        let volume = sample_volume().unwrap();
+        let mut entry = file_entry(&volume).unwrap();
+        let expected = entry.read_all().unwrap();
+
+        entry.rewind().unwrap();
+        let mut unbuffered = CountingReads {
+            inner: &mut entry,
+            reads: 0,
+        };
+        assert_eq!(read_in_chunks(&mut unbuffered), expected);
+        let unbuffered_reads = unbuffered.reads;
+
+        entry.rewind().unwrap();
+        let mut buffered = CountingReads {
+            inner: &mut entry,
+            reads: 0,
+        };
+        let mut reader = io::BufReader::with_capacity(RECOMMENDED_BUFFER_SIZE, &mut buffered);
+        assert_eq!(read_in_chunks(&mut reader), expected);
+
+        // One read per buffer filled, and a last one that finds the end of the stream.
+        let buffers = (expected.len() + RECOMMENDED_BUFFER_SIZE - 1) / RECOMMENDED_BUFFER_SIZE;
+        assert_eq!(buffered.reads, buffers + 1);
+        assert!(buffered.reads < unbuffered_reads);
+    }
+
+    /// Reads the default data stream of `entry` in two halves split at `middle`, once with
+    /// `read_at` and once with `seek` and `read`.
+    fn read_in_halves(entry: &mut FileEntry, middle: u64) -> (Vec<u8>, Vec<u8>) {
+        let size = entry.get_size().unwrap();
+
+        let mut first = vec![0; middle as usize];
+        let mut second = vec![0; (size - middle) as usize];
+        assert_eq!(entry.read_at(middle, &mut second).unwrap(), second.len());
+        assert_eq!(entry.read_at(0, &mut first).unwrap(), first.len());
+        first.extend(second);
+
+        let mut sought = Vec::new();
+        entry.seek(SeekFrom::Start(middle)).unwrap();
+        entry.read_to_end(&mut sought).unwrap();
+        entry.rewind().unwrap();
+        let mut head = vec![0; middle as usize];
+        entry.read_exact(&mut head).unwrap();
+        head.extend(sought);
+
+        (first, head)
+    }
+
+    #[test]
+    fn test_read_at_in_halves() {
+        let volume = sample_volume().unwrap();
+        let mut entry = file_entry(&volume).unwrap();
+        let expected = entry.read_all().unwrap();
+
+        let (read_at, sought) = read_in_halves(&mut entry, expected.len() as u64 / 2 + 1);
+
+        assert_eq!(read_at, expected);
+        assert_eq!(sought, expected);
+    }
+
+    #[test]
+    fn test_read_at_in_halves_of_compressed_file() {
+        let volume = compressed_volume().unwrap();
+        let mut entry = volume
+            .get_file_entry_by_path("\\file-n-1.dat", MatchMode::Exact)
+            .unwrap();
+
+        assert!(entry
+            .get_file_attributes()
+            .unwrap()
+            .contains(FileAttributeFlags::COMPRESSED));
+        assert!(entry
+            .get_extents()
+            .unwrap()
+            .iter()
+            .any(Extent::is_compressed));
+
+        let mut expected = Vec::new();
+        entry.read_to_end(&mut expected).unwrap();
+        entry.rewind().unwrap();
+        assert!(expected.len() > 64 << 10);
+
+        // Neither on a cluster nor on a compression unit boundary.
+        let (read_at, sought) = read_in_halves(&mut entry, (64 << 10) + 1234);
+
+        assert_eq!(read_at, expected);
+        assert_eq!(sought, expected);
+    }
+
+    #[test]
+    fn test_rewind() {
+        let volume = sample_volume().unwrap();
+        let mut entry = file_entry(&volume).unwrap();
+
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data).unwrap();
+        assert_eq!(entry.read(&mut [0; 10]).unwrap(), 0);
+
+        entry.rewind().unwrap();
+
+        let mut buf = vec![0; 10];
+        entry.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, data[..10]);
+    }
+
+    #[test]
+    fn test_rewind_data_stream() {
+        let volume = sample_volume().unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(29).unwrap();
+
+        for i in data_attribute_indices(&entry) {
+            let mut stream = entry.open_data_stream_by_attribute_index(i).unwrap();
+
+            let mut data = Vec::new();
+            stream.read_to_end(&mut data).unwrap();
+
+            stream.rewind().unwrap();
+
+            let mut again = Vec::new();
+            stream.read_to_end(&mut again).unwrap();
+            assert_eq!(again, data);
+        }
+    }
+
+    #[test]
+    fn test_seek_to_negative_position() {
+        let volume = sample_volume().unwrap();
+        let mut entry = file_entry(&volume).unwrap();
+        let size = entry.get_size().unwrap();
+
+        entry.seek(SeekFrom::Start(4)).unwrap();
+
+        for pos in &[
+            SeekFrom::Current(-5),
+            SeekFrom::End(-(size as i64) - 1),
+            SeekFrom::Start(u64::max_value()),
+        ] {
+            let error = entry.seek(*pos).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        }
+
+        // A failed seek leaves the offset unchanged.
+        assert_eq!(entry.get_offset().unwrap(), 4);
+        assert_eq!(entry.seek(SeekFrom::Current(-4)).unwrap(), 0);
+        assert_eq!(entry.seek(SeekFrom::End(-(size as i64))).unwrap(), 0);
+    }
+
     #[test]
     fn test_read_to_end() {
         let volume = sample_volume().unwrap();
@@ -807,4 +2316,394 @@ mod tests {
 
         assert_eq!(buf.len(), 75776);
     }
+
+    #[test]
+    fn test_read_all() {
+        let volume = sample_volume().unwrap();
+        let mut entry = file_entry(&volume).unwrap();
+
+        let mut expected = Vec::new();
+        entry.read_to_end(&mut expected).unwrap();
+
+        let data = entry.read_all().unwrap();
+
+        assert_eq!(data.len(), 75776);
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn test_read_all_with_small_chunks() {
+        let volume = sample_volume().unwrap();
+        let mut entry = file_entry(&volume).unwrap();
+
+        let data = entry.read_all_with_chunk_size(1000).unwrap();
+
+        assert_eq!(data, entry.read_all().unwrap());
+    }
+
+    #[test]
+    fn test_read_at() {
+        let volume = sample_volume().unwrap();
+        let entry = file_entry(&volume).unwrap();
+
+        let mut buf = vec![0; 10];
+        entry.read_at(10, &mut buf).unwrap();
+
+        assert_eq!(buf, [16, 0, 0, 0, 0, 0, 1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn test_get_extended_attributes_when_absent() {
+        let volume = sample_volume().unwrap();
+        let entry = file_entry(&volume).unwrap();
+
+        assert!(entry.get_extended_attributes().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_extended_attributes_of_wsl_file() {
+        let volume = wsl_volume().unwrap();
+        let entry = volume
+            .get_file_entry_by_path("\\file-r-1.dat", MatchMode::Exact)
+            .unwrap();
+
+        let extended_attributes = entry.get_extended_attributes().unwrap();
+
+        assert_eq!(
+            extended_attributes
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>(),
+            ["$LXUID", "$LXGID", "$LXMOD"]
+        );
+        assert!(extended_attributes
+            .iter()
+            .any(|(name, value)| name == "$LXMOD" && value[..] == 0o100_644_u32.to_le_bytes()));
+    }
+
+    #[test]
+    fn test_get_logged_utility_stream_when_absent() {
+        let volume = sample_volume().unwrap();
+        let entry = file_entry(&volume).unwrap();
+
+        assert!(entry.get_logged_utility_stream("$EFS").unwrap().is_none());
+        assert!(entry
+            .get_logged_utility_stream("$TXF_DATA")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_get_efs_logged_utility_stream() {
+        let volume = efs_volume().unwrap();
+        let entry = volume
+            .get_file_entry_by_path("\\file-r-1.dat", MatchMode::Exact)
+            .unwrap();
+
+        let efs = entry.get_logged_utility_stream("$EFS").unwrap().unwrap();
+
+        // The header of `$EFS` starts with the length of the whole blob, followed by the state
+        // (always 0) and the version (2 for Windows XP and later).
+        assert_eq!(crate::mft::read_u32(&efs, 0), Some(efs.len() as u32));
+        assert_eq!(crate::mft::read_u32(&efs, 4), Some(0));
+        assert_eq!(crate::mft::read_u32(&efs, 8), Some(2));
+    }
+
+    #[test]
+    fn test_get_reparse_point_data_of_regular_file() {
+        let volume = sample_volume().unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(27).unwrap();
+
+        assert_eq!(entry.get_reparse_point_data().unwrap(), None);
+    }
+
+    #[test]
+    fn test_get_reparse_point_data_of_junction() {
+        let volume = junction_volume().unwrap();
+        let entry = volume
+            .get_file_entry_by_path("\\dir-r-4", MatchMode::Exact)
+            .unwrap();
+
+        let (tag, data) = entry.get_reparse_point_data().unwrap().unwrap();
+        assert_eq!(tag, Ok(ReparsePointTag::MountPoint));
+        assert!(!data.is_empty());
+
+        // Mount points start with the offset and length of the substitute name, relative to the
+        // path buffer that follows the 8-byte header.
+        let offset = 8 + read_u16(&data, 0).unwrap() as usize;
+        let length = read_u16(&data, 2).unwrap() as usize;
+        let substitute_name = String::from_utf16(
+            &data[offset..offset + length]
+                .chunks_exact(2)
+                .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                .collect::<Vec<u16>>(),
+        )
+        .unwrap();
+
+        assert_eq!(substitute_name, "\\??\\C:\\System Volume Information");
+        assert_eq!(
+            Some(substitute_name),
+            entry.get_reparse_point_substitute_name().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_missing_sub_file_entry_is_not_found() {
+        let volume = sample_volume().unwrap();
+        let root = volume.get_root_directory().unwrap();
+
+        let result = root.get_sub_file_entry_by_name("does-not-exist");
+
+        assert!(result.unwrap_err().is_not_found());
+    }
+
+    #[test]
+    fn test_boundary_inputs_return_errors() {
+        let volume = sample_volume().unwrap();
+        let root = volume.get_root_directory().unwrap();
+
+        assert!(!matches!(
+            root.find_sub_entry("", MatchMode::Exact),
+            Ok(Some(_))
+        ));
+        assert!(root.get_sub_file_entry(-1).is_err());
+        assert!(root.get_sub_file_entry(i32::MAX).is_err());
+        assert!(root.get_name_by_attribute_index(-1).is_err());
+        assert!(root
+            .get_parent_file_reference_by_attribute_index(c_int::MAX)
+            .is_err());
+    }
+
+    #[test]
+    fn test_describe() {
+        let volume = sample_volume().unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(27).unwrap();
+
+        let file_reference = FileReference::from(entry.get_file_reference().unwrap());
+        let description = entry.describe().unwrap();
+
+        assert!(description.starts_with("file-r-1.dat "));
+        assert!(description.contains(&format!("27-{}", file_reference.sequence_number())));
+    }
+
+    #[test]
+    fn test_get_name_by_attribute_index() {
+        let volume = sample_volume().unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(27).unwrap();
+
+        let attribute_index = entry.get_name_attribute_index().unwrap().unwrap();
+
+        assert_eq!(
+            entry.get_name_by_attribute_index(attribute_index).unwrap(),
+            "file-r-1.dat"
+        );
+        assert_eq!(
+            mft_entry_index(
+                entry
+                    .get_parent_file_reference_by_attribute_index(attribute_index)
+                    .unwrap()
+            ),
+            5
+        );
+        assert_eq!(entry.get_base_record_file_reference().unwrap(), 0);
+        assert!(entry.get_journal_sequence_number().unwrap().is_some());
+        assert!(!entry.is_empty().unwrap());
+    }
+
+    #[test]
+    fn test_iter_names_of_entry_with_short_name() {
+        let volume = sample_volume().unwrap();
+        // System Volume Information
+        let entry = volume.get_file_entry_by_mft_idx(31).unwrap();
+
+        let names: Vec<(NameNamespace, String)> =
+            entry.iter_names().unwrap().map(|n| n.unwrap()).collect();
+
+        assert_eq!(
+            names,
+            vec![
+                (NameNamespace::Dos, "SYSTEM~1".to_string()),
+                (
+                    NameNamespace::Windows,
+                    "System Volume Information".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_iter_names_of_entry_with_single_name() {
+        let volume = sample_volume().unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(27).unwrap();
+
+        let names: Vec<(NameNamespace, String)> =
+            entry.iter_names().unwrap().map(|n| n.unwrap()).collect();
+
+        assert_eq!(
+            names,
+            vec![(NameNamespace::WindowsAndDos, "file-r-1.dat".to_string())]
+        );
+    }
+
+    fn data_attribute_indices(entry: &FileEntry) -> Vec<i32> {
+        entry
+            .get_attribute_types()
+            .unwrap()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, t)| *t == AttributeType::Data)
+            .map(|(i, _)| i as i32)
+            .collect()
+    }
+
+    #[test]
+    fn test_open_default_data_stream_by_attribute_index() {
+        let volume = sample_volume().unwrap();
+        let mut entry = volume.get_file_entry_by_mft_idx(29).unwrap();
+        let expected = entry.read_all().unwrap();
+
+        let streams: Vec<DataStream> = data_attribute_indices(&entry)
+            .into_iter()
+            .map(|i| entry.open_data_stream_by_attribute_index(i).unwrap())
+            .collect();
+
+        let mut default_stream = streams.into_iter().find(|s| s.is_default()).unwrap();
+        assert_eq!(default_stream.get_name().unwrap(), "");
+        assert_eq!(default_stream.get_size().unwrap(), expected.len() as u64);
+
+        let mut data = Vec::new();
+        default_stream.read_to_end(&mut data).unwrap();
+        assert_eq!(data, expected);
+
+        let tail = min(expected.len(), 10);
+        default_stream.seek(SeekFrom::End(-(tail as i64))).unwrap();
+        let mut data = Vec::new();
+        default_stream.read_to_end(&mut data).unwrap();
+        assert_eq!(data, &expected[expected.len() - tail..]);
+    }
+
+    #[test]
+    fn test_open_alternate_data_stream_by_attribute_index() {
+        let volume = sample_volume().unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(29).unwrap();
+
+        let names: Vec<String> = data_attribute_indices(&entry)
+            .into_iter()
+            .map(|i| entry.open_data_stream_by_attribute_index(i).unwrap())
+            .filter(|s| !s.is_default())
+            .map(|s| s.get_name().unwrap())
+            .collect();
+
+        assert_eq!(names, vec!["here".to_string()]);
+    }
+
+    #[test]
+    fn test_open_data_stream_of_non_data_attribute_fails() {
+        let volume = sample_volume().unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(29).unwrap();
+
+        // The first attribute is $STANDARD_INFORMATION.
+        assert!(entry.open_data_stream_by_attribute_index(0).is_err());
+        assert!(entry.open_data_stream_by_attribute_index(-1).is_err());
+    }
+
+    #[test]
+    fn test_same_entry_fetched_two_ways() {
+        let volume = sample_volume().unwrap();
+        let by_idx = volume.get_file_entry_by_mft_idx(27).unwrap();
+        let by_path = volume
+            .get_file_entry_by_path("\\file-r-1.dat", MatchMode::Exact)
+            .unwrap();
+        let other = volume.get_file_entry_by_mft_idx(29).unwrap();
+
+        assert!(by_idx.same_entry_as(&by_path).unwrap());
+        assert_eq!(by_idx, by_path);
+
+        assert!(!by_idx.same_entry_as(&other).unwrap());
+        assert_ne!(by_idx, other);
+    }
+
+    #[test]
+    fn test_entries_of_different_volumes_are_not_the_same() {
+        let volume = sample_volume().unwrap();
+        let other_volume = sample_volume().unwrap();
+
+        let entry = volume.get_file_entry_by_mft_idx(27).unwrap();
+        let other_entry = other_volume.get_file_entry_by_mft_idx(27).unwrap();
+
+        assert!(!entry.same_entry_as(&other_entry).unwrap());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "`FileEntry` was used after it was freed")]
+    fn test_use_after_free_panics() {
+        let volume = sample_volume().unwrap();
+        let mut entry = mem::ManuallyDrop::new(file_entry(&volume).unwrap());
+
+        unsafe { mem::ManuallyDrop::drop(&mut entry) };
+
+        let _ = entry.get_size();
+    }
+
+    #[test]
+    fn test_timestamp_variants_agree() {
+        let volume = sample_volume().unwrap();
+        let entry = file_entry(&volume).unwrap();
+
+        let datetime = entry.get_creation_time().unwrap().unwrap();
+        let nanos = entry.get_creation_time_unix_nanos().unwrap();
+        let since_epoch = entry
+            .get_creation_time_systemtime()
+            .unwrap()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap();
+
+        assert_eq!(nanos, datetime.timestamp_nanos());
+        assert_eq!(since_epoch.as_nanos() as i64, nanos);
+        assert_eq!(
+            entry.get_modification_time_unix_nanos().unwrap(),
+            entry
+                .get_modification_time()
+                .unwrap()
+                .unwrap()
+                .timestamp_nanos()
+        );
+    }
+
+    #[test]
+    fn test_get_missing_alternate_data_stream_is_not_found() {
+        let volume = sample_volume().unwrap();
+        let entry = file_entry(&volume).unwrap();
+
+        assert!(!entry.has_alternate_data_stream_by_name("missing").unwrap());
+
+        let result = entry.get_alternate_data_stream_by_name("missing");
+
+        assert!(result.unwrap_err().is_not_found());
+    }
+
+    #[test]
+    fn test_get_number_of_alternate_data_streams() {
+        let volume = sample_volume().unwrap();
+
+        let plain = volume.get_file_entry_by_mft_idx(27).unwrap();
+        assert_eq!(plain.get_number_of_alternate_data_streams().unwrap(), 0);
+
+        // file-r-3.dat carries a single stream named "here".
+        let with_ads = volume.get_file_entry_by_mft_idx(29).unwrap();
+        assert_eq!(with_ads.get_number_of_alternate_data_streams().unwrap(), 1);
+        assert!(with_ads.has_alternate_data_stream_by_name("here").unwrap());
+    }
+
+    #[test]
+    fn test_get_extents() {
+        let volume = sample_volume().unwrap();
+        let entry = file_entry(&volume).unwrap();
+
+        let extents = entry.get_extents().unwrap();
+        let total_size: u64 = extents.iter().map(|e| e.size).sum();
+
+        assert!(total_size >= entry.get_size().unwrap());
+    }
 }
@@ -0,0 +1,102 @@
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Number of 100 nanosecond intervals between 1601-01-01 and 1970-01-01.
+const FILETIME_UNIX_EPOCH_DIFFERENCE: u64 = 116_444_736_000_000_000;
+
+/// Converts a FILETIME (100 nanosecond intervals since 1601-01-01) to a `SystemTime`.
+/// Returns `None` for a zero FILETIME, which denotes an unset timestamp.
+pub fn filetime_to_system_time(filetime: u64) -> Option<SystemTime> {
+    if filetime == 0 {
+        return None;
+    }
+
+    if filetime >= FILETIME_UNIX_EPOCH_DIFFERENCE {
+        let intervals = filetime - FILETIME_UNIX_EPOCH_DIFFERENCE;
+        UNIX_EPOCH.checked_add(intervals_to_duration(intervals))
+    } else {
+        let intervals = FILETIME_UNIX_EPOCH_DIFFERENCE - filetime;
+        UNIX_EPOCH.checked_sub(intervals_to_duration(intervals))
+    }
+}
+
+/// Converts a FILETIME to nanoseconds since the Unix epoch, which are negative before 1970.
+/// Returns `None` for a zero FILETIME, or if the result does not fit in an `i64` (i.e. it is not
+/// between the years 1677 and 2262).
+pub fn filetime_to_unix_nanos(filetime: u64) -> Option<i64> {
+    if filetime == 0 {
+        return None;
+    }
+
+    let intervals = i128::from(filetime) - i128::from(FILETIME_UNIX_EPOCH_DIFFERENCE);
+
+    i64::try_from(intervals * 100).ok()
+}
+
+fn intervals_to_duration(intervals: u64) -> Duration {
+    Duration::new(
+        intervals / 10_000_000,
+        ((intervals % 10_000_000) * 100) as u32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_filetime_is_none() {
+        assert_eq!(filetime_to_system_time(0), None);
+    }
+
+    #[test]
+    fn test_unix_epoch() {
+        assert_eq!(
+            filetime_to_system_time(FILETIME_UNIX_EPOCH_DIFFERENCE),
+            Some(UNIX_EPOCH)
+        );
+    }
+
+    #[test]
+    fn test_known_filetime() {
+        // 2019-01-01T00:00:00.5Z
+        let time = filetime_to_system_time(131_907_744_005_000_000).unwrap();
+
+        assert_eq!(
+            time.duration_since(UNIX_EPOCH).unwrap(),
+            Duration::from_millis(1_546_300_800_500)
+        );
+    }
+
+    #[test]
+    fn test_pre_1970_filetime() {
+        // 1960-01-01T00:00:00.25Z
+        let filetime = 113_288_544_002_500_000;
+
+        assert_eq!(
+            UNIX_EPOCH
+                .duration_since(filetime_to_system_time(filetime).unwrap())
+                .unwrap(),
+            Duration::from_millis(315_619_199_750)
+        );
+        assert_eq!(
+            filetime_to_unix_nanos(filetime),
+            Some(-315_619_199_750_000_000)
+        );
+    }
+
+    #[test]
+    fn test_unix_nanos() {
+        assert_eq!(filetime_to_unix_nanos(0), None);
+        assert_eq!(
+            filetime_to_unix_nanos(FILETIME_UNIX_EPOCH_DIFFERENCE),
+            Some(0)
+        );
+        assert_eq!(
+            filetime_to_unix_nanos(131_907_744_005_000_000),
+            Some(1_546_300_800_500_000_000)
+        );
+        // 1601-01-01 is before the earliest time an `i64` of nanoseconds can hold.
+        assert_eq!(filetime_to_unix_nanos(1), None);
+    }
+}
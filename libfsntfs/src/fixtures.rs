@@ -1,11 +1,52 @@
-use crate::attribute::{Attribute, AttributeType};
+use crate::attribute::{Attribute, AttributeType, ReparsePointTag};
 use crate::error::Error;
 use crate::file_entry::FileEntry;
+use crate::mft::{apply_fixups, read_u16, read_u32};
 use crate::volume::{AccessMode, Volume};
 use env_logger;
+#[cfg(feature = "test-fixtures")]
+use flate2::read::GzDecoder;
 use lazy_static::lazy_static;
-use std::path::PathBuf;
 use libbfio_rs::handle::{Handle, LibbfioAccessFlags};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::{env, fs, io, process};
+use tempdir::TempDir;
+
+/// Counts heap allocations made by the current thread, see `count_allocations`.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = Cell::new(0);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Runs `f` and returns its result with the number of heap allocations it made.
+pub fn count_allocations<F: FnOnce() -> R, R>(f: F) -> (R, usize) {
+    let before = ALLOCATIONS.with(|count| count.get());
+    let result = f();
+    let after = ALLOCATIONS.with(|count| count.get());
+
+    (result, after - before)
+}
 
 lazy_static! {
     static ref LOGGER_INIT: () = {
@@ -13,9 +54,22 @@ lazy_static! {
     };
 }
 
-pub fn sample_volume_path() -> String {
+/// Environment variable overriding the path of the sample image.
+const SAMPLE_IMAGE_VAR: &str = "LIBFSNTFS_SAMPLE_IMAGE";
+
+/// The sample image with its unallocated clusters zeroed, for when the external image is missing.
+#[cfg(feature = "test-fixtures")]
+const EMBEDDED_SAMPLE: &[u8] = include_bytes!("../examples/ntfs-img-kw-1.dd.gz");
+
+#[cfg(feature = "test-fixtures")]
+lazy_static! {
+    static ref EMBEDDED_SAMPLE_PATH: String = decompress_embedded_sample().unwrap();
+}
+
+fn examples_dir() -> PathBuf {
     let this_file = file!();
-    let sample = PathBuf::from(this_file)
+
+    PathBuf::from(this_file)
         .parent()
         .unwrap()
         .parent()
@@ -23,11 +77,44 @@ pub fn sample_volume_path() -> String {
         .parent()
         .unwrap()
         .join("examples")
-        .join("ntfs-img-kw-1.dd");
+}
+
+pub fn sample_volume_path() -> String {
+    if let Ok(path) = env::var(SAMPLE_IMAGE_VAR) {
+        return path;
+    }
+
+    let sample = examples_dir().join("ntfs-img-kw-1.dd");
+
+    #[cfg(feature = "test-fixtures")]
+    {
+        if !sample.exists() {
+            return EMBEDDED_SAMPLE_PATH.clone();
+        }
+    }
 
     sample.to_str().unwrap().to_string()
 }
 
+/// Decompresses the embedded sample image into the temporary directory, where it is shared by
+/// subsequent test runs.
+#[cfg(feature = "test-fixtures")]
+fn decompress_embedded_sample() -> io::Result<String> {
+    let path = env::temp_dir().join("libfsntfs-rs-ntfs-img-kw-1.dd");
+
+    if !path.exists() {
+        // Concurrent test runs each write their own copy, and only rename a complete one.
+        let partial = path.with_extension(format!("{}.partial", process::id()));
+
+        let mut image = Vec::new();
+        GzDecoder::new(EMBEDDED_SAMPLE).read_to_end(&mut image)?;
+        fs::write(&partial, image)?;
+        fs::rename(&partial, &path)?;
+    }
+
+    Ok(path.to_str().unwrap().to_string())
+}
+
 pub fn sample_volume_io_handle() -> Result<Handle, Error> {
     let volume_path = sample_volume_path();
     Ok(Handle::open_file(volume_path, LibbfioAccessFlags::Read).expect("libbfio failed"))
@@ -38,6 +125,525 @@ pub fn sample_volume() -> Result<Volume, Error> {
     Volume::open(&volume_path, AccessMode::Read)
 }
 
+/// Size of the clusters of the sample image.
+pub const SAMPLE_CLUSTER_SIZE: usize = 512;
+
+/// First of 3617 clusters that are unallocated in the sample image, free for the data of edits.
+pub const SAMPLE_FREE_CLUSTER: u64 = 9167;
+
+/// Offset of the MFT in the sample image, which stores it in a single run.
+const SAMPLE_MFT_OFFSET: usize = 5355 * SAMPLE_CLUSTER_SIZE;
+
+/// Size of the MFT entries of the sample image.
+const SAMPLE_MFT_ENTRY_SIZE: usize = 1024;
+
+/// A copy of the sample image, edited in memory before it is opened.
+pub struct SampleImage {
+    image: Vec<u8>,
+}
+
+impl SampleImage {
+    pub fn new() -> SampleImage {
+        SampleImage {
+            image: fs::read(sample_volume_path()).unwrap(),
+        }
+    }
+
+    /// Replaces every occurrence of `from` by `to`.
+    pub fn replace(&mut self, from: &[u8], to: &[u8]) -> &mut SampleImage {
+        assert_eq!(from.len(), to.len());
+
+        let mut offset = 0;
+
+        while let Some(found) = self.image[offset..]
+            .windows(from.len())
+            .position(|w| w == from)
+        {
+            let start = offset + found;
+            self.image[start..start + to.len()].copy_from_slice(to);
+            offset = start + to.len();
+        }
+
+        self
+    }
+
+    fn mft_entry_offset(idx: u64) -> usize {
+        SAMPLE_MFT_OFFSET + idx as usize * SAMPLE_MFT_ENTRY_SIZE
+    }
+
+    /// Overwrites MFT entry `idx` with zeroes, as if it had never been written.
+    pub fn clear_mft_entry(&mut self, idx: u64) -> &mut SampleImage {
+        let offset = Self::mft_entry_offset(idx);
+
+        for byte in &mut self.image[offset..offset + SAMPLE_MFT_ENTRY_SIZE] {
+            *byte = 0;
+        }
+
+        self
+    }
+
+    /// Edits MFT entry `idx` with its fixups applied, and protects it again afterwards.
+    pub fn edit_mft_entry<F: FnOnce(&mut SampleMftEntry)>(
+        &mut self,
+        idx: u64,
+        edit: F,
+    ) -> &mut SampleImage {
+        let offset = Self::mft_entry_offset(idx);
+        let record = &mut self.image[offset..offset + SAMPLE_MFT_ENTRY_SIZE];
+
+        let mut entry = SampleMftEntry {
+            data: record.to_vec(),
+        };
+        apply_fixups(&mut entry.data).unwrap();
+        edit(&mut entry);
+        entry.protect();
+
+        record.copy_from_slice(&entry.data);
+
+        self
+    }
+
+    /// Writes `data` to the clusters starting at `first_cluster`.
+    pub fn write_clusters(&mut self, first_cluster: u64, data: &[u8]) -> &mut SampleImage {
+        let offset = first_cluster as usize * SAMPLE_CLUSTER_SIZE;
+        self.image[offset..offset + data.len()].copy_from_slice(data);
+
+        self
+    }
+
+    /// Writes the image to a temporary directory named after `name` and opens it. The copy is
+    /// removed when the returned volume is dropped.
+    pub fn open(&self, name: &str) -> Result<SampleVolume, Error> {
+        let dir = TempDir::new(&format!("libfsntfs-rs-{}", name)).unwrap();
+        let path = dir.path().join(format!("{}.dd", name));
+        fs::write(&path, &self.image).unwrap();
+
+        let volume = Volume::open(path.to_str().unwrap(), AccessMode::Read)?;
+
+        Ok(SampleVolume { volume, _dir: dir })
+    }
+
+    /// Opens the image from memory, along with the number of bytes libfsntfs has read from it.
+    pub fn open_counting_reads(&self) -> Result<(Volume, Rc<Cell<u64>>), Error> {
+        let bytes_read = Rc::new(Cell::new(0));
+        let reader = CountingReader {
+            inner: io::Cursor::new(self.image.clone()),
+            bytes_read: Rc::clone(&bytes_read),
+        };
+
+        Ok((Volume::open_stream(reader)?, bytes_read))
+    }
+}
+
+/// A volume opened from an edited copy of the sample image, see `SampleImage::open`.
+pub struct SampleVolume {
+    // Dropped before the directory, so that the image is closed when it is removed.
+    volume: Volume,
+    _dir: TempDir,
+}
+
+impl Deref for SampleVolume {
+    type Target = Volume;
+
+    fn deref(&self) -> &Volume {
+        &self.volume
+    }
+}
+
+impl DerefMut for SampleVolume {
+    fn deref_mut(&mut self) -> &mut Volume {
+        &mut self.volume
+    }
+}
+
+/// Counts the bytes read through it, see `SampleImage::open_counting_reads`.
+struct CountingReader {
+    inner: io::Cursor<Vec<u8>>,
+    bytes_read: Rc<Cell<u64>>,
+}
+
+impl Read for CountingReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read_count = self.inner.read(buf)?;
+        self.bytes_read
+            .set(self.bytes_read.get() + read_count as u64);
+
+        Ok(read_count)
+    }
+}
+
+impl Seek for CountingReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+/// An MFT entry of a `SampleImage`, see `SampleImage::edit_mft_entry`.
+pub struct SampleMftEntry {
+    data: Vec<u8>,
+}
+
+impl SampleMftEntry {
+    fn u16_at(&self, offset: usize) -> usize {
+        read_u16(&self.data, offset).unwrap() as usize
+    }
+
+    fn u32_at(&self, offset: usize) -> usize {
+        read_u32(&self.data, offset).unwrap() as usize
+    }
+
+    fn set(&mut self, offset: usize, bytes: &[u8]) {
+        self.data[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+
+    /// Moves the last two bytes of every sector into the fixup array, undoing `apply_fixups`.
+    fn protect(&mut self) {
+        let fixup_offset = self.u16_at(4);
+        let signature = self.data[fixup_offset..fixup_offset + 2].to_vec();
+
+        for i in 1..self.u16_at(6) {
+            let sector_end = i * 512 - 2;
+            let original = self.data[sector_end..sector_end + 2].to_vec();
+
+            self.set(fixup_offset + i * 2, &original);
+            self.set(sector_end, &signature);
+        }
+    }
+
+    /// Offsets of the attributes, followed by the offset of the end marker.
+    fn attribute_offsets(&self) -> Vec<usize> {
+        let mut offsets = vec![self.u16_at(0x14)];
+
+        while let Some(&offset) = offsets.last().filter(|&&o| self.u32_at(o) != 0xffff_ffff) {
+            offsets.push(offset + self.u32_at(offset + 4));
+        }
+
+        offsets
+    }
+
+    /// Type and name of the attribute at `offset`, in the order attributes are stored.
+    fn attribute_key(&self, offset: usize) -> (usize, String) {
+        let name_offset = offset + self.u16_at(offset + 10);
+        let name = (0..self.data[offset + 9] as usize)
+            .map(|i| self.u16_at(name_offset + i * 2) as u16)
+            .collect::<Vec<_>>();
+
+        (self.u32_at(offset), String::from_utf16(&name).unwrap())
+    }
+
+    fn find_attribute(&self, attribute_type: AttributeType, name: &str) -> usize {
+        let key = (attribute_type as usize, name.to_string());
+
+        *self
+            .attribute_offsets()
+            .iter()
+            .find(|&&offset| self.attribute_key(offset) == key)
+            .unwrap()
+    }
+
+    fn splice(&mut self, offset: usize, removed: usize, inserted: &[u8]) {
+        let used = self.u32_at(0x18) + inserted.len() - removed;
+        assert!(used <= self.u32_at(0x1c), "MFT entry is full");
+
+        self.data
+            .splice(offset..offset + removed, inserted.iter().cloned());
+        self.data.resize(self.u32_at(0x1c), 0);
+        self.set(0x18, &(used as u32).to_le_bytes());
+    }
+
+    /// Inserts an attribute built by `resident_attribute` or `non_resident_attribute`, keeping
+    /// the attributes sorted and giving it the next attribute identifier.
+    pub fn insert_attribute(&mut self, mut attribute: Vec<u8>) -> &mut SampleMftEntry {
+        let next_id = self.u16_at(0x28) as u16;
+        attribute[14..16].copy_from_slice(&next_id.to_le_bytes());
+        self.set(0x28, &(next_id + 1).to_le_bytes());
+
+        let new = SampleMftEntry {
+            data: attribute.clone(),
+        };
+        let offsets = self.attribute_offsets();
+        let offset = offsets
+            .iter()
+            .cloned()
+            .find(|&o| {
+                self.u32_at(o) == 0xffff_ffff || self.attribute_key(o) > new.attribute_key(0)
+            })
+            .unwrap();
+
+        self.splice(offset, 0, &attribute);
+
+        self
+    }
+
+    pub fn remove_attribute(
+        &mut self,
+        attribute_type: AttributeType,
+        name: &str,
+    ) -> &mut SampleMftEntry {
+        let offset = self.find_attribute(attribute_type, name);
+        let length = self.u32_at(offset + 4);
+
+        self.splice(offset, length, &[]);
+
+        self
+    }
+
+    /// Retrieves the value of a resident attribute.
+    pub fn resident_value(&self, attribute_type: AttributeType, name: &str) -> Vec<u8> {
+        let offset = self.find_attribute(attribute_type, name);
+        let value_offset = offset + self.u16_at(offset + 20);
+
+        self.data[value_offset..value_offset + self.u32_at(offset + 16)].to_vec()
+    }
+
+    /// Replaces the value of a resident attribute, resizing the attribute to fit it.
+    pub fn set_resident_value(
+        &mut self,
+        attribute_type: AttributeType,
+        name: &str,
+        value: &[u8],
+    ) -> &mut SampleMftEntry {
+        let offset = self.find_attribute(attribute_type, name);
+        let value_offset = self.u16_at(offset + 20);
+
+        let mut attribute = self.data[offset..offset + value_offset].to_vec();
+        attribute.extend_from_slice(value);
+        attribute.resize(align8(attribute.len()), 0);
+        let length = attribute.len() as u32;
+        attribute[4..8].copy_from_slice(&length.to_le_bytes());
+        attribute[16..20].copy_from_slice(&(value.len() as u32).to_le_bytes());
+
+        let old_length = self.u32_at(offset + 4);
+        self.splice(offset, old_length, &attribute);
+
+        self
+    }
+
+    /// Inserts `entry` into the resident index root `index_name`, before the entry at `position`.
+    /// Entries must be inserted in the order of the collation rule of the index.
+    pub fn insert_index_entry(
+        &mut self,
+        index_name: &str,
+        position: usize,
+        entry: &[u8],
+    ) -> &mut SampleMftEntry {
+        let mut index_root = SampleMftEntry {
+            data: self.resident_value(AttributeType::IndexRoot, index_name),
+        };
+
+        let mut offset = 16 + index_root.u32_at(16);
+        for _ in 0..position {
+            offset += index_root.u16_at(offset + 8);
+        }
+
+        let total_size = (index_root.u32_at(20) + entry.len()) as u32;
+        index_root
+            .data
+            .splice(offset..offset, entry.iter().cloned());
+        index_root.set(20, &total_size.to_le_bytes());
+        index_root.set(24, &total_size.to_le_bytes());
+
+        self.set_resident_value(AttributeType::IndexRoot, index_name, &index_root.data)
+    }
+
+    /// Sets the flags of the MFT entry, e.g. `0x1` for in use and `0x2` for a directory.
+    pub fn set_entry_flags(&mut self, flags: u16) -> &mut SampleMftEntry {
+        self.set(0x16, &flags.to_le_bytes());
+
+        self
+    }
+
+    /// Sets the number of hard links of the MFT entry.
+    pub fn set_link_count(&mut self, count: u16) -> &mut SampleMftEntry {
+        self.set(0x12, &count.to_le_bytes());
+
+        self
+    }
+
+    /// Sets the file attribute flags stored in `$STANDARD_INFORMATION`.
+    pub fn set_file_attribute_flags(&mut self, flags: u32) -> &mut SampleMftEntry {
+        let mut value = self.resident_value(AttributeType::StandardInformation, "");
+        value[32..36].copy_from_slice(&flags.to_le_bytes());
+
+        self.set_resident_value(AttributeType::StandardInformation, "", &value)
+    }
+
+    /// Sets the valid data size of the non-resident attribute of the given type and name.
+    pub fn set_valid_data_size(
+        &mut self,
+        attribute_type: AttributeType,
+        name: &str,
+        size: u64,
+    ) -> &mut SampleMftEntry {
+        let offset = self.find_attribute(attribute_type, name);
+        self.set(offset + 0x38, &size.to_le_bytes());
+
+        self
+    }
+}
+
+fn align8(size: usize) -> usize {
+    (size + 7) & !7
+}
+
+/// Encodes `name` as the UTF-16 little-endian bytes NTFS stores names in.
+pub fn utf16_bytes(name: &str) -> Vec<u8> {
+    name.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+/// Builds the header shared by resident and non-resident attributes, followed by the name.
+fn attribute_header(attribute_type: AttributeType, name: &str, header_size: usize) -> Vec<u8> {
+    let name = utf16_bytes(name);
+
+    let mut attribute = vec![0; header_size];
+    attribute[0..4].copy_from_slice(&(attribute_type as u32).to_le_bytes());
+    attribute[8] = (header_size > 24) as u8;
+    attribute[9] = (name.len() / 2) as u8;
+    attribute[10..12].copy_from_slice(&(header_size as u16).to_le_bytes());
+    attribute.extend_from_slice(&name);
+    attribute.resize(align8(attribute.len()), 0);
+
+    attribute
+}
+
+/// Builds a resident attribute, to insert with `SampleMftEntry::insert_attribute`.
+pub fn resident_attribute(attribute_type: AttributeType, name: &str, value: &[u8]) -> Vec<u8> {
+    let mut attribute = attribute_header(attribute_type, name, 24);
+    let value_offset = attribute.len();
+
+    attribute.extend_from_slice(value);
+    attribute.resize(align8(attribute.len()), 0);
+    let length = attribute.len() as u32;
+    attribute[4..8].copy_from_slice(&length.to_le_bytes());
+    attribute[16..20].copy_from_slice(&(value.len() as u32).to_le_bytes());
+    attribute[20..22].copy_from_slice(&(value_offset as u16).to_le_bytes());
+    attribute[22] = (attribute_type == AttributeType::FileName) as u8;
+
+    attribute
+}
+
+/// Builds a non-resident attribute of `size` bytes, to insert with
+/// `SampleMftEntry::insert_attribute`.
+///
+/// `runs` are `(first cluster, number of clusters)`, without a first cluster if sparse, and
+/// `flags` are the attribute data flags, e.g. `0x8000` for sparse or `0x1` for compressed.
+pub fn non_resident_attribute(
+    attribute_type: AttributeType,
+    name: &str,
+    runs: &[(Option<u64>, u64)],
+    size: u64,
+    flags: u16,
+    compression_unit: u16,
+) -> Vec<u8> {
+    // Compressed and sparse attributes store the number of allocated clusters after the sizes.
+    let header_size = if flags & 0x80ff != 0 { 72 } else { 64 };
+    let mut attribute = attribute_header(attribute_type, name, header_size);
+    let runs_offset = attribute.len();
+
+    let clusters: u64 = runs.iter().map(|&(_, count)| count).sum();
+    let allocated: u64 = runs
+        .iter()
+        .filter(|(first_cluster, _)| first_cluster.is_some())
+        .map(|&(_, count)| count)
+        .sum();
+    let cluster_size = SAMPLE_CLUSTER_SIZE as u64;
+
+    attribute[12..14].copy_from_slice(&flags.to_le_bytes());
+    attribute[24..32].copy_from_slice(&(clusters - 1).to_le_bytes());
+    attribute[32..34].copy_from_slice(&(runs_offset as u16).to_le_bytes());
+    attribute[34..36].copy_from_slice(&compression_unit.to_le_bytes());
+    attribute[40..48].copy_from_slice(&(clusters * cluster_size).to_le_bytes());
+    attribute[48..56].copy_from_slice(&size.to_le_bytes());
+    attribute[56..64].copy_from_slice(&size.to_le_bytes());
+
+    if header_size == 72 {
+        attribute[64..72].copy_from_slice(&(allocated * cluster_size).to_le_bytes());
+    }
+
+    let mut previous_cluster = 0;
+
+    for &(first_cluster, count) in runs {
+        let count = minimal_le_bytes(count as i64);
+        let offset = match first_cluster {
+            Some(first_cluster) => {
+                let offset = minimal_le_bytes(first_cluster as i64 - previous_cluster);
+                previous_cluster = first_cluster as i64;
+                offset
+            }
+            None => Vec::new(),
+        };
+
+        attribute.push((offset.len() << 4 | count.len()) as u8);
+        attribute.extend_from_slice(&count);
+        attribute.extend_from_slice(&offset);
+    }
+
+    attribute.push(0);
+    attribute.resize(align8(attribute.len()), 0);
+    let length = attribute.len() as u32;
+    attribute[4..8].copy_from_slice(&length.to_le_bytes());
+
+    attribute
+}
+
+/// Builds the value of a `$FILE_NAME` attribute, which is also the key of `$I30` index entries.
+pub fn file_name_value(
+    parent_file_reference: u64,
+    name: &str,
+    file_attribute_flags: u32,
+) -> Vec<u8> {
+    let name = utf16_bytes(name);
+
+    let mut value = vec![0; 66];
+    value[0..8].copy_from_slice(&parent_file_reference.to_le_bytes());
+    for time in value[8..40].chunks_exact_mut(8) {
+        time.copy_from_slice(&132_223_104_000_000_000_u64.to_le_bytes());
+    }
+    value[56..60].copy_from_slice(&file_attribute_flags.to_le_bytes());
+    value[64] = (name.len() / 2) as u8;
+    value[65] = 1;
+    value.extend_from_slice(&name);
+
+    value
+}
+
+/// Builds an entry of a `$I30` index, referencing `file_reference` under the `$FILE_NAME` key.
+pub fn file_name_index_entry(file_reference: u64, key: &[u8]) -> Vec<u8> {
+    let mut entry = vec![0; 16];
+    entry[0..8].copy_from_slice(&file_reference.to_le_bytes());
+    entry[10..12].copy_from_slice(&(key.len() as u16).to_le_bytes());
+    entry.extend_from_slice(key);
+    entry.resize(align8(entry.len()), 0);
+
+    let length = entry.len() as u16;
+    entry[8..10].copy_from_slice(&length.to_le_bytes());
+
+    entry
+}
+
+/// Encodes `value` in as few little-endian bytes as keep its sign, as in data runs.
+fn minimal_le_bytes(value: i64) -> Vec<u8> {
+    let bytes = value.to_le_bytes();
+    let mut length = 8;
+
+    while length > 1 {
+        let sign = bytes[length - 2] & 0x80;
+
+        match bytes[length - 1] {
+            0 if sign == 0 => length -= 1,
+            0xff if sign != 0 => length -= 1,
+            _ => break,
+        }
+    }
+
+    bytes[..length].to_vec()
+}
+
+/// Opens a copy of the sample volume in which every occurrence of `from` is replaced by `to`.
+pub fn patched_sample_volume(name: &str, from: &[u8], to: &[u8]) -> Result<SampleVolume, Error> {
+    SampleImage::new().replace(from, to).open(name)
+}
+
 pub fn entries_with_data(volume: &Volume) {
     let entries: Vec<FileEntry> = volume
         .iter_entries()
@@ -99,3 +705,440 @@ pub fn file_entry(volume: &Volume) -> Result<FileEntry, Error> {
 
     Ok(f)
 }
+
+/// Opens the sample volume in which `file-r-1.dat` has the extended attributes WSL stores the
+/// owner and mode of Linux files in.
+pub fn wsl_volume() -> Result<SampleVolume, Error> {
+    let extended_attributes: [(&str, u32); 3] =
+        [("$LXUID", 1000), ("$LXGID", 1000), ("$LXMOD", 0o100_644)];
+
+    let mut packed_size = 0;
+    let mut ea = Vec::new();
+
+    for (i, (name, value)) in extended_attributes.iter().enumerate() {
+        let entry_size = (8 + name.len() + 1 + 4 + 3) & !3;
+        let next_entry_offset = if i + 1 < extended_attributes.len() {
+            entry_size as u32
+        } else {
+            0
+        };
+
+        ea.extend_from_slice(&next_entry_offset.to_le_bytes());
+        ea.extend_from_slice(&[0, name.len() as u8]);
+        ea.extend_from_slice(&4_u16.to_le_bytes());
+        ea.extend_from_slice(name.as_bytes());
+        ea.push(0);
+        ea.extend_from_slice(&value.to_le_bytes());
+        ea.resize((ea.len() + 3) & !3, 0);
+
+        packed_size += 5 + name.len() + 4;
+    }
+
+    let mut ea_information = Vec::new();
+    ea_information.extend_from_slice(&(packed_size as u16).to_le_bytes());
+    ea_information.extend_from_slice(&0_u16.to_le_bytes());
+    ea_information.extend_from_slice(&(ea.len() as u32).to_le_bytes());
+
+    SampleImage::new()
+        .edit_mft_entry(27, |entry| {
+            entry
+                .insert_attribute(resident_attribute(
+                    AttributeType::ExtendedInformation,
+                    "",
+                    &ea_information,
+                ))
+                .insert_attribute(resident_attribute(AttributeType::Extended, "", &ea));
+        })
+        .open("wsl")
+}
+
+/// Journal identifier of the change journal of `usn_journal_volume`.
+pub const USN_JOURNAL_ID: u64 = 0x01d5_8e4c_2a1b_3c00;
+
+/// Opens the sample volume in which `$Extend\$Reparse` is turned into a change journal, whose
+/// `$J` stream holds 4 KiB of purged records followed by 1 KiB of records.
+pub fn usn_journal_volume() -> Result<SampleVolume, Error> {
+    let mut max = Vec::new();
+    max.extend_from_slice(&0x200_0000_u64.to_le_bytes());
+    max.extend_from_slice(&0x80_0000_u64.to_le_bytes());
+    max.extend_from_slice(&USN_JOURNAL_ID.to_le_bytes());
+    max.extend_from_slice(&4096_u64.to_le_bytes());
+
+    let runs = [(None, 8), (Some(SAMPLE_FREE_CLUSTER), 2)];
+
+    SampleImage::new()
+        .replace(&utf16_bytes("$Reparse"), &utf16_bytes("$UsnJrnl"))
+        .edit_mft_entry(26, |entry| {
+            entry
+                .set_entry_flags(0x5)
+                .set_file_attribute_flags(0x26)
+                .remove_attribute(AttributeType::IndexRoot, "$R")
+                .insert_attribute(non_resident_attribute(
+                    AttributeType::Data,
+                    "$J",
+                    &runs,
+                    5120,
+                    0x8000,
+                    0,
+                ))
+                .insert_attribute(resident_attribute(AttributeType::Data, "$Max", &max));
+        })
+        .open("usn-journal")
+}
+
+/// Opens the sample volume in which `dir-r-4` is a junction to `System Volume Information`.
+pub fn junction_volume() -> Result<SampleVolume, Error> {
+    let target = "C:\\System Volume Information";
+    let substitute_name = utf16_bytes(&format!("\\??\\{}", target));
+    let print_name = utf16_bytes(target);
+
+    let mut mount_point = Vec::new();
+    for field in &[
+        0,
+        substitute_name.len(),
+        substitute_name.len() + 2,
+        print_name.len(),
+    ] {
+        mount_point.extend_from_slice(&(*field as u16).to_le_bytes());
+    }
+    mount_point.extend_from_slice(&substitute_name);
+    mount_point.extend_from_slice(&[0, 0]);
+    mount_point.extend_from_slice(&print_name);
+    mount_point.extend_from_slice(&[0, 0]);
+
+    let mut reparse_point = Vec::new();
+    reparse_point.extend_from_slice(&(ReparsePointTag::MountPoint as u32).to_le_bytes());
+    reparse_point.extend_from_slice(&(mount_point.len() as u16).to_le_bytes());
+    reparse_point.extend_from_slice(&[0, 0]);
+    reparse_point.extend_from_slice(&mount_point);
+
+    // The entry of `$R` has no data, only a key of the tag and the file reference.
+    let mut index_entry = vec![0; 32];
+    index_entry[8..10].copy_from_slice(&32_u16.to_le_bytes());
+    index_entry[10..12].copy_from_slice(&12_u16.to_le_bytes());
+    index_entry[16..20].copy_from_slice(&reparse_point[..4]);
+    index_entry[20..28].copy_from_slice(&(30_u64 | 1 << 48).to_le_bytes());
+
+    SampleImage::new()
+        .edit_mft_entry(30, |entry| {
+            entry
+                .set_file_attribute_flags(0x420)
+                .insert_attribute(resident_attribute(
+                    AttributeType::ReparsePoint,
+                    "",
+                    &reparse_point,
+                ));
+        })
+        .edit_mft_entry(26, |entry| {
+            entry.insert_index_entry("$R", 0, &index_entry);
+        })
+        .open("junction")
+}
+
+/// The sample image in which `file-n-1.dat` is a 64 KiB sparse file, along with its data, which
+/// is zero but for its first and last kilobyte.
+pub fn sparse_image() -> (SampleImage, Vec<u8>) {
+    let data = (0..2048).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+    let runs = [
+        (Some(SAMPLE_FREE_CLUSTER), 2),
+        (None, 124),
+        (Some(SAMPLE_FREE_CLUSTER + 2), 2),
+    ];
+
+    let mut image = SampleImage::new();
+    image
+        .write_clusters(SAMPLE_FREE_CLUSTER, &data)
+        .edit_mft_entry(33, |entry| {
+            entry
+                .set_file_attribute_flags(0x220)
+                .remove_attribute(AttributeType::Data, "")
+                .insert_attribute(non_resident_attribute(
+                    AttributeType::Data,
+                    "",
+                    &runs,
+                    65536,
+                    0x8000,
+                    0,
+                ));
+        });
+
+    let mut expected = vec![0; 65536];
+    expected[..1024].copy_from_slice(&data[..1024]);
+    expected[64512..].copy_from_slice(&data[1024..]);
+
+    (image, expected)
+}
+
+/// Opens the sample volume in which `file-r-1.dat` is encrypted, with a `$EFS` stream that only
+/// holds a header.
+pub fn efs_volume() -> Result<SampleVolume, Error> {
+    let mut efs = vec![0; 64];
+    efs[0..4].copy_from_slice(&64_u32.to_le_bytes());
+    efs[8..12].copy_from_slice(&2_u32.to_le_bytes());
+
+    SampleImage::new()
+        .edit_mft_entry(27, |entry| {
+            entry
+                .set_file_attribute_flags(0x4020)
+                .insert_attribute(resident_attribute(
+                    AttributeType::LoggedUtilityStream,
+                    "$EFS",
+                    &efs,
+                ));
+        })
+        .open("efs")
+}
+
+/// Number of bytes of `file-n-1.dat` that were written in `preallocated_volume`.
+pub const PREALLOCATED_VALID_DATA_SIZE: usize = 1000;
+
+/// Opens the sample volume in which only the first `PREALLOCATED_VALID_DATA_SIZE` bytes of the
+/// 2000 allocated for `file-n-1.dat` were written, so the rest reads as zeros.
+pub fn preallocated_volume() -> Result<SampleVolume, Error> {
+    SampleImage::new()
+        .edit_mft_entry(33, |entry| {
+            entry.set_valid_data_size(AttributeType::Data, "", PREALLOCATED_VALID_DATA_SIZE as u64);
+        })
+        .open("preallocated")
+}
+
+/// Opens the sample volume in which `dir-r-4` held `deleted.txt`, whose key was left in the slack
+/// of the index root when it was deleted.
+pub fn deleted_files_volume() -> Result<SampleVolume, Error> {
+    let key = file_name_value(30 | 1 << 48, "deleted.txt", 0x20);
+    let index_entry = file_name_index_entry(34 | 2 << 48, &key);
+
+    SampleImage::new()
+        .edit_mft_entry(30, |entry| {
+            entry.insert_index_entry("$I30", 0, &index_entry);
+
+            // Deleting the first entry moves the end marker over its header, and shrinks the
+            // used size of the node to the node header and the end marker.
+            let mut index_root = entry.resident_value(AttributeType::IndexRoot, "$I30");
+            let end_marker = index_root[32 + index_entry.len()..].to_vec();
+            index_root[32..48].copy_from_slice(&end_marker);
+            index_root[20..24].copy_from_slice(&32_u32.to_le_bytes());
+
+            entry.set_resident_value(AttributeType::IndexRoot, "$I30", &index_root);
+        })
+        .open("deleted-files")
+}
+
+/// Opens the sample volume in which quota tracking is enabled, with an owner `0x101` that has
+/// used 2000 bytes of a limit of 2 MiB, with a threshold of 1 MiB.
+pub fn quota_volume() -> Result<SampleVolume, Error> {
+    // S-1-5-21-1-2-3-1000
+    let mut sid = vec![1, 5, 0, 0, 0, 0, 0, 5];
+    for sub_authority in &[21_u32, 1, 2, 3, 1000] {
+        sid.extend_from_slice(&sub_authority.to_le_bytes());
+    }
+
+    let mut quota = vec![0; 48];
+    quota[0..4].copy_from_slice(&2_u32.to_le_bytes());
+    quota[8..16].copy_from_slice(&2000_u64.to_le_bytes());
+    quota[16..24].copy_from_slice(&132_223_104_000_000_000_u64.to_le_bytes());
+    quota[24..32].copy_from_slice(&(1_u64 << 20).to_le_bytes());
+    quota[32..40].copy_from_slice(&(2_u64 << 20).to_le_bytes());
+    quota.extend_from_slice(&sid);
+
+    let mut index_entry = vec![0; 20];
+    index_entry[0..2].copy_from_slice(&20_u16.to_le_bytes());
+    index_entry[2..4].copy_from_slice(&(quota.len() as u16).to_le_bytes());
+    index_entry[8..10].copy_from_slice(&96_u16.to_le_bytes());
+    index_entry[10..12].copy_from_slice(&4_u16.to_le_bytes());
+    index_entry[16..20].copy_from_slice(&0x101_u32.to_le_bytes());
+    index_entry.extend_from_slice(&quota);
+    index_entry.resize(96, 0);
+
+    SampleImage::new()
+        .edit_mft_entry(24, |entry| {
+            // The first entry holds the defaults, with the quota flags of the volume.
+            let mut index_root = entry.resident_value(AttributeType::IndexRoot, "$Q");
+            index_root[32 + 20 + 4] |= 0x10;
+
+            entry
+                .set_resident_value(AttributeType::IndexRoot, "$Q", &index_root)
+                .insert_index_entry("$Q", 2, &index_entry);
+        })
+        .open("quota")
+}
+
+/// Compresses `data` with LZNT1 in 4 KiB chunks, greedily matching against the last occurrence of
+/// each three byte sequence. Chunks that do not compress are stored as they are.
+fn lznt1_compress(data: &[u8]) -> Vec<u8> {
+    let mut compressed = Vec::new();
+
+    for chunk in data.chunks(4096) {
+        let mut body = Vec::new();
+        let mut last_positions: HashMap<&[u8], usize> = HashMap::new();
+        let mut position = 0;
+        let mut tokens = 0;
+        let mut flags_offset = 0;
+
+        while position < chunk.len() {
+            if tokens % 8 == 0 {
+                flags_offset = body.len();
+                body.push(0);
+            }
+
+            // Back references share 16 bits between offset and length, with more offset bits
+            // further into the chunk.
+            let mut offset_bits = 4;
+            while position > 1 << offset_bits {
+                offset_bits += 1;
+            }
+            let max_length = (chunk.len() - position).min((1 << (16 - offset_bits)) + 2);
+
+            let candidate = chunk
+                .get(position..position + 3)
+                .and_then(|key| last_positions.get(key).copied());
+            let length = candidate.map_or(0, |candidate| {
+                (0..max_length)
+                    .take_while(|&i| chunk[candidate + i] == chunk[position + i])
+                    .count()
+            });
+
+            let length = if length >= 3 {
+                let offset = position - candidate.unwrap();
+                let token = ((offset - 1) << (16 - offset_bits) | (length - 3)) as u16;
+
+                body[flags_offset] |= 1 << (tokens % 8);
+                body.extend_from_slice(&token.to_le_bytes());
+                length
+            } else {
+                body.push(chunk[position]);
+                1
+            };
+
+            for start in position..position + length {
+                if let Some(key) = chunk.get(start..start + 3) {
+                    last_positions.insert(key, start);
+                }
+            }
+
+            position += length;
+            tokens += 1;
+        }
+
+        if body.len() < chunk.len() {
+            let header = 0xb000 | (body.len() + 2 - 3) as u16;
+            compressed.extend_from_slice(&header.to_le_bytes());
+            compressed.extend_from_slice(&body);
+        } else {
+            let header = 0x3000 | (chunk.len() + 2 - 3) as u16;
+            compressed.extend_from_slice(&header.to_le_bytes());
+            compressed.extend_from_slice(chunk);
+        }
+    }
+
+    compressed
+}
+
+/// Opens the sample volume in which `file-n-1.dat` is an LZNT1 compressed text file spanning ten
+/// compression units of 8 KiB, one of which holds noise and is stored uncompressed.
+pub fn compressed_volume() -> Result<SampleVolume, Error> {
+    const UNIT_CLUSTERS: usize = 16;
+    let unit_size = UNIT_CLUSTERS * SAMPLE_CLUSTER_SIZE;
+
+    let mut data = (0..)
+        .flat_map(|line| {
+            format!("{:05} the quick brown fox jumps over the lazy dog\n", line).into_bytes()
+        })
+        .take(9 * unit_size + 1000)
+        .collect::<Vec<u8>>();
+
+    let mut state = 1_u32;
+    for byte in &mut data[3 * unit_size..4 * unit_size] {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        *byte = (state >> 16) as u8;
+    }
+
+    let mut clusters = Vec::new();
+    let mut runs = Vec::new();
+
+    for unit in data.chunks(unit_size) {
+        let start = clusters.len();
+        let first_cluster = SAMPLE_FREE_CLUSTER + (start / SAMPLE_CLUSTER_SIZE) as u64;
+        let compressed = lznt1_compress(unit);
+        let used_clusters = (compressed.len() + SAMPLE_CLUSTER_SIZE - 1) / SAMPLE_CLUSTER_SIZE;
+
+        // A unit is only stored compressed if that saves at least a cluster, the rest of it
+        // then being sparse.
+        if used_clusters < UNIT_CLUSTERS {
+            clusters.extend_from_slice(&compressed);
+            clusters.resize(start + used_clusters * SAMPLE_CLUSTER_SIZE, 0);
+            runs.push((Some(first_cluster), used_clusters as u64));
+            runs.push((None, (UNIT_CLUSTERS - used_clusters) as u64));
+        } else {
+            clusters.extend_from_slice(unit);
+            clusters.resize(start + unit_size, 0);
+            runs.push((Some(first_cluster), UNIT_CLUSTERS as u64));
+        }
+    }
+
+    SampleImage::new()
+        .write_clusters(SAMPLE_FREE_CLUSTER, &clusters)
+        .edit_mft_entry(33, |entry| {
+            entry
+                .set_file_attribute_flags(0x820)
+                .remove_attribute(AttributeType::Data, "")
+                .insert_attribute(non_resident_attribute(
+                    AttributeType::Data,
+                    "",
+                    &runs,
+                    data.len() as u64,
+                    0x0001,
+                    4,
+                ));
+        })
+        .open("compressed")
+}
+
+/// Opens the sample volume in which `file-r-1.dat` is hard linked as `dir-r-4\link.dat`.
+pub fn hardlink_volume() -> Result<SampleVolume, Error> {
+    let key = file_name_value(30 | 1 << 48, "link.dat", 0x20);
+    let index_entry = file_name_index_entry(27 | 1 << 48, &key);
+
+    SampleImage::new()
+        .edit_mft_entry(27, |entry| {
+            entry.set_link_count(2).insert_attribute(resident_attribute(
+                AttributeType::FileName,
+                "",
+                &key,
+            ));
+        })
+        .edit_mft_entry(30, |entry| {
+            entry.insert_index_entry("$I30", 0, &index_entry);
+        })
+        .open("hardlink")
+}
+
+/// The contents of the `Zone.Identifier` stream of [`zone_identifier_volume`].
+pub const ZONE_IDENTIFIER: &str = "[ZoneTransfer]\r\nZoneId=3\r\n";
+
+/// Opens the sample volume in which `file-r-1.dat` is marked as downloaded, with a
+/// `Zone.Identifier` alternate data stream.
+pub fn zone_identifier_volume() -> Result<SampleVolume, Error> {
+    SampleImage::new()
+        .edit_mft_entry(27, |entry| {
+            entry.insert_attribute(resident_attribute(
+                AttributeType::Data,
+                "Zone.Identifier",
+                ZONE_IDENTIFIER.as_bytes(),
+            ));
+        })
+        .open("zone-identifier")
+}
+
+#[cfg(feature = "test-fixtures")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_sample() {
+        let volume = Volume::open(&*EMBEDDED_SAMPLE_PATH, AccessMode::Read).unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(27).unwrap();
+
+        assert_eq!(entry.get_name().unwrap(), "file-r-1.dat");
+    }
+}
@@ -0,0 +1,251 @@
+//! Hashing of the default data stream of file entries.
+use crate::error::Error;
+use crate::file_entry::FileEntry;
+use crate::volume::{FileReference, MftEntryIndex, Volume};
+use log::warn;
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+
+/// A digest algorithm, see `Volume::hash_all_files`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgo {
+    Sha256,
+    #[cfg(feature = "legacy_hashes")]
+    Md5,
+    #[cfg(feature = "legacy_hashes")]
+    Sha1,
+}
+
+impl HashAlgo {
+    /// Size of a digest in bytes.
+    pub fn digest_size(&self) -> usize {
+        match self {
+            HashAlgo::Sha256 => 32,
+            #[cfg(feature = "legacy_hashes")]
+            HashAlgo::Md5 => 16,
+            #[cfg(feature = "legacy_hashes")]
+            HashAlgo::Sha1 => 20,
+        }
+    }
+}
+
+impl<'a> FileEntry<'a> {
+    /// Computes the SHA-256 digest of the default data stream.
+    pub fn hash_sha256(&self) -> Result<[u8; 32], Error> {
+        let mut hasher = Sha256::new();
+        self.stream_default_data(|chunk| {
+            hasher.update(chunk);
+            Ok(())
+        })?;
+
+        let mut digest = [0; 32];
+        digest.copy_from_slice(&hasher.finalize());
+
+        Ok(digest)
+    }
+
+    /// Computes the MD5 digest of the default data stream.
+    #[cfg(feature = "legacy_hashes")]
+    pub fn hash_md5(&self) -> Result<[u8; 16], Error> {
+        let mut hasher = md5::Md5::new();
+        self.stream_default_data(|chunk| {
+            hasher.update(chunk);
+            Ok(())
+        })?;
+
+        let mut digest = [0; 16];
+        digest.copy_from_slice(&hasher.finalize());
+
+        Ok(digest)
+    }
+
+    /// Computes the SHA-1 digest of the default data stream.
+    #[cfg(feature = "legacy_hashes")]
+    pub fn hash_sha1(&self) -> Result<[u8; 20], Error> {
+        let mut hasher = sha1::Sha1::new();
+        self.stream_default_data(|chunk| {
+            hasher.update(chunk);
+            Ok(())
+        })?;
+
+        let mut digest = [0; 20];
+        digest.copy_from_slice(&hasher.finalize());
+
+        Ok(digest)
+    }
+
+    /// Computes the digest of the default data stream with `algo`, padded with zeros to 32 bytes.
+    pub fn hash(&self, algo: HashAlgo) -> Result<[u8; 32], Error> {
+        match algo {
+            HashAlgo::Sha256 => self.hash_sha256(),
+            #[cfg(feature = "legacy_hashes")]
+            HashAlgo::Md5 => self.hash_md5().map(|digest| padded(&digest)),
+            #[cfg(feature = "legacy_hashes")]
+            HashAlgo::Sha1 => self.hash_sha1().map(|digest| padded(&digest)),
+        }
+    }
+}
+
+#[cfg(feature = "legacy_hashes")]
+fn padded(digest: &[u8]) -> [u8; 32] {
+    let mut padded = [0; 32];
+    padded[..digest.len()].copy_from_slice(digest);
+
+    padded
+}
+
+/// Hashes the entry at `idx` if it is an allocated file with a default data stream.
+fn hash_file(
+    volume: &Volume,
+    idx: MftEntryIndex,
+    algo: HashAlgo,
+) -> Result<Option<(FileReference, [u8; 32])>, Error> {
+    let entry = volume.get_file_entry_by_mft_idx(idx)?;
+
+    if !entry.is_allocated()? || !entry.has_default_data_stream()? {
+        return Ok(None);
+    }
+
+    Ok(Some((
+        FileReference::from(entry.get_file_reference()?),
+        entry.hash(algo)?,
+    )))
+}
+
+impl Volume {
+    /// Hashes the default data stream of every allocated file, on one thread per available CPU,
+    /// e.g. to build a hash set or compare the files against one.
+    ///
+    /// Each thread reads through its own handle to the image, so this is only available for
+    /// volumes with raw reads, see `read_at`. Digests shorter than 32 bytes are padded with
+    /// zeros. Files that cannot be read are logged and left out, instead of failing the whole
+    /// run. The digests are ordered by MFT entry.
+    pub fn hash_all_files(&self, algo: HashAlgo) -> Result<Vec<(FileReference, [u8; 32])>, Error> {
+        let number_of_file_entries = self.get_number_of_file_entries()? as MftEntryIndex;
+        let number_of_workers = thread::available_parallelism().map_or(1, |n| n.get());
+
+        let sources = (0..number_of_workers)
+            .map(|_| self.image_source())
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        // Workers take the next entry as soon as they are done with one, so that a few large
+        // files do not hold up the entries queued behind them.
+        let next_idx = AtomicU64::new(0);
+
+        let results = thread::scope(|scope| {
+            let workers: Vec<_> = sources
+                .into_iter()
+                .map(|source| {
+                    let next_idx = &next_idx;
+
+                    scope.spawn(move || -> Result<Vec<_>, Error> {
+                        let volume = source.open()?;
+                        let mut digests = Vec::new();
+
+                        loop {
+                            let idx = next_idx.fetch_add(1, Ordering::Relaxed);
+
+                            if idx >= number_of_file_entries {
+                                return Ok(digests);
+                            }
+
+                            match hash_file(&volume, idx, algo) {
+                                Ok(Some(digest)) => digests.push(digest),
+                                Ok(None) => {}
+                                Err(e) => warn!("Failed to hash MFT entry {}: {}", idx, e),
+                            }
+                        }
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .map(|worker| {
+                    worker
+                        .join()
+                        .unwrap_or_else(|_| Err(Error::Other("Hashing thread panicked".to_owned())))
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let mut digests = Vec::new();
+
+        for result in results {
+            digests.extend(result?);
+        }
+
+        digests.sort_by_key(|(reference, _)| reference.mft_entry_index());
+
+        Ok(digests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+    use crate::name_match::MatchMode;
+
+    fn hex(digest: &[u8]) -> String {
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_hash_sha256() {
+        let volume = sample_volume().unwrap();
+        let upcase = volume
+            .get_file_entry_by_path("\\$UpCase", MatchMode::Exact)
+            .unwrap();
+
+        assert_eq!(
+            hex(&upcase.hash_sha256().unwrap()),
+            "19442bdd7623101de9e217a943b103283406ff96d332f6e7ca4e1fe06e111a53"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "legacy_hashes")]
+    fn test_hash_md5_and_sha1() {
+        let volume = sample_volume().unwrap();
+        let upcase = volume
+            .get_file_entry_by_path("\\$UpCase", MatchMode::Exact)
+            .unwrap();
+
+        assert_eq!(
+            hex(&upcase.hash_md5().unwrap()),
+            "6fa3db2468275286210751e869d36373"
+        );
+        assert_eq!(
+            hex(&upcase.hash_sha1().unwrap()),
+            "f70af7226bf6b9ada686a70afb899eb879dc24aa"
+        );
+    }
+
+    #[test]
+    fn test_hash_all_files() {
+        let volume = sample_volume().unwrap();
+        let digests = volume.hash_all_files(HashAlgo::Sha256).unwrap();
+
+        let files: Vec<FileReference> = volume
+            .iter_entries()
+            .unwrap()
+            .map(|entry| entry.unwrap())
+            .filter(|entry| {
+                entry.is_allocated().unwrap() && entry.has_default_data_stream().unwrap()
+            })
+            .map(|entry| FileReference::from(entry.get_file_reference().unwrap()))
+            .collect();
+
+        let hashed: Vec<FileReference> = digests.iter().map(|(reference, _)| *reference).collect();
+        assert_eq!(hashed, files);
+
+        let upcase = volume
+            .get_file_entry_by_path("\\$UpCase", MatchMode::Exact)
+            .unwrap();
+        let upcase_reference = FileReference::from(upcase.get_file_reference().unwrap());
+
+        assert!(digests.contains(&(upcase_reference, upcase.hash_sha256().unwrap())));
+    }
+}
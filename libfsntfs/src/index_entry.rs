@@ -0,0 +1,427 @@
+//! Entries of directory indexes, including those left behind in slack space by deleted files.
+use crate::attribute::{AttributeType, NameNamespace};
+use crate::error::Error;
+use crate::file_attributes::FileAttributeFlags;
+use crate::file_entry::FileEntry;
+use crate::filetime::filetime_to_system_time;
+use crate::mft::{apply_fixups, read_u16, read_u32, read_u64, MftRecord};
+use crate::volume::{mft_entry_index, FileReference, MftEntryIndex, Volume};
+use std::convert::TryFrom;
+use std::ops::Range;
+use std::time::SystemTime;
+
+const DIRECTORY_INDEX_NAME: &str = "$I30";
+
+/// Offset of the index node header in the `$INDEX_ROOT` value.
+const INDEX_ROOT_NODE_OFFSET: usize = 16;
+/// Offset of the index node header in an `INDX` record.
+const INDEX_RECORD_NODE_OFFSET: usize = 24;
+
+const ENTRY_HEADER_SIZE: usize = 16;
+const ENTRY_IS_LAST: u32 = 0x0000_0002;
+
+/// Size of a `$FILE_NAME` key without its name.
+const FILE_NAME_KEY_SIZE: usize = 66;
+
+/// An entry of a directory index, see `FileEntry::iter_index_entries`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexEntry {
+    /// Reference of the MFT entry of the file. For entries recovered from slack space, the
+    /// reference may have since been overwritten, e.g. by the end marker of the index node.
+    pub file_reference: FileReference,
+    /// Reference of the directory, as stored in the `$FILE_NAME` key.
+    pub parent_file_reference: FileReference,
+    pub name: String,
+    pub name_namespace: NameNamespace,
+    /// File attribute flags as of the last update of the key, which happens less often than
+    /// updates of `$STANDARD_INFORMATION`.
+    pub file_attribute_flags: FileAttributeFlags,
+    pub creation_time: Option<SystemTime>,
+    pub modification_time: Option<SystemTime>,
+    pub entry_modification_time: Option<SystemTime>,
+    pub access_time: Option<SystemTime>,
+    /// `false` if the entry was recovered from slack space, i.e. it may describe a deleted file.
+    pub is_allocated: bool,
+}
+
+/// An index node, with the bounds of its entries relative to the start of `data`.
+pub(crate) struct IndexNode {
+    data: Vec<u8>,
+    entries_offset: usize,
+    entries_end: usize,
+    allocated_end: usize,
+    /// `false` for index records that are not in use according to the `$BITMAP` of the index,
+    /// all of whose entries are stale.
+    is_in_use: bool,
+}
+
+impl IndexNode {
+    pub(crate) fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn parse(data: Vec<u8>, node_offset: usize, is_in_use: bool) -> Result<Self, Error> {
+        let out_of_bounds = || Error::Other("Index node header is out of bounds".to_owned());
+
+        let entries_offset = read_u32(&data, node_offset).ok_or_else(out_of_bounds)? as usize;
+        let entries_size = read_u32(&data, node_offset + 4).ok_or_else(out_of_bounds)? as usize;
+        let allocated_size = read_u32(&data, node_offset + 8).ok_or_else(out_of_bounds)? as usize;
+
+        let allocated_end = (node_offset + allocated_size).min(data.len());
+
+        Ok(IndexNode {
+            entries_offset: node_offset + entries_offset,
+            entries_end: (node_offset + entries_size).min(allocated_end),
+            allocated_end,
+            data,
+            is_in_use,
+        })
+    }
+
+    /// Locates the entries of the node before its end marker, returning their bounds and the
+    /// offset of the end marker. Nodes that are not in use have no entries.
+    ///
+    /// Stops at the first entry that cannot be bounded, in which case no end marker is returned.
+    pub(crate) fn allocated_entries(&self) -> (Vec<Result<Range<usize>, Error>>, Option<usize>) {
+        let mut entries = Vec::new();
+        let mut offset = self.entries_offset;
+
+        if !self.is_in_use {
+            return (entries, Some(offset));
+        }
+
+        loop {
+            let (length, flags) = match (
+                read_u16(&self.data, offset + 8),
+                read_u32(&self.data, offset + 12),
+            ) {
+                (Some(length), Some(flags)) if offset < self.entries_end => {
+                    (length as usize, flags)
+                }
+                _ => {
+                    entries.push(Err(Error::Other(
+                        "Index entries are missing their end marker".to_owned(),
+                    )));
+                    return (entries, None);
+                }
+            };
+
+            if flags & ENTRY_IS_LAST != 0 {
+                return (entries, Some(offset));
+            }
+
+            if length < ENTRY_HEADER_SIZE {
+                entries.push(Err(Error::Other(format!(
+                    "Index entry at offset {} has an invalid length",
+                    offset
+                ))));
+                return (entries, None);
+            }
+
+            entries.push(Ok(offset..(offset + length).min(self.entries_end)));
+            offset += length;
+        }
+    }
+
+    /// Parses the entries of the node, followed by the entries that can be recovered from the
+    /// space after its end marker.
+    fn entries(&self, directory: MftEntryIndex) -> Vec<Result<IndexEntry, Error>> {
+        let (allocated, end) = self.allocated_entries();
+
+        let mut entries: Vec<Result<IndexEntry, Error>> = allocated
+            .into_iter()
+            .map(|bounds| {
+                let offset = bounds?.start;
+
+                parse_entry(&self.data[..self.entries_end], offset, true).ok_or_else(|| {
+                    Error::Other(format!("Index entry at offset {} is malformed", offset))
+                })
+            })
+            .collect();
+
+        let end = match end {
+            Some(end) => end,
+            None => return entries,
+        };
+
+        // Deleted entries are not wiped, the remaining entries are only shifted over them. Any
+        // key referencing this directory after the end marker is a remnant.
+        let mut offset = (end + 7) & !7;
+
+        while offset + ENTRY_HEADER_SIZE + FILE_NAME_KEY_SIZE <= self.allocated_end {
+            let slack = &self.data[..self.allocated_end];
+
+            match parse_entry(slack, offset, false) {
+                Some(entry) if entry.parent_file_reference.mft_entry_index() == directory => {
+                    let key_size = FILE_NAME_KEY_SIZE + 2 * entry.name.encode_utf16().count();
+                    offset += (ENTRY_HEADER_SIZE + key_size + 7) & !7;
+
+                    entries.push(Ok(entry));
+                }
+                _ => offset += 8,
+            }
+        }
+
+        entries
+    }
+}
+
+/// Parses the index entry at `offset`, with a `$FILE_NAME` key.
+fn parse_entry(data: &[u8], offset: usize, is_allocated: bool) -> Option<IndexEntry> {
+    let key = data.get(offset + ENTRY_HEADER_SIZE..)?;
+
+    let name_length = *key.get(64)? as usize;
+    let name_namespace = NameNamespace::try_from(*key.get(65)?).ok()?;
+
+    if name_length == 0 {
+        return None;
+    }
+
+    let name_data = key.get(FILE_NAME_KEY_SIZE..FILE_NAME_KEY_SIZE + 2 * name_length)?;
+    let name = String::from_utf16(
+        &name_data
+            .chunks_exact(2)
+            .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+            .collect::<Vec<_>>(),
+    )
+    .ok()?;
+
+    let time = |offset| read_u64(key, offset).and_then(filetime_to_system_time);
+
+    Some(IndexEntry {
+        file_reference: FileReference(read_u64(data, offset)?),
+        parent_file_reference: FileReference(read_u64(key, 0)?),
+        name,
+        name_namespace,
+        file_attribute_flags: FileAttributeFlags::from_bits_truncate(read_u32(key, 56)?),
+        creation_time: time(8),
+        modification_time: time(16),
+        entry_modification_time: time(24),
+        access_time: time(32),
+        is_allocated,
+    })
+}
+
+impl IndexEntry {
+    /// Whether the entry was recovered from slack space rather than the used portion of its
+    /// index node, i.e. from past the end marker of the node, or from a node of
+    /// `$INDEX_ALLOCATION` that the `$BITMAP` of the index marks as unused. Such entries may
+    /// describe recently deleted or renamed files.
+    pub fn is_slack(&self) -> bool {
+        !self.is_allocated
+    }
+}
+
+/// Iterator over the entries of a directory index, see `FileEntry::iter_index_entries`.
+pub struct IterIndexEntries {
+    directory: MftEntryIndex,
+    nodes: std::vec::IntoIter<Result<IndexNode, Error>>,
+    entries: std::vec::IntoIter<Result<IndexEntry, Error>>,
+}
+
+impl Iterator for IterIndexEntries {
+    type Item = Result<IndexEntry, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.entries.next() {
+                return Some(entry);
+            }
+
+            match self.nodes.next()? {
+                Ok(node) => self.entries = node.entries(self.directory).into_iter(),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+impl<'a> FileEntry<'a> {
+    /// Iterates over the entries of the directory index, which libfsntfs does not expose.
+    ///
+    /// Besides the entries of existing files, this yields entries recovered from the slack space
+    /// of the index nodes (with `is_allocated` unset). These can reveal names of deleted files
+    /// whose MFT entries have since been reused.
+    ///
+    /// Only indexes whose attributes are stored in the base MFT entry are supported.
+    pub fn iter_index_entries(&self) -> Result<IterIndexEntries, Error> {
+        let volume = self.get_volume();
+        let directory = mft_entry_index(self.get_file_reference()?);
+        let record = MftRecord::read(volume, directory)?;
+
+        Ok(IterIndexEntries {
+            directory,
+            nodes: read_index_nodes(volume, &record, DIRECTORY_INDEX_NAME)?.into_iter(),
+            entries: Vec::new().into_iter(),
+        })
+    }
+}
+
+/// Reads the nodes of the index named `index_name` from its `$INDEX_ROOT` and
+/// `$INDEX_ALLOCATION` attributes in `record`.
+pub(crate) fn read_index_nodes(
+    volume: &Volume,
+    record: &MftRecord,
+    index_name: &str,
+) -> Result<Vec<Result<IndexNode, Error>>, Error> {
+    let index_root = record
+        .find_named_attribute(AttributeType::IndexRoot, index_name)?
+        .ok_or_else(|| Error::not_found("$INDEX_ROOT"))?
+        .read(volume)?;
+
+    let index_record_size = read_u32(&index_root, 8)
+        .ok_or_else(|| Error::Other("$INDEX_ROOT is truncated".to_owned()))?
+        as usize;
+
+    let mut nodes = vec![IndexNode::parse(index_root, INDEX_ROOT_NODE_OFFSET, true)];
+
+    if let Some(index_allocation) =
+        record.find_named_attribute(AttributeType::IndexAllocation, index_name)?
+    {
+        let index_allocation = index_allocation.read(volume)?;
+
+        let bitmap = match record.find_named_attribute(AttributeType::Bitmap, index_name)? {
+            Some(bitmap) => Some(bitmap.read(volume)?),
+            None => None,
+        };
+
+        if index_record_size == 0 {
+            return Err(Error::Other("Invalid index record size of 0".to_owned()));
+        }
+
+        for (i, chunk) in index_allocation.chunks_exact(index_record_size).enumerate() {
+            let is_in_use = bitmap.as_ref().map_or(true, |bitmap| {
+                bitmap
+                    .get(i / 8)
+                    .map_or(false, |byte| byte & (1 << (i % 8)) != 0)
+            });
+
+            nodes.push(parse_index_record(chunk.to_vec(), is_in_use));
+        }
+    }
+
+    Ok(nodes)
+}
+
+fn parse_index_record(mut data: Vec<u8>, is_in_use: bool) -> Result<IndexNode, Error> {
+    if data.get(0..4) != Some(b"INDX") {
+        return Err(Error::Other(
+            "Index record is missing its INDX signature".to_owned(),
+        ));
+    }
+
+    apply_fixups(&mut data)
+        .map_err(|reason| Error::Other(format!("Index record is malformed: {}", reason)))?;
+
+    IndexNode::parse(data, INDEX_RECORD_NODE_OFFSET, is_in_use)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+    use crate::volume::ROOT_DIRECTORY_MFT_ENTRY_INDEX;
+
+    #[test]
+    fn test_iter_index_entries_of_root() {
+        let volume = sample_volume().unwrap();
+        let root = volume.get_root_directory().unwrap();
+
+        let entries: Vec<IndexEntry> = root
+            .iter_index_entries()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let entry = entries
+            .iter()
+            .find(|entry| entry.name == "file-r-1.dat")
+            .unwrap();
+
+        assert_eq!(entry.file_reference.mft_entry_index(), 27);
+        assert_eq!(
+            entry.parent_file_reference.mft_entry_index(),
+            ROOT_DIRECTORY_MFT_ENTRY_INDEX
+        );
+        assert!(entry.is_allocated);
+        assert!(entry.creation_time.is_some());
+
+        assert!(entries
+            .iter()
+            .any(|entry| entry.name == "SYSTEM~1" && entry.name_namespace == NameNamespace::Dos));
+        assert!(entries.iter().all(|entry| entry.is_allocated));
+        assert!(entries.iter().all(|entry| !entry.is_slack()));
+    }
+
+    #[test]
+    fn test_entries_in_slack() {
+        let parent_file_reference = FileReference(5 << 48 | ROOT_DIRECTORY_MFT_ENTRY_INDEX);
+
+        // An index root holding "test.log", followed by the end marker. Deleting "test.log"
+        // moves the end marker over the header of its entry, leaving the key in slack space.
+        let name: Vec<u8> = "test.log"
+            .encode_utf16()
+            .flat_map(|unit| unit.to_le_bytes().to_vec())
+            .collect();
+        let key_size = FILE_NAME_KEY_SIZE + name.len();
+        let entry_size = (ENTRY_HEADER_SIZE + key_size + 7) & !7;
+
+        let mut data = vec![0; INDEX_ROOT_NODE_OFFSET + 16 + entry_size + ENTRY_HEADER_SIZE];
+        let node = INDEX_ROOT_NODE_OFFSET;
+        data[node..node + 4].copy_from_slice(&16_u32.to_le_bytes());
+        data[node + 4..node + 8].copy_from_slice(&(16 + ENTRY_HEADER_SIZE as u32).to_le_bytes());
+        data[node + 8..node + 12]
+            .copy_from_slice(&((16 + entry_size + ENTRY_HEADER_SIZE) as u32).to_le_bytes());
+
+        let entry = node + 16;
+        let key = entry + ENTRY_HEADER_SIZE;
+        data[entry + 8..entry + 10].copy_from_slice(&(ENTRY_HEADER_SIZE as u16).to_le_bytes());
+        data[entry + 12..entry + 16].copy_from_slice(&ENTRY_IS_LAST.to_le_bytes());
+        data[key..key + 8].copy_from_slice(&parent_file_reference.0.to_le_bytes());
+        data[key + 8..key + 16].copy_from_slice(&132_223_104_000_000_000_u64.to_le_bytes());
+        data[key + 64] = 8;
+        data[key + 65] = NameNamespace::WindowsAndDos as u8;
+        data[key + FILE_NAME_KEY_SIZE..key + key_size].copy_from_slice(&name);
+
+        let node = IndexNode::parse(data, INDEX_ROOT_NODE_OFFSET, true).unwrap();
+        let entries: Vec<IndexEntry> = node
+            .entries(ROOT_DIRECTORY_MFT_ENTRY_INDEX)
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "test.log");
+        assert_eq!(entries[0].parent_file_reference, parent_file_reference);
+        assert!(entries[0].creation_time.is_some());
+        assert!(!entries[0].is_allocated);
+        assert!(entries[0].is_slack());
+    }
+
+    #[test]
+    fn test_iter_index_entries_recovers_deleted_names() {
+        let volume = deleted_files_volume().unwrap();
+        let directory = volume
+            .get_file_entry_by_path("\\dir-r-4", crate::name_match::MatchMode::Exact)
+            .unwrap();
+
+        let recovered: Vec<IndexEntry> = directory
+            .iter_index_entries()
+            .unwrap()
+            .filter_map(Result::ok)
+            .filter(IndexEntry::is_slack)
+            .collect();
+
+        assert_eq!(
+            recovered
+                .iter()
+                .map(|entry| entry.name.as_str())
+                .collect::<Vec<_>>(),
+            ["deleted.txt"]
+        );
+        assert!(recovered.iter().all(|entry| directory
+            .find_sub_entry(&entry.name, crate::name_match::MatchMode::Exact)
+            .unwrap()
+            .is_none()));
+    }
+}
@@ -1,16 +1,59 @@
 #![allow(unused_imports)]
 #![allow(unused_variables)]
 #![allow(dead_code)]
+#![cfg_attr(not(test), deny(clippy::unwrap_used))]
 
+extern crate alloc;
 #[macro_use]
 extern crate libyal_rs_common;
 
+#[cfg(feature = "tokio")]
+pub mod async_file_entry;
 pub mod attribute;
+pub mod bitmap;
+pub mod boot_sector;
+#[cfg(feature = "lru")]
+pub mod cached_volume;
+pub mod data_run;
+pub mod data_stream;
+pub mod diff;
+pub mod dirty;
 pub mod error;
+pub mod error_core;
+mod extended_attributes;
+pub mod extent;
+pub mod extract;
 pub mod ffi_error;
+pub mod file_attributes;
 pub mod file_entry;
+pub mod filetime;
+pub mod hash;
+pub mod index_entry;
+pub mod metadata;
+mod mft;
+#[cfg(feature = "mmap")]
+mod mmap;
+pub mod name_match;
+pub mod notify;
+pub mod options;
+pub mod path_index;
+pub mod pool;
+pub mod quota;
+pub mod read_dir;
+pub mod reparse;
+pub mod scanner;
+pub mod security;
+pub mod sparse;
+pub mod statistics;
+pub mod summary;
+#[cfg(feature = "tar")]
+mod tar_export;
+pub mod timestomp;
+pub mod usn_journal;
 mod utils;
+pub mod verify;
 pub mod volume;
+pub mod walk;
 
 #[cfg(test)]
 mod fixtures;
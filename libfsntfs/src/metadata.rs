@@ -0,0 +1,212 @@
+use crate::attribute::AttributeType;
+use crate::error::Error;
+use crate::file_attributes::FileAttributeFlags;
+use crate::file_entry::FileEntry;
+use crate::filetime::filetime_to_system_time;
+use crate::volume::FileReference;
+use std::fs;
+use std::io;
+use std::time::SystemTime;
+
+/// Common metadata of file system entries, modeled after `std::fs::Metadata`, so that NTFS
+/// entries and files on the host can be handled by the same code.
+pub trait EntryMetadata {
+    type Error;
+
+    fn len(&self) -> Result<u64, Self::Error>;
+    fn is_dir(&self) -> Result<bool, Self::Error>;
+    fn is_file(&self) -> Result<bool, Self::Error>;
+    fn is_symlink(&self) -> Result<bool, Self::Error>;
+    fn created(&self) -> Result<SystemTime, Self::Error>;
+    fn modified(&self) -> Result<SystemTime, Self::Error>;
+    fn accessed(&self) -> Result<SystemTime, Self::Error>;
+}
+
+fn to_system_time(filetime: u64, name: &str) -> Result<SystemTime, Error> {
+    require_time(filetime_to_system_time(filetime), name)
+}
+
+fn require_time(time: Option<SystemTime>, name: &str) -> Result<SystemTime, Error> {
+    time.ok_or_else(|| Error::Other(format!("{} time is not set", name)))
+}
+
+impl<'a> EntryMetadata for FileEntry<'a> {
+    type Error = Error;
+
+    fn len(&self) -> Result<u64, Error> {
+        self.get_size()
+    }
+
+    fn is_dir(&self) -> Result<bool, Error> {
+        Ok(!self.is_symlink()? && self.has_directory_entries_index()?)
+    }
+
+    fn is_file(&self) -> Result<bool, Error> {
+        Ok(!self.is_symlink()? && !self.has_directory_entries_index()?)
+    }
+
+    /// Reparse points (symbolic links, junctions, etc.) are reported as symbolic links.
+    fn is_symlink(&self) -> Result<bool, Error> {
+        Ok(self
+            .get_file_attributes()?
+            .contains(FileAttributeFlags::REPARSE_POINT))
+    }
+
+    fn created(&self) -> Result<SystemTime, Error> {
+        to_system_time(self.get_creation_time_as_integer()?, "Creation")
+    }
+
+    fn modified(&self) -> Result<SystemTime, Error> {
+        to_system_time(self.get_modification_time_as_integer()?, "Modification")
+    }
+
+    fn accessed(&self) -> Result<SystemTime, Error> {
+        to_system_time(self.get_access_time_as_integer()?, "Access")
+    }
+}
+
+/// A snapshot of the metadata of a file entry, which does not keep the entry open.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileEntryMetadata {
+    pub file_reference: FileReference,
+    pub size: u64,
+    pub file_attribute_flags: u32,
+    pub is_dir: bool,
+    pub creation_time: Option<SystemTime>,
+    pub modification_time: Option<SystemTime>,
+    pub access_time: Option<SystemTime>,
+    pub entry_modification_time: Option<SystemTime>,
+    pub attribute_types: Vec<AttributeType>,
+}
+
+impl FileEntryMetadata {
+    pub fn from_entry(entry: &FileEntry) -> Result<Self, Error> {
+        Ok(FileEntryMetadata {
+            file_reference: FileReference(entry.get_file_reference()?),
+            size: entry.get_size()?,
+            file_attribute_flags: entry.get_file_attribute_flags()?,
+            is_dir: entry.is_dir()?,
+            creation_time: filetime_to_system_time(entry.get_creation_time_as_integer()?),
+            modification_time: filetime_to_system_time(entry.get_modification_time_as_integer()?),
+            access_time: filetime_to_system_time(entry.get_access_time_as_integer()?),
+            entry_modification_time: filetime_to_system_time(
+                entry.get_entry_modification_time_as_integer()?,
+            ),
+            attribute_types: entry.get_attribute_types()?,
+        })
+    }
+}
+
+impl EntryMetadata for FileEntryMetadata {
+    type Error = Error;
+
+    fn len(&self) -> Result<u64, Error> {
+        Ok(self.size)
+    }
+
+    fn is_dir(&self) -> Result<bool, Error> {
+        Ok(self.is_dir)
+    }
+
+    fn is_file(&self) -> Result<bool, Error> {
+        Ok(!self.is_dir && !self.is_symlink()?)
+    }
+
+    fn is_symlink(&self) -> Result<bool, Error> {
+        Ok(
+            FileAttributeFlags::from_bits_truncate(self.file_attribute_flags)
+                .contains(FileAttributeFlags::REPARSE_POINT),
+        )
+    }
+
+    fn created(&self) -> Result<SystemTime, Error> {
+        require_time(self.creation_time, "Creation")
+    }
+
+    fn modified(&self) -> Result<SystemTime, Error> {
+        require_time(self.modification_time, "Modification")
+    }
+
+    fn accessed(&self) -> Result<SystemTime, Error> {
+        require_time(self.access_time, "Access")
+    }
+}
+
+impl EntryMetadata for fs::Metadata {
+    type Error = io::Error;
+
+    fn len(&self) -> Result<u64, io::Error> {
+        Ok(fs::Metadata::len(self))
+    }
+
+    fn is_dir(&self) -> Result<bool, io::Error> {
+        Ok(fs::Metadata::is_dir(self))
+    }
+
+    fn is_file(&self) -> Result<bool, io::Error> {
+        Ok(fs::Metadata::is_file(self))
+    }
+
+    fn is_symlink(&self) -> Result<bool, io::Error> {
+        Ok(self.file_type().is_symlink())
+    }
+
+    fn created(&self) -> Result<SystemTime, io::Error> {
+        fs::Metadata::created(self)
+    }
+
+    fn modified(&self) -> Result<SystemTime, io::Error> {
+        fs::Metadata::modified(self)
+    }
+
+    fn accessed(&self) -> Result<SystemTime, io::Error> {
+        fs::Metadata::accessed(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+    use std::time::UNIX_EPOCH;
+
+    fn seconds(time: SystemTime) -> i64 {
+        time.duration_since(UNIX_EPOCH).unwrap().as_secs() as i64
+    }
+
+    #[test]
+    fn test_entry_metadata_agrees_with_getters() {
+        let volume = sample_volume().unwrap();
+        let entry = file_entry(&volume).unwrap();
+
+        assert_eq!(
+            EntryMetadata::len(&entry).unwrap(),
+            entry.get_size().unwrap()
+        );
+        assert!(entry.is_file().unwrap());
+        assert!(!entry.is_dir().unwrap());
+        assert!(!entry.is_symlink().unwrap());
+
+        assert_eq!(
+            seconds(entry.created().unwrap()),
+            entry.get_creation_time().unwrap().unwrap().timestamp()
+        );
+        assert_eq!(
+            seconds(entry.modified().unwrap()),
+            entry.get_modification_time().unwrap().unwrap().timestamp()
+        );
+        assert_eq!(
+            seconds(entry.accessed().unwrap()),
+            entry.get_access_time().unwrap().unwrap().timestamp()
+        );
+    }
+
+    #[test]
+    fn test_root_directory_is_dir() {
+        let volume = sample_volume().unwrap();
+        let root = volume.get_root_directory().unwrap();
+
+        assert!(root.is_dir().unwrap());
+        assert!(!root.is_file().unwrap());
+    }
+}
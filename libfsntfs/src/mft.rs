@@ -0,0 +1,534 @@
+//! Minimal parsing of raw MFT records, for attributes libfsntfs does not expose.
+use crate::attribute::AttributeType;
+use crate::error::Error;
+use crate::volume::{mft_entry_index, MftEntryIndex, Volume};
+use std::cmp::min;
+use std::convert::{TryFrom, TryInto};
+
+const FIXUP_STRIDE: usize = 512;
+const END_OF_ATTRIBUTES: u32 = 0xffff_ffff;
+
+pub(crate) fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset.checked_add(2)?)
+        .and_then(|b| b.try_into().ok())
+        .map(u16::from_le_bytes)
+}
+
+pub(crate) fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset.checked_add(4)?)
+        .and_then(|b| b.try_into().ok())
+        .map(u32::from_le_bytes)
+}
+
+pub(crate) fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    data.get(offset..offset.checked_add(8)?)
+        .and_then(|b| b.try_into().ok())
+        .map(u64::from_le_bytes)
+}
+
+fn malformed(idx: MftEntryIndex, reason: &str) -> Error {
+    Error::Other(format!("MFT entry {} is malformed: {}", idx, reason))
+}
+
+/// The value of an attribute inside an MFT record.
+pub(crate) enum RawAttributeValue<'a> {
+    Resident(&'a [u8]),
+    NonResident {
+        /// Runs of `(first cluster, number of clusters)`, without a first cluster if sparse.
+        runs: Vec<(Option<u64>, u64)>,
+        /// Size of the value in bytes.
+        size: u64,
+        /// Number of bytes at the start of the value that were written, the rest reads as zeros.
+        valid_data_size: u64,
+    },
+}
+
+impl<'a> RawAttributeValue<'a> {
+    /// Size of the value in bytes.
+    pub(crate) fn size(&self) -> u64 {
+        match self {
+            RawAttributeValue::Resident(data) => data.len() as u64,
+            RawAttributeValue::NonResident { size, .. } => *size,
+        }
+    }
+
+    /// Reads the value, from the clusters of the volume if it is non-resident.
+    pub(crate) fn read(&self, volume: &Volume) -> Result<Vec<u8>, Error> {
+        self.read_capped(volume, u64::max_value())
+    }
+
+    /// Reads at most the first `max_size` bytes of the value.
+    ///
+    /// The size and the runs come from disk, so values larger than the volume (possible for
+    /// sparse values, but also for corrupt ones) are rejected before anything is allocated.
+    pub(crate) fn read_capped(&self, volume: &Volume, max_size: u64) -> Result<Vec<u8>, Error> {
+        let (runs, size) = match self {
+            RawAttributeValue::Resident(data) => {
+                let size = usize::try_from(max_size).unwrap_or(usize::max_value());
+                return Ok(data[..min(data.len(), size)].to_vec());
+            }
+            RawAttributeValue::NonResident { runs, size, .. } => (runs, min(*size, max_size)),
+        };
+
+        let volume_size = volume.get_size()?;
+
+        if size > volume_size {
+            return Err(Error::Other(format!(
+                "Non-resident value of {} bytes exceeds the volume size of {} bytes",
+                size, volume_size
+            )));
+        }
+
+        let cluster_size = volume.get_cluster_block_size()? as u64;
+        let mut value = Vec::with_capacity(to_usize(size)?);
+
+        for &(first_cluster, number_of_clusters) in runs {
+            let remaining = size - value.len() as u64;
+
+            if remaining == 0 {
+                break;
+            }
+
+            let run_size = number_of_clusters
+                .checked_mul(cluster_size)
+                .map_or(remaining, |run_size| run_size.min(remaining));
+            let start = value.len();
+            value.resize(start + to_usize(run_size)?, 0);
+
+            if let Some(first_cluster) = first_cluster {
+                let offset = first_cluster
+                    .checked_mul(cluster_size)
+                    .filter(|offset| {
+                        offset
+                            .checked_add(run_size)
+                            .map_or(false, |end| end <= volume_size)
+                    })
+                    .ok_or_else(|| {
+                        Error::Other(format!("Run at cluster {} is out of bounds", first_cluster))
+                    })?;
+
+                if volume.read_at(offset, &mut value[start..])? != value.len() - start {
+                    return Err(Error::Other(format!(
+                        "Run at cluster {} is truncated",
+                        first_cluster
+                    )));
+                }
+            }
+        }
+
+        if (value.len() as u64) < size {
+            return Err(Error::Other(
+                "Runs of a non-resident value are shorter than its size".to_owned(),
+            ));
+        }
+
+        Ok(value)
+    }
+}
+
+fn to_usize(size: u64) -> Result<usize, Error> {
+    usize::try_from(size)
+        .map_err(|_| Error::Other(format!("Value of {} bytes does not fit in memory", size)))
+}
+
+/// Lists the MFT entries other than `base_idx` that hold attributes with the type code
+/// `type_code`, according to the value of the `$ATTRIBUTE_LIST` of entry `base_idx`.
+fn extension_records(
+    attribute_list: &[u8],
+    type_code: u32,
+    base_idx: MftEntryIndex,
+) -> Result<Vec<MftEntryIndex>, Error> {
+    let truncated = || malformed(base_idx, "attribute list is truncated");
+    let mut records = Vec::new();
+    let mut offset = 0;
+
+    while offset < attribute_list.len() {
+        let entry_type = read_u32(attribute_list, offset).ok_or_else(truncated)?;
+        let length = read_u16(attribute_list, offset + 4).ok_or_else(truncated)? as usize;
+        let idx = mft_entry_index(read_u64(attribute_list, offset + 16).ok_or_else(truncated)?);
+
+        if length == 0 {
+            return Err(malformed(base_idx, "attribute list entry is empty"));
+        }
+
+        if entry_type == type_code && idx != base_idx && !records.contains(&idx) {
+            records.push(idx);
+        }
+
+        offset += length;
+    }
+
+    Ok(records)
+}
+
+/// Decodes a run list into runs of `(first cluster, number of clusters)`.
+fn decode_runs(data: &[u8]) -> Option<Vec<(Option<u64>, u64)>> {
+    let mut runs = Vec::new();
+    let mut offset = 0;
+    let mut first_cluster = 0_i64;
+
+    loop {
+        let header = *data.get(offset)?;
+
+        if header == 0 {
+            return Some(runs);
+        }
+
+        let length_size = (header & 0x0f) as usize;
+        let offset_size = (header >> 4) as usize;
+
+        if length_size == 0 || length_size > 8 || offset_size > 8 {
+            return None;
+        }
+
+        let number_of_clusters = read_le(data.get(offset + 1..offset + 1 + length_size)?, false);
+        offset += 1 + length_size;
+
+        if offset_size == 0 {
+            runs.push((None, number_of_clusters as u64));
+        } else {
+            let delta = read_le(data.get(offset..offset + offset_size)?, true);
+            first_cluster = first_cluster.checked_add(delta)?;
+
+            if first_cluster < 0 {
+                return None;
+            }
+
+            runs.push((Some(first_cluster as u64), number_of_clusters as u64));
+        }
+
+        offset += offset_size;
+    }
+}
+
+/// Reads a little-endian integer of up to 8 bytes, sign-extending it if `signed`.
+fn read_le(bytes: &[u8], signed: bool) -> i64 {
+    let mut value = [0_u8; 8];
+    value[..bytes.len()].copy_from_slice(bytes);
+
+    if signed && bytes.last().map_or(false, |b| b & 0x80 != 0) {
+        value[bytes.len()..].iter_mut().for_each(|b| *b = 0xff);
+    }
+
+    i64::from_le_bytes(value)
+}
+
+/// Restores the last two bytes of every sector of a multi-sector record, such as an MFT record
+/// or an index record, from its fixup array.
+pub(crate) fn apply_fixups(data: &mut [u8]) -> Result<(), &'static str> {
+    let fixup_offset = read_u16(data, 4).ok_or("header is truncated")? as usize;
+    let fixup_count = read_u16(data, 6).ok_or("header is truncated")? as usize;
+
+    let signature = read_u16(data, fixup_offset).ok_or("fixup array is out of bounds")?;
+
+    for i in 1..fixup_count {
+        let sector_end = i * FIXUP_STRIDE - 2;
+        let replacement =
+            read_u16(data, fixup_offset + i * 2).ok_or("fixup array is out of bounds")?;
+
+        if read_u16(data, sector_end) != Some(signature) {
+            return Err("fixup value mismatch");
+        }
+
+        data[sector_end..sector_end + 2].copy_from_slice(&replacement.to_le_bytes());
+    }
+
+    Ok(())
+}
+
+/// An MFT record with its fixups applied.
+pub(crate) struct MftRecord {
+    idx: MftEntryIndex,
+    data: Vec<u8>,
+}
+
+impl MftRecord {
+    /// Reads the record of MFT entry `idx` through the `$MFT` file entry.
+    pub(crate) fn read(volume: &Volume, idx: MftEntryIndex) -> Result<Self, Error> {
+        let entry_size = volume.get_mft_entry_size()? as usize;
+        let mft = volume.get_file_entry_by_mft_idx(0)?;
+
+        let offset = idx
+            .checked_mul(entry_size as u64)
+            .ok_or_else(|| malformed(idx, "index is out of bounds"))?;
+
+        // Not `read_at`, which reads MFT records itself with the `mmap` feature.
+        let mut data = vec![0; entry_size];
+        let read_count = mft.read_at_ffi(offset, &mut data)?;
+
+        if read_count != entry_size {
+            return Err(malformed(idx, "record is truncated"));
+        }
+
+        MftRecord::from_bytes(idx, data)
+    }
+
+    /// Reads the value of the first attribute of the given type of MFT entry `idx`, following
+    /// the `$ATTRIBUTE_LIST` of the entry into its extension records if the base record does not
+    /// hold the attribute.
+    pub(crate) fn read_attribute_value(
+        volume: &Volume,
+        idx: MftEntryIndex,
+        attribute_type: AttributeType,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let record = MftRecord::read(volume, idx)?;
+
+        if let Some(value) = record.find_attribute(attribute_type)? {
+            return value.read(volume).map(Some);
+        }
+
+        let attribute_list = match record.find_attribute(AttributeType::AttributeList)? {
+            Some(value) => value.read(volume)?,
+            None => return Ok(None),
+        };
+
+        for extension_idx in extension_records(&attribute_list, attribute_type as u32, idx)? {
+            let extension = MftRecord::read(volume, extension_idx)?;
+
+            if let Some(value) = extension.find_attribute(attribute_type)? {
+                return value.read(volume).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub(crate) fn from_bytes(idx: MftEntryIndex, mut data: Vec<u8>) -> Result<Self, Error> {
+        if data.get(0..4) != Some(b"FILE") {
+            return Err(malformed(idx, "missing FILE signature"));
+        }
+
+        apply_fixups(&mut data).map_err(|reason| malformed(idx, reason))?;
+
+        Ok(MftRecord { idx, data })
+    }
+
+    /// Finds the first attribute of the given type.
+    pub(crate) fn find_attribute(
+        &self,
+        attribute_type: AttributeType,
+    ) -> Result<Option<RawAttributeValue>, Error> {
+        self.find_attribute_with_name(attribute_type, None)
+    }
+
+    /// Finds the attribute of the given type and name.
+    pub(crate) fn find_named_attribute(
+        &self,
+        attribute_type: AttributeType,
+        name: &str,
+    ) -> Result<Option<RawAttributeValue>, Error> {
+        self.find_attribute_with_name(attribute_type, Some(name))
+    }
+
+    /// Finds all attributes with the type code `type_code` and name `name`, e.g. both the long
+    /// and the short `$FILE_NAME` of an entry.
+    pub(crate) fn find_all_named_attributes(
+        &self,
+        type_code: u32,
+        name: &str,
+    ) -> Result<Vec<RawAttributeValue>, Error> {
+        self.find_attributes(type_code, Some(name), usize::max_value())
+    }
+
+    fn find_attribute_with_name(
+        &self,
+        attribute_type: AttributeType,
+        name: Option<&str>,
+    ) -> Result<Option<RawAttributeValue>, Error> {
+        Ok(self.find_attributes(attribute_type as u32, name, 1)?.pop())
+    }
+
+    /// Finds up to `limit` attributes, stopping at the last one found so that malformed
+    /// attributes after it are not reached.
+    fn find_attributes(
+        &self,
+        type_code: u32,
+        name: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<RawAttributeValue>, Error> {
+        let data = &self.data;
+        let mut found = Vec::new();
+        let mut offset = read_u16(data, 0x14)
+            .ok_or_else(|| malformed(self.idx, "header is truncated"))?
+            as usize;
+
+        while found.len() < limit {
+            let current_type = read_u32(data, offset)
+                .ok_or_else(|| malformed(self.idx, "attribute is out of bounds"))?;
+
+            if current_type == END_OF_ATTRIBUTES {
+                break;
+            }
+
+            let length = read_u32(data, offset + 4)
+                .ok_or_else(|| malformed(self.idx, "attribute is out of bounds"))?
+                as usize;
+
+            if length < 0x18 || offset + length > data.len() {
+                return Err(malformed(self.idx, "invalid attribute length"));
+            }
+
+            let attribute = &data[offset..offset + length];
+
+            if current_type == type_code && self.has_name(attribute, name)? {
+                found.push(self.attribute_value(attribute)?);
+            }
+
+            offset += length;
+        }
+
+        Ok(found)
+    }
+
+    /// Determines if `attribute` is named `name`, any name matches if `name` is `None`.
+    fn has_name(&self, attribute: &[u8], name: Option<&str>) -> Result<bool, Error> {
+        let name = match name {
+            Some(name) => name,
+            None => return Ok(true),
+        };
+
+        let name_length = attribute[9] as usize;
+        let name_offset = read_u16(attribute, 10)
+            .ok_or_else(|| malformed(self.idx, "attribute is out of bounds"))?
+            as usize;
+
+        let name_data = attribute
+            .get(name_offset..name_offset + 2 * name_length)
+            .ok_or_else(|| malformed(self.idx, "attribute name is out of bounds"))?;
+
+        let attribute_name = name_data
+            .chunks_exact(2)
+            .map(|unit| u16::from_le_bytes([unit[0], unit[1]]));
+
+        Ok(attribute_name.eq(name.encode_utf16()))
+    }
+
+    fn attribute_value<'r>(&self, attribute: &'r [u8]) -> Result<RawAttributeValue<'r>, Error> {
+        let out_of_bounds = || malformed(self.idx, "attribute is out of bounds");
+
+        if attribute[8] != 0 {
+            let runs_offset = read_u16(attribute, 0x20).ok_or_else(out_of_bounds)? as usize;
+            let size = read_u64(attribute, 0x30).ok_or_else(out_of_bounds)?;
+            let valid_data_size = read_u64(attribute, 0x38).ok_or_else(out_of_bounds)?;
+
+            let runs = attribute
+                .get(runs_offset..)
+                .and_then(decode_runs)
+                .ok_or_else(|| malformed(self.idx, "invalid run list"))?;
+
+            return Ok(RawAttributeValue::NonResident {
+                runs,
+                size,
+                valid_data_size,
+            });
+        }
+
+        let value_size = read_u32(attribute, 0x10).ok_or_else(out_of_bounds)? as usize;
+        let value_offset = read_u16(attribute, 0x14).ok_or_else(out_of_bounds)? as usize;
+
+        let value = attribute
+            .get(value_offset..value_offset + value_size)
+            .ok_or_else(|| malformed(self.idx, "resident value is out of bounds"))?;
+
+        Ok(RawAttributeValue::Resident(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    #[test]
+    fn test_malformed_records_return_errors() {
+        assert!(MftRecord::from_bytes(0, vec![]).is_err());
+        assert!(MftRecord::from_bytes(0, b"FILE".to_vec()).is_err());
+
+        // Fixup array offset beyond the end of the record.
+        let mut data = vec![0; 1024];
+        data[0..4].copy_from_slice(b"FILE");
+        data[4..6].copy_from_slice(&0xfff0_u16.to_le_bytes());
+        data[6..8].copy_from_slice(&3_u16.to_le_bytes());
+        assert!(MftRecord::from_bytes(0, data).is_err());
+    }
+
+    #[test]
+    fn test_decode_runs() {
+        // 3 clusters at 8056, 3 clusters 16 clusters further, 16 sparse clusters, and 2
+        // clusters 10 clusters back.
+        let data = [
+            0x21, 0x03, 0x78, 0x1f, 0x11, 0x03, 0x10, 0x01, 0x10, 0x11, 0x02, 0xf6, 0x00,
+        ];
+
+        assert_eq!(
+            decode_runs(&data),
+            Some(vec![
+                (Some(8056), 3),
+                (Some(8072), 3),
+                (None, 16),
+                (Some(8062), 2)
+            ])
+        );
+
+        assert_eq!(decode_runs(&[0x21, 0x03]), None);
+        assert_eq!(decode_runs(&[0x11, 0x01, 0x80, 0x00]), None);
+    }
+
+    #[test]
+    fn test_read_value_larger_than_volume_is_err() {
+        let volume = sample_volume().unwrap();
+        let volume_size = volume.get_size().unwrap();
+
+        let sparse = RawAttributeValue::NonResident {
+            runs: vec![(None, u64::max_value())],
+            size: u64::max_value(),
+            valid_data_size: u64::max_value(),
+        };
+        assert!(sparse.read(&volume).is_err());
+        assert_eq!(sparse.read_capped(&volume, 16).unwrap(), vec![0; 16]);
+
+        let out_of_bounds = RawAttributeValue::NonResident {
+            runs: vec![(Some(volume_size / 512 - 1), 4)],
+            size: 2048,
+            valid_data_size: 2048,
+        };
+        assert!(out_of_bounds.read(&volume).is_err());
+    }
+
+    #[test]
+    fn test_extension_records() {
+        let entry = |type_code: u32, reference: u64| {
+            let mut entry = vec![0; 32];
+            entry[0..4].copy_from_slice(&type_code.to_le_bytes());
+            entry[4..6].copy_from_slice(&32_u16.to_le_bytes());
+            entry[16..24].copy_from_slice(&reference.to_le_bytes());
+            entry
+        };
+
+        let attribute_list = [
+            entry(0x10, 40 | 1 << 48),
+            entry(0xc0, 40 | 1 << 48),
+            entry(0xc0, 41 | 1 << 48),
+            entry(0xc0, 41 | 1 << 48),
+            entry(0x80, 42 | 1 << 48),
+        ]
+        .concat();
+
+        assert_eq!(
+            extension_records(&attribute_list, 0xc0, 40).unwrap(),
+            vec![41]
+        );
+        assert!(extension_records(&attribute_list, 0xe0, 40)
+            .unwrap()
+            .is_empty());
+        assert!(extension_records(&attribute_list[..40], 0xc0, 40).is_err());
+        assert!(extension_records(&[0; 32], 0xc0, 40).is_err());
+    }
+
+    #[test]
+    fn test_read_huge_index_is_err() {
+        let volume = sample_volume().unwrap();
+
+        assert!(MftRecord::read(&volume, u64::MAX).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+use crate::extent::Extent;
+use memmap2::Mmap;
+use std::cmp::min;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+/// Where the default data stream of a file entry lies in the image, see
+/// `FileEntry::stream_layout`.
+pub(crate) struct StreamLayout {
+    pub(crate) extents: Vec<Extent>,
+    pub(crate) size: u64,
+    pub(crate) valid_data_size: u64,
+}
+
+/// A read-only memory mapping of the image backing a volume.
+pub(crate) struct VolumeMapping {
+    map: Mmap,
+}
+
+impl VolumeMapping {
+    pub(crate) fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let map = unsafe { Mmap::map(&file)? };
+
+        Ok(VolumeMapping { map })
+    }
+
+    /// Copies data at `offset` of a stream laid out as `layout` into `buf`.
+    ///
+    /// Returns `None` if the stream cannot be served from the mapping, i.e. it is resident,
+    /// compressed, or its extents do not fall within the image. Like libfsntfs, bytes past the
+    /// valid data length of the stream are read as zeros rather than as stored on disk.
+    pub(crate) fn read_at(
+        &self,
+        layout: &StreamLayout,
+        offset: u64,
+        buf: &mut [u8],
+    ) -> Option<usize> {
+        let StreamLayout {
+            extents,
+            size: data_size,
+            valid_data_size,
+        } = layout;
+        let data_size = *data_size;
+
+        if extents.is_empty() || extents.iter().any(Extent::is_compressed) {
+            return None;
+        }
+
+        if offset >= data_size {
+            return Some(0);
+        }
+
+        let to_read = min(buf.len() as u64, data_size - offset) as usize;
+        let mut read_count = 0;
+        let mut extent_start = 0_u64;
+
+        for extent in extents {
+            if read_count == to_read {
+                break;
+            }
+
+            let extent_end = extent_start.checked_add(extent.size)?;
+            let position = offset + read_count as u64;
+
+            if position < extent_end {
+                let relative_offset = position - extent_start;
+                let count =
+                    min(extent.size - relative_offset, (to_read - read_count) as u64) as usize;
+                let out = &mut buf[read_count..read_count + count];
+
+                if extent.is_sparse() {
+                    out.iter_mut().for_each(|b| *b = 0);
+                } else {
+                    let start = extent.offset.checked_add(relative_offset)? as usize;
+                    out.copy_from_slice(self.map.get(start..start.checked_add(count)?)?);
+                }
+
+                read_count += count;
+            }
+
+            extent_start = extent_end;
+        }
+
+        // The extents did not cover the requested range, let libfsntfs handle it.
+        if read_count < to_read {
+            return None;
+        }
+
+        let valid_count = min(valid_data_size.saturating_sub(offset), read_count as u64) as usize;
+        buf[valid_count..read_count].iter_mut().for_each(|b| *b = 0);
+
+        Some(read_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::*;
+
+    #[test]
+    fn test_mmap_read_matches_ffi_read() {
+        let volume = sample_volume().unwrap();
+        let entry = file_entry(&volume).unwrap();
+        let size = entry.get_size().unwrap() as usize;
+
+        let mut mapped = vec![0; size];
+        let mut unmapped = vec![0; size];
+
+        assert_eq!(entry.read_at(0, &mut mapped).unwrap(), size);
+        assert_eq!(entry.read_at_ffi(0, &mut unmapped).unwrap(), size);
+
+        assert_eq!(mapped, unmapped);
+    }
+
+    #[test]
+    fn test_mmap_chunked_reads_match_ffi_reads() {
+        // Not a multiple of the cluster size, so that chunks straddle clusters.
+        const CHUNK_SIZE: usize = 1000;
+
+        let volume = sample_volume().unwrap();
+        let entry = file_entry(&volume).unwrap();
+        let size = entry.get_size().unwrap();
+        let layout = entry.stream_layout().unwrap().unwrap();
+        let mapping = volume.mapping().unwrap();
+
+        let mut mapped = vec![0; CHUNK_SIZE];
+        let mut unmapped = vec![0; CHUNK_SIZE];
+        let mut offset = 0;
+
+        while offset < size {
+            // Every chunk is copied from the mapping, without falling back to libfsntfs.
+            let read_count = mapping.read_at(layout, offset, &mut mapped).unwrap();
+
+            assert_eq!(
+                entry.read_at_ffi(offset, &mut unmapped).unwrap(),
+                read_count
+            );
+            assert_eq!(mapped[..read_count], unmapped[..read_count]);
+
+            offset += read_count as u64;
+        }
+    }
+
+    #[test]
+    fn test_mmap_read_zeroes_data_past_valid_data_size() {
+        let volume = preallocated_volume().unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(33).unwrap();
+        let size = entry.get_size().unwrap() as usize;
+        let layout = entry.stream_layout().unwrap().unwrap();
+
+        let mut mapped = vec![0xff; size];
+        let mut unmapped = vec![0xff; size];
+
+        assert_eq!(
+            volume
+                .mapping()
+                .unwrap()
+                .read_at(layout, 0, &mut mapped)
+                .unwrap(),
+            size
+        );
+        assert_eq!(entry.read_at_ffi(0, &mut unmapped).unwrap(), size);
+
+        assert_eq!(mapped, unmapped);
+        assert!(mapped[..PREALLOCATED_VALID_DATA_SIZE]
+            .iter()
+            .any(|&b| b != 0));
+        assert!(mapped[PREALLOCATED_VALID_DATA_SIZE..]
+            .iter()
+            .all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_mmap_read_caches_the_stream_layout() {
+        let volume = sample_volume().unwrap();
+        let entry = file_entry(&volume).unwrap();
+        let mut buf = vec![0; 100];
+
+        entry.read_at(0, &mut buf).unwrap();
+        let (read_count, allocations) = count_allocations(|| entry.read_at(0, &mut buf).unwrap());
+
+        assert_eq!(read_count, buf.len());
+        assert_eq!(allocations, 0);
+    }
+}
@@ -0,0 +1,64 @@
+//! Name comparisons for lookups by name or path.
+use unicode_normalization::UnicodeNormalization;
+
+/// How names are compared by `FileEntry::find_sub_entry` and `Volume::get_file_entry_by_path`.
+///
+/// libfsntfs compares names exactly, while Windows treats them as case-insensitive by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMode {
+    Exact,
+    /// Compares the Unicode lowercase forms of the names.
+    CaseInsensitive,
+    /// Like `CaseInsensitive`, but also treats canonically equivalent names (e.g. a precomposed
+    /// `é` and an `e` followed by a combining accent) as equal.
+    NormalizedCaseInsensitive,
+}
+
+impl Default for MatchMode {
+    fn default() -> Self {
+        MatchMode::Exact
+    }
+}
+
+impl MatchMode {
+    /// Determines if `name` matches `other` in this mode.
+    pub fn matches(self, name: &str, other: &str) -> bool {
+        match self {
+            MatchMode::Exact => name == other,
+            MatchMode::CaseInsensitive => lowercase(name).eq(lowercase(other)),
+            MatchMode::NormalizedCaseInsensitive => {
+                lowercase(name).nfc().eq(lowercase(other).nfc())
+            }
+        }
+    }
+}
+
+fn lowercase(name: &str) -> impl Iterator<Item = char> + '_ {
+    name.chars().flat_map(char::to_lowercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact() {
+        assert!(MatchMode::Exact.matches("Windows", "Windows"));
+        assert!(!MatchMode::Exact.matches("WINDOWS", "Windows"));
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        assert!(MatchMode::CaseInsensitive.matches("WINDOWS", "Windows"));
+        assert!(MatchMode::CaseInsensitive.matches("ΣΊΣΥΦΟΣ", "σίσυφοσ"));
+        assert!(!MatchMode::CaseInsensitive.matches("Windows", "Windows.old"));
+        assert!(!MatchMode::CaseInsensitive.matches("CAFÉ", "cafe\u{301}"));
+    }
+
+    #[test]
+    fn test_normalized_case_insensitive() {
+        assert!(MatchMode::NormalizedCaseInsensitive.matches("WINDOWS", "Windows"));
+        assert!(MatchMode::NormalizedCaseInsensitive.matches("CAFÉ", "cafe\u{301}"));
+        assert!(!MatchMode::NormalizedCaseInsensitive.matches("cafe", "café"));
+    }
+}
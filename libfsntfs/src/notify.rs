@@ -0,0 +1,145 @@
+//! Redirection of the diagnostic notifications libfsntfs prints while parsing.
+//!
+//! Note that most notifications are only emitted when libfsntfs is built with
+//! `--enable-verbose-output` or `--enable-debug-output`.
+use crate::error::Error;
+use crate::ffi_error::LibfsntfsErrorRefMut;
+use libfsntfs_sys::FILE;
+use std::convert::TryFrom;
+use std::io::{self, Write};
+use std::os::raw::c_int;
+use std::ptr;
+use std::thread::{self, JoinHandle};
+
+extern "C" {
+    pub fn libfsntfs_notify_set_verbose(verbose: c_int);
+    pub fn libfsntfs_notify_set_stream(
+        stream: *mut FILE,
+        error: *mut LibfsntfsErrorRefMut,
+    ) -> c_int;
+}
+
+/// Enables or disables verbose notifications.
+pub fn set_notify_verbose(verbose: bool) {
+    unsafe { libfsntfs_notify_set_verbose(verbose as c_int) }
+}
+
+/// Forwards notifications to a writer until dropped, after which they are discarded.
+///
+/// The notification stream is global to libfsntfs, so only one `NotifyStream` should be alive
+/// at a time.
+#[cfg(unix)]
+pub struct NotifyStream {
+    stream: *mut libc::FILE,
+    forwarder: Option<JoinHandle<()>>,
+}
+
+/// Sends libfsntfs notifications to `writer`.
+///
+/// The notifications are written to a pipe, which is drained into `writer` on a background
+/// thread.
+#[cfg(unix)]
+pub fn set_notify_stream<W: Write + Send + 'static>(mut writer: W) -> Result<NotifyStream, Error> {
+    use std::fs::File;
+    use std::os::unix::io::FromRawFd;
+
+    let mut fds = [0 as libc::c_int; 2];
+
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(Error::Other(format!(
+            "Failed to create notification pipe: {}",
+            io::Error::last_os_error()
+        )));
+    }
+
+    let mut reader = unsafe { File::from_raw_fd(fds[0]) };
+    let stream = unsafe { libc::fdopen(fds[1], b"w\0".as_ptr() as *const libc::c_char) };
+
+    if stream.is_null() {
+        unsafe { libc::close(fds[1]) };
+
+        return Err(Error::Other(format!(
+            "Failed to open notification stream: {}",
+            io::Error::last_os_error()
+        )));
+    }
+
+    // Deliver notifications as they are printed, rather than when the buffer fills up.
+    unsafe { libc::setvbuf(stream, ptr::null_mut(), libc::_IOLBF, 0) };
+
+    let mut error = ptr::null_mut();
+
+    if unsafe { libfsntfs_notify_set_stream(stream as *mut FILE, &mut error) } != 1 {
+        unsafe { libc::fclose(stream) };
+
+        return Err(Error::try_from(error)?);
+    }
+
+    let forwarder = thread::spawn(move || {
+        let _ = io::copy(&mut reader, &mut writer);
+    });
+
+    Ok(NotifyStream {
+        stream,
+        forwarder: Some(forwarder),
+    })
+}
+
+#[cfg(unix)]
+impl Drop for NotifyStream {
+    fn drop(&mut self) {
+        let mut error = ptr::null_mut();
+
+        unsafe {
+            if libfsntfs_notify_set_stream(ptr::null_mut(), &mut error) != 1 {
+                let _ = Error::try_from(error);
+            }
+
+            // Closing the write end lets the forwarding thread reach EOF.
+            libc::fclose(self.stream);
+        }
+
+        if let Some(forwarder) = self.forwarder.take() {
+            let _ = forwarder.join();
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // Requires libfsntfs to be configured with `--enable-verbose-output`.
+    #[test]
+    #[ignore]
+    fn test_capture_notifications_during_open() {
+        let buffer = SharedBuffer::default();
+
+        set_notify_verbose(true);
+        let stream = set_notify_stream(buffer.clone()).unwrap();
+
+        sample_volume().unwrap();
+
+        drop(stream);
+        set_notify_verbose(false);
+
+        let output = String::from_utf8_lossy(&buffer.0.lock().unwrap()).to_string();
+        assert!(output.lines().count() >= 1);
+    }
+}
@@ -0,0 +1,198 @@
+//! A builder for opening volumes with options beyond those of `Volume::open`.
+use crate::error::Error;
+use crate::ffi_error::LibfsntfsErrorRefMut;
+use crate::volume::{AccessMode, Volume};
+use std::convert::TryFrom;
+use std::os::raw::c_int;
+use std::path::Path;
+use std::ptr;
+
+extern "C" {
+    pub fn libfsntfs_get_codepage(codepage: *mut c_int, error: *mut LibfsntfsErrorRefMut) -> c_int;
+    pub fn libfsntfs_set_codepage(codepage: c_int, error: *mut LibfsntfsErrorRefMut) -> c_int;
+}
+
+/// Retrieves the codepage libfsntfs uses for narrow strings, e.g. 1252 for Windows-1252.
+pub fn get_codepage() -> Result<i32, Error> {
+    let mut codepage = 0;
+    let mut error = ptr::null_mut();
+
+    if unsafe { libfsntfs_get_codepage(&mut codepage, &mut error) } != 1 {
+        Err(Error::try_from(error)?)
+    } else {
+        Ok(codepage)
+    }
+}
+
+/// Sets the codepage libfsntfs uses for narrow strings.
+///
+/// The codepage is a process-wide setting of libfsntfs, which affects all volumes.
+pub fn set_codepage(codepage: i32) -> Result<(), Error> {
+    let mut error = ptr::null_mut();
+
+    if unsafe { libfsntfs_set_codepage(codepage, &mut error) } != 1 {
+        Err(Error::try_from(error)?)
+    } else {
+        Ok(())
+    }
+}
+
+/// Options for opening a volume, e.g.
+/// `VolumeOptions::new().offset(1_048_576).read_only().codepage(1252).open(path)`.
+#[derive(Debug, Clone)]
+pub struct VolumeOptions {
+    offset: u64,
+    mode: AccessMode,
+    codepage: Option<i32>,
+    #[cfg(feature = "mmap")]
+    memory_mapping: bool,
+    strict: bool,
+}
+
+impl Default for VolumeOptions {
+    fn default() -> Self {
+        VolumeOptions {
+            offset: 0,
+            mode: AccessMode::Read,
+            codepage: None,
+            #[cfg(feature = "mmap")]
+            memory_mapping: true,
+            strict: false,
+        }
+    }
+}
+
+impl VolumeOptions {
+    /// Options for opening a volume for reading, at the start of the file.
+    pub fn new() -> Self {
+        VolumeOptions::default()
+    }
+
+    /// Opens a volume that starts `offset` bytes into the file, see `Volume::open_at_offset`.
+    /// Volumes at a nonzero offset can only be opened for reading.
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn read_only(self) -> Self {
+        self.access_mode(AccessMode::Read)
+    }
+
+    pub fn access_mode(mut self, mode: AccessMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the codepage for narrow strings when the volume is opened, see `set_codepage`.
+    pub fn codepage(mut self, codepage: i32) -> Self {
+        self.codepage = Some(codepage);
+        self
+    }
+
+    /// Enables or disables the memory mapping of the image, see `Volume::set_memory_mapping`.
+    #[cfg(feature = "mmap")]
+    pub fn memory_mapping(mut self, enabled: bool) -> Self {
+        self.memory_mapping = enabled;
+        self
+    }
+
+    /// Fails with `Error::DirtyVolume` if the volume was not cleanly unmounted, like
+    /// `Volume::open_strict`.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Opens the volume in the file at `path`.
+    pub fn open(&self, path: impl AsRef<Path>) -> Result<Volume, Error> {
+        let path = path.as_ref();
+
+        if let Some(codepage) = self.codepage {
+            set_codepage(codepage)?;
+        }
+
+        let mut volume = if self.offset == 0 {
+            let path = path.to_str().ok_or_else(|| {
+                Error::Other(format!("Path {} is not valid UTF-8", path.display()))
+            })?;
+
+            Volume::open(path, self.mode)?
+        } else if self.mode == AccessMode::Read {
+            Volume::open_at_offset(path, self.offset)?
+        } else {
+            return Err(Error::Other(
+                "Volumes at an offset can only be opened for reading".to_owned(),
+            ));
+        };
+
+        #[cfg(feature = "mmap")]
+        volume.set_memory_mapping(self.memory_mapping);
+        volume.set_open_codepage(self.codepage);
+
+        if self.strict && volume.is_dirty()? {
+            return Err(Error::DirtyVolume);
+        }
+
+        Ok(volume)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_open_with_offset_and_codepage() {
+        const PARTITION_OFFSET: u64 = 2048 * 512;
+
+        let dir = TempDir::new("volume_options").unwrap();
+        let disk_path = dir.path().join("disk.dd");
+
+        let mut disk = File::create(&disk_path).unwrap();
+        disk.write_all(&vec![0; PARTITION_OFFSET as usize]).unwrap();
+        disk.write_all(&fs::read(sample_volume_path()).unwrap())
+            .unwrap();
+        drop(disk);
+
+        let volume = VolumeOptions::new()
+            .offset(PARTITION_OFFSET)
+            .read_only()
+            .codepage(1252)
+            .strict(true)
+            .open(&disk_path)
+            .unwrap();
+
+        assert_eq!(volume.get_name().unwrap(), "KW-SRCH-1");
+        assert_eq!(get_codepage().unwrap(), 1252);
+
+        let reopened = volume.reopen().unwrap();
+        assert_eq!(reopened.get_name().unwrap(), "KW-SRCH-1");
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_open_without_memory_mapping() {
+        let volume = VolumeOptions::new()
+            .memory_mapping(false)
+            .open(sample_volume_path())
+            .unwrap();
+
+        assert!(!volume.is_memory_mapped());
+        assert!(!volume.reopen().unwrap().is_memory_mapped());
+    }
+
+    #[test]
+    fn test_open_at_offset_for_writing_fails() {
+        let result = VolumeOptions::new()
+            .offset(512)
+            .access_mode(AccessMode::Write)
+            .open(sample_volume_path());
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,244 @@
+use crate::attribute::{AttributeType, NameNamespace};
+use crate::error::Error;
+use crate::file_entry::FileEntry;
+use crate::volume::{
+    mft_entry_index, sequence_number, MftEntryIndex, Volume, ROOT_DIRECTORY_MFT_ENTRY_INDEX,
+};
+use std::collections::{HashMap, HashSet};
+use std::os::raw::c_int;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+struct IndexedEntry {
+    parent_file_reference: u64,
+    sequence_number: u16,
+    name: String,
+}
+
+/// Names and parent references of all MFT entries, for resolving paths without further FFI
+/// calls.
+#[derive(Debug, Clone, Default)]
+pub struct PathIndex {
+    entries: HashMap<MftEntryIndex, IndexedEntry>,
+}
+
+impl PathIndex {
+    /// Number of indexed entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Resolves the path of a file reference (or a bare MFT entry index), relative to the root
+    /// directory.
+    ///
+    /// Returns `None` if the entry is unknown, if it is orphaned (an ancestor is missing or has
+    /// been reused, as indicated by the sequence number) or if the parent references form a cycle.
+    pub fn resolve(&self, file_reference: u64) -> Option<PathBuf> {
+        let mut idx = mft_entry_index(file_reference);
+        let mut names = Vec::new();
+        let mut visited = HashSet::new();
+
+        while idx != ROOT_DIRECTORY_MFT_ENTRY_INDEX {
+            if !visited.insert(idx) {
+                return None;
+            }
+
+            let entry = self.entries.get(&idx)?;
+            names.push(entry.name.as_str());
+
+            let parent_idx = mft_entry_index(entry.parent_file_reference);
+            let parent = self.entries.get(&parent_idx)?;
+
+            if parent.sequence_number != sequence_number(entry.parent_file_reference) {
+                return None;
+            }
+
+            idx = parent_idx;
+        }
+
+        Some(names.iter().rev().collect())
+    }
+}
+
+impl Volume {
+    /// Builds a `PathIndex` with a single pass over the MFT.
+    /// Entries that cannot be read or have no name are left out.
+    pub fn build_path_index(&self) -> Result<PathIndex, Error> {
+        let mut index = PathIndex {
+            entries: HashMap::with_capacity(self.get_number_of_file_entries()?),
+        };
+
+        for entry in self.iter_entries()? {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(_) => continue,
+            };
+
+            let indexed = entry.get_file_reference().and_then(|file_reference| {
+                Ok((
+                    file_reference,
+                    IndexedEntry {
+                        parent_file_reference: entry.get_parent_file_reference()?,
+                        sequence_number: sequence_number(file_reference),
+                        name: entry.get_name()?,
+                    },
+                ))
+            });
+
+            if let Ok((file_reference, indexed)) = indexed {
+                if !indexed.name.is_empty() {
+                    index
+                        .entries
+                        .insert(mft_entry_index(file_reference), indexed);
+                }
+            }
+        }
+
+        Ok(index)
+    }
+}
+
+impl<'a> FileEntry<'a> {
+    /// Resolves the path of every hard link of the entry, one for each of its `$FILE_NAME`
+    /// attributes, relative to the root directory. `volume` must be the volume of the entry.
+    ///
+    /// This builds a `PathIndex` of the volume, use `all_paths_in` to look up the links of
+    /// many entries.
+    pub fn all_paths(&self, volume: &Volume) -> Result<Vec<PathBuf>, Error> {
+        self.all_paths_in(&volume.build_path_index()?)
+    }
+
+    /// Resolves the path of every hard link of the entry like `all_paths`, with the parent
+    /// directories looked up in `index`.
+    ///
+    /// DOS (8.3) names are left out, as they alias a long name in the same directory. Links
+    /// whose parent directory cannot be resolved, e.g. because it was deleted, are left out too.
+    pub fn all_paths_in(&self, index: &PathIndex) -> Result<Vec<PathBuf>, Error> {
+        let mut paths = Vec::new();
+
+        for (attribute_index, attribute) in self.iter_attributes()?.enumerate() {
+            let attribute = attribute?;
+
+            if attribute.get_type()? != AttributeType::FileName
+                || attribute.get_file_name_namespace()? == NameNamespace::Dos
+            {
+                continue;
+            }
+
+            let attribute_index = attribute_index as c_int;
+            let parent = self.get_parent_file_reference_by_attribute_index(attribute_index)?;
+
+            if let Some(directory) = index.resolve(parent) {
+                let path = directory.join(self.get_name_by_attribute_index(attribute_index)?);
+
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+
+        Ok(paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+    use crate::name_match::MatchMode;
+    use std::path::Path;
+
+    #[test]
+    fn test_resolve_nested_path() {
+        let volume = sample_volume().unwrap();
+        let index = volume.build_path_index().unwrap();
+
+        let entry = volume
+            .get_file_entry_by_path("\\$Extend\\$ObjId", MatchMode::Exact)
+            .unwrap();
+        let path = index.resolve(entry.get_file_reference().unwrap()).unwrap();
+
+        assert_eq!(path, Path::new("$Extend").join("$ObjId"));
+        assert_eq!(path, entry.full_path().unwrap());
+    }
+
+    #[test]
+    fn test_resolve_root() {
+        let volume = sample_volume().unwrap();
+        let index = volume.build_path_index().unwrap();
+
+        assert_eq!(
+            index.resolve(ROOT_DIRECTORY_MFT_ENTRY_INDEX),
+            Some(PathBuf::new())
+        );
+    }
+
+    #[test]
+    fn test_resolve_cycle_and_orphan() {
+        let mut index = PathIndex::default();
+        let reference = |idx: u64, sequence: u64| idx | (sequence << 48);
+
+        index.entries.insert(
+            100,
+            IndexedEntry {
+                parent_file_reference: reference(101, 1),
+                sequence_number: 1,
+                name: "a".to_string(),
+            },
+        );
+        index.entries.insert(
+            101,
+            IndexedEntry {
+                parent_file_reference: reference(100, 1),
+                sequence_number: 1,
+                name: "b".to_string(),
+            },
+        );
+        index.entries.insert(
+            102,
+            IndexedEntry {
+                parent_file_reference: reference(101, 2),
+                sequence_number: 1,
+                name: "c".to_string(),
+            },
+        );
+
+        assert_eq!(index.resolve(100), None);
+        assert_eq!(index.resolve(102), None);
+        assert_eq!(index.resolve(103), None);
+    }
+
+    #[test]
+    fn test_all_paths_of_single_link() {
+        let volume = sample_volume().unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(27).unwrap();
+
+        assert_eq!(
+            entry.all_paths(&volume).unwrap(),
+            vec![PathBuf::from("file-r-1.dat")]
+        );
+    }
+
+    #[test]
+    fn test_all_paths_of_hard_linked_file() {
+        let volume = hardlink_volume().unwrap();
+        let entry = volume
+            .get_file_entry_by_path("\\file-r-1.dat", MatchMode::Exact)
+            .unwrap();
+
+        let mut paths = entry.all_paths(&volume).unwrap();
+        paths.sort();
+
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("dir-r-4").join("link.dat"),
+                PathBuf::from("file-r-1.dat"),
+            ]
+        );
+    }
+}
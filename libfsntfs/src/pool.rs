@@ -0,0 +1,133 @@
+use crate::error::Error;
+use crate::volume::{AccessMode, Volume};
+use std::sync::{Condvar, Mutex, PoisonError};
+
+/// A volume that is only ever used by one thread at a time.
+struct PooledVolume(Volume);
+
+// libfsntfs handles are not bound to the thread that created them, they only must not be used
+// concurrently. The pool guarantees that a volume is checked out by at most one thread.
+unsafe impl Send for PooledVolume {}
+
+/// A fixed set of independently opened handles to the same volume, allowing concurrent reads
+/// from multiple threads.
+pub struct VolumePool {
+    volumes: Mutex<Vec<PooledVolume>>,
+    available: Condvar,
+    size: usize,
+}
+
+/// Returns the checked out volume to the pool, even if the caller panicked.
+struct Checkout<'p> {
+    pool: &'p VolumePool,
+    volume: Option<PooledVolume>,
+}
+
+impl<'p> Drop for Checkout<'p> {
+    fn drop(&mut self) {
+        if let Some(volume) = self.volume.take() {
+            let mut volumes = self
+                .pool
+                .volumes
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+
+            volumes.push(volume);
+            self.pool.available.notify_one();
+        }
+    }
+}
+
+impl VolumePool {
+    /// Opens `size` read-only handles to the volume at `filename`.
+    pub fn open(filename: impl AsRef<str>, size: usize) -> Result<Self, Error> {
+        if size == 0 {
+            return Err(Error::Other("Pool size must be greater than 0".to_owned()));
+        }
+
+        let volumes = (0..size)
+            .map(|_| Volume::open(filename.as_ref(), AccessMode::Read).map(PooledVolume))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(VolumePool {
+            volumes: Mutex::new(volumes),
+            available: Condvar::new(),
+            size,
+        })
+    }
+
+    /// Number of handles in the pool.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Runs `f` with a volume from the pool, blocking until one is available.
+    pub fn with_volume<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&Volume) -> R,
+    {
+        let volume = {
+            // A panic in `f` never leaves the pool inconsistent, so poisoning can be ignored.
+            let mut volumes = self.volumes.lock().unwrap_or_else(PoisonError::into_inner);
+
+            loop {
+                match volumes.pop() {
+                    Some(volume) => break volume,
+                    None => {
+                        volumes = self
+                            .available
+                            .wait(volumes)
+                            .unwrap_or_else(PoisonError::into_inner)
+                    }
+                }
+            }
+        };
+
+        let mut checkout = Checkout {
+            pool: self,
+            volume: None,
+        };
+
+        f(&checkout.volume.get_or_insert(volume).0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_concurrent_reads_through_pool() {
+        let pool = Arc::new(VolumePool::open(sample_volume_path(), 3).unwrap());
+        let volume = sample_volume().unwrap();
+
+        let handles: Vec<_> = (0..8_u64)
+            .map(|idx| {
+                let pool = Arc::clone(&pool);
+
+                thread::spawn(move || {
+                    pool.with_volume(|volume| {
+                        volume
+                            .get_file_entry_by_mft_idx(idx)
+                            .and_then(|entry| entry.get_name())
+                            .unwrap()
+                    })
+                })
+            })
+            .collect();
+
+        for (idx, handle) in handles.into_iter().enumerate() {
+            let name = handle.join().unwrap();
+            let expected = volume
+                .get_file_entry_by_mft_idx(idx as u64)
+                .unwrap()
+                .get_name()
+                .unwrap();
+
+            assert_eq!(name, expected);
+        }
+    }
+}
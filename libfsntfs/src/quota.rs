@@ -0,0 +1,195 @@
+//! Disk usage and limits of the owners of files, from the `$Q` index of `$Extend\$Quota`.
+use crate::error::Error;
+use crate::filetime::filetime_to_system_time;
+use crate::index_entry::read_index_nodes;
+use crate::mft::{read_u16, read_u32, read_u64, MftRecord};
+use crate::name_match::MatchMode;
+use crate::security::Sid;
+use crate::volume::{mft_entry_index, Volume};
+use std::time::SystemTime;
+
+const QUOTA_PATH: &str = "\\$Extend\\$Quota";
+const QUOTA_INDEX_NAME: &str = "$Q";
+
+/// Owner identifier of the entry that holds the default limits and the quota flags of the volume.
+const DEFAULTS_OWNER_ID: u32 = 1;
+const QUOTA_FLAG_TRACKING_ENABLED: u32 = 0x0000_0010;
+
+/// Size of the quota control data preceding the SID.
+const QUOTA_DATA_SIZE: usize = 48;
+
+/// The usage and limits of an owner, see `Volume::iter_quotas`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaEntry {
+    /// Identifier of the owner, as referenced by `StandardInformation::owner_id`.
+    pub owner_id: u32,
+    pub sid: Sid,
+    pub flags: u32,
+    /// Size of the files of the owner, in bytes.
+    pub bytes_used: u64,
+    pub change_time: Option<SystemTime>,
+    /// Usage past which an event is logged, `None` if there is no threshold.
+    pub threshold: Option<u64>,
+    /// Usage past which writes are denied if quotas are enforced, `None` if there is no limit.
+    pub limit: Option<u64>,
+    /// When the owner exceeded the threshold.
+    pub exceeded_time: Option<SystemTime>,
+}
+
+/// Iterator over the quota entries of a volume, see `Volume::iter_quotas`.
+pub struct IterQuotas(std::vec::IntoIter<Result<QuotaEntry, Error>>);
+
+impl Iterator for IterQuotas {
+    type Item = Result<QuotaEntry, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+/// Limits are stored as signed values, with -1 for none.
+fn limit(value: u64) -> Option<u64> {
+    if (value as i64) < 0 {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Splits an index entry of `$Q` into the owner identifier it is keyed by and its data.
+fn parse_index_entry(entry: &[u8]) -> Option<(u32, &[u8])> {
+    let data_offset = read_u16(entry, 0)? as usize;
+    let data_size = read_u16(entry, 2)? as usize;
+
+    Some((
+        read_u32(entry, 16)?,
+        entry.get(data_offset..data_offset.checked_add(data_size)?)?,
+    ))
+}
+
+fn parse_quota_entry(owner_id: u32, data: &[u8]) -> Result<QuotaEntry, Error> {
+    let truncated = || Error::Other(format!("Quota entry of owner {} is truncated", owner_id));
+    let field = |offset| read_u64(data, offset).ok_or_else(truncated);
+
+    if data.len() <= QUOTA_DATA_SIZE {
+        return Err(truncated());
+    }
+
+    Ok(QuotaEntry {
+        owner_id,
+        sid: Sid::from_bytes(data, QUOTA_DATA_SIZE)?,
+        flags: read_u32(data, 4).ok_or_else(truncated)?,
+        bytes_used: field(8)?,
+        change_time: filetime_to_system_time(field(16)?),
+        threshold: limit(field(24)?),
+        limit: limit(field(32)?),
+        exceeded_time: filetime_to_system_time(field(40)?),
+    })
+}
+
+impl Volume {
+    /// Iterates the usage and limits of every owner tracked in `$Extend\$Quota:$Q`, which
+    /// libfsntfs does not expose.
+    ///
+    /// Yields nothing if quota tracking is disabled, even if the index holds stale entries.
+    pub fn iter_quotas(&self) -> Result<IterQuotas, Error> {
+        let quota = match self.get_file_entry_by_path(QUOTA_PATH, MatchMode::Exact) {
+            Ok(quota) => quota,
+            Err(e) if e.is_not_found() => return Ok(IterQuotas(Vec::new().into_iter())),
+            Err(e) => return Err(e),
+        };
+
+        let record = MftRecord::read(self, mft_entry_index(quota.get_file_reference()?))?;
+
+        let mut is_tracking_enabled = false;
+        let mut entries = Vec::new();
+
+        for node in read_index_nodes(self, &record, QUOTA_INDEX_NAME)? {
+            let node = node?;
+            let (bounds, _) = node.allocated_entries();
+
+            for bounds in bounds {
+                let bounds = bounds?;
+
+                let (owner_id, data) =
+                    parse_index_entry(&node.data()[bounds.clone()]).ok_or_else(|| {
+                        Error::Other(format!("$Q entry at offset {} is malformed", bounds.start))
+                    })?;
+
+                if owner_id == DEFAULTS_OWNER_ID {
+                    is_tracking_enabled = read_u32(data, 4)
+                        .map_or(false, |flags| flags & QUOTA_FLAG_TRACKING_ENABLED != 0);
+                } else {
+                    entries.push(parse_quota_entry(owner_id, data));
+                }
+            }
+        }
+
+        if !is_tracking_enabled {
+            entries.clear();
+        }
+
+        Ok(IterQuotas(entries.into_iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    /// Owner identifier and the start of the quota control data of the defaults entry of the
+    /// sample volume, whose flags do not enable tracking.
+    const SAMPLE_DEFAULTS: [u8; 12] = [1, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0];
+
+    #[test]
+    fn test_iter_quotas_when_disabled() {
+        let volume = sample_volume().unwrap();
+
+        assert_eq!(volume.iter_quotas().unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_iter_quotas_when_tracking() {
+        let mut enabled = SAMPLE_DEFAULTS;
+        enabled[8] |= QUOTA_FLAG_TRACKING_ENABLED as u8;
+
+        let volume = patched_sample_volume("quota-tracking", &SAMPLE_DEFAULTS, &enabled).unwrap();
+
+        let quotas = volume
+            .iter_quotas()
+            .unwrap()
+            .collect::<Result<Vec<QuotaEntry>, Error>>()
+            .unwrap();
+
+        assert_eq!(quotas.len(), 1);
+        assert_eq!(quotas[0].owner_id, 0x100);
+        assert_eq!(quotas[0].sid.to_string(), "S-1-5-32-544");
+        assert_eq!(quotas[0].threshold, None);
+        assert_eq!(quotas[0].limit, None);
+    }
+
+    #[test]
+    fn test_iter_quotas_of_quota_volume() {
+        let volume = quota_volume().unwrap();
+
+        let quotas = volume
+            .iter_quotas()
+            .unwrap()
+            .collect::<Result<Vec<QuotaEntry>, Error>>()
+            .unwrap();
+
+        assert_eq!(quotas.len(), 2);
+        assert!(quotas
+            .iter()
+            .all(|quota| quota.sid.to_string().starts_with("S-1-")));
+
+        assert_eq!(quotas[1].owner_id, 0x101);
+        assert_eq!(quotas[1].sid.to_string(), "S-1-5-21-1-2-3-1000");
+        assert_eq!(quotas[1].bytes_used, 2000);
+        assert!(quotas[1].change_time.is_some());
+        assert_eq!(quotas[1].threshold, Some(1 << 20));
+        assert_eq!(quotas[1].limit, Some(2 << 20));
+        assert_eq!(quotas[1].exceeded_time, None);
+    }
+}
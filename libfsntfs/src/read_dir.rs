@@ -0,0 +1,191 @@
+//! Directory listings modeled after `std::fs::read_dir`.
+use crate::attribute::NameNamespace;
+use crate::error::Error;
+use crate::file_attributes::FileAttributeFlags;
+use crate::file_entry::FileEntry;
+use crate::index_entry::{IndexEntry, IterIndexEntries};
+use crate::metadata::FileEntryMetadata;
+use crate::volume::{mft_entry_index, FileReference, MftEntryIndex, ReferenceResolution, Volume};
+use std::fmt;
+
+/// The type of a directory entry, see `DirEntry::file_type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileType(FileAttributeFlags);
+
+impl FileType {
+    pub fn is_dir(&self) -> bool {
+        !self.is_symlink() && self.0.contains(FileAttributeFlags::HAS_FILE_NAME_INDEX)
+    }
+
+    pub fn is_file(&self) -> bool {
+        !self.is_symlink() && !self.is_dir()
+    }
+
+    /// Reparse points (symbolic links, junctions, etc.) are reported as symbolic links.
+    pub fn is_symlink(&self) -> bool {
+        self.0.contains(FileAttributeFlags::REPARSE_POINT)
+    }
+}
+
+/// An entry of a directory, see `FileEntry::read_dir`.
+///
+/// Names and types come from the directory index, the file entry itself is only opened by
+/// `metadata` and `open`.
+#[derive(Clone)]
+pub struct DirEntry<'a> {
+    volume: &'a Volume,
+    entry: IndexEntry,
+}
+
+impl<'a> fmt::Debug for DirEntry<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DirEntry")
+            .field("name", &self.entry.name)
+            .field("file_reference", &self.entry.file_reference)
+            .finish()
+    }
+}
+
+impl<'a> DirEntry<'a> {
+    pub fn file_name(&self) -> &str {
+        &self.entry.name
+    }
+
+    pub fn file_reference(&self) -> FileReference {
+        self.entry.file_reference
+    }
+
+    /// Determines the type from the flags stored in the directory index.
+    pub fn file_type(&self) -> FileType {
+        FileType(self.entry.file_attribute_flags)
+    }
+
+    /// Opens the file entry, failing with a not found error if it was deleted meanwhile.
+    pub fn open(&self) -> Result<FileEntry<'a>, Error> {
+        match self.volume.resolve_reference(self.entry.file_reference)? {
+            ReferenceResolution::Live(entry) => Ok(entry),
+            _ => Err(Error::not_found(&self.entry.name)),
+        }
+    }
+
+    /// Reads the metadata of the file entry.
+    pub fn metadata(&self) -> Result<FileEntryMetadata, Error> {
+        FileEntryMetadata::from_entry(&self.open()?)
+    }
+}
+
+/// Iterator over the entries of a directory, see `FileEntry::read_dir`.
+pub struct ReadDir<'a> {
+    volume: &'a Volume,
+    directory: MftEntryIndex,
+    entries: IterIndexEntries,
+}
+
+impl<'a> Iterator for ReadDir<'a> {
+    type Item = Result<DirEntry<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for entry in &mut self.entries {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => return Some(Err(e)),
+            };
+
+            // Skip remnants of deleted files, DOS names of files that are also listed by their
+            // long name, and the self-reference of the root directory.
+            if !entry.is_allocated
+                || entry.name_namespace == NameNamespace::Dos
+                || entry.file_reference.mft_entry_index() == self.directory
+            {
+                continue;
+            }
+
+            return Some(Ok(DirEntry {
+                volume: self.volume,
+                entry,
+            }));
+        }
+
+        None
+    }
+}
+
+impl<'a> FileEntry<'a> {
+    /// Iterates over the entries of the directory, without opening them.
+    ///
+    /// Like `iter_index_entries`, this only supports indexes whose attributes are stored in the
+    /// base MFT entry.
+    pub fn read_dir(&self) -> Result<ReadDir<'a>, Error> {
+        Ok(ReadDir {
+            volume: self.get_volume(),
+            directory: mft_entry_index(self.get_file_reference()?),
+            entries: self.iter_index_entries()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+    use crate::metadata::EntryMetadata;
+
+    #[test]
+    fn test_read_dir_does_not_open_children() {
+        // Without its MFT entry, `file-r-1.dat` can only be listed from the index of the root.
+        let volume = SampleImage::new()
+            .clear_mft_entry(27)
+            .open("read-dir-cleared-entry")
+            .unwrap();
+        let root = volume.get_root_directory().unwrap();
+
+        let entries = root
+            .read_dir()
+            .unwrap()
+            .map(|entry| entry.unwrap())
+            .collect::<Vec<_>>();
+
+        let entry = entries
+            .iter()
+            .find(|entry| entry.file_name() == "file-r-1.dat")
+            .unwrap();
+        assert!(entry.file_type().is_file());
+        assert!(entry.open().is_err());
+
+        let entries = entries
+            .iter()
+            .map(|entry| (entry.file_name().to_string(), entry.file_type()))
+            .collect::<Vec<_>>();
+
+        let file_type = |name: &str| entries.iter().find(|(n, _)| n == name).unwrap().1;
+
+        assert!(file_type("file-r-1.dat").is_file());
+        assert!(file_type("dir-r-4").is_dir());
+        assert!(file_type("$Extend").is_dir());
+        assert!(entries
+            .iter()
+            .any(|(name, _)| name == "System Volume Information"));
+        assert!(!entries
+            .iter()
+            .any(|(name, _)| name == "SYSTEM~1" || name == "."));
+    }
+
+    #[test]
+    fn test_dir_entry_metadata() {
+        let volume = sample_volume().unwrap();
+        let root = volume.get_root_directory().unwrap();
+
+        let entry = root
+            .read_dir()
+            .unwrap()
+            .map(|entry| entry.unwrap())
+            .find(|entry| entry.file_name() == "file-r-1.dat")
+            .unwrap();
+
+        let metadata = entry.metadata().unwrap();
+
+        assert_eq!(metadata.file_reference, entry.file_reference());
+        assert!(metadata.is_file().unwrap());
+        assert_eq!(entry.open().unwrap().get_name().unwrap(), "file-r-1.dat");
+    }
+}
@@ -0,0 +1,187 @@
+//! Enumeration of the reparse points of a volume, from the `$R` index of `$Extend\$Reparse`.
+use crate::attribute::{AttributeType, ReparsePointTag};
+use crate::error::Error;
+use crate::file_attributes::FileAttributeFlags;
+use crate::file_entry::FileEntry;
+use crate::index_entry::read_index_nodes;
+use crate::mft::{read_u16, read_u32, read_u64, MftRecord};
+use crate::name_match::MatchMode;
+use crate::volume::{mft_entry_index, FileReference, Volume};
+use std::convert::TryFrom;
+
+const REPARSE_PATH: &str = "\\$Extend\\$Reparse";
+const REPARSE_INDEX_NAME: &str = "$R";
+
+/// Offset of the key in an index entry.
+const INDEX_ENTRY_KEY_OFFSET: usize = 16;
+/// Size of a key of `$R`, a reparse tag followed by a file reference.
+const REPARSE_KEY_SIZE: usize = 12;
+
+/// Splits the key of an index entry of `$R` into the reparse tag and the file reference.
+fn parse_index_entry(entry: &[u8]) -> Option<(u32, u64)> {
+    if (read_u16(entry, 10)? as usize) < REPARSE_KEY_SIZE {
+        return None;
+    }
+
+    Some((
+        read_u32(entry, INDEX_ENTRY_KEY_OFFSET)?,
+        read_u64(entry, INDEX_ENTRY_KEY_OFFSET + 4)?,
+    ))
+}
+
+/// Reads the reparse tag of an entry, `None` if it is not a reparse point.
+fn reparse_point_tag(entry: &FileEntry) -> Result<Option<ReparsePointTag>, Error> {
+    if !entry
+        .get_file_attributes()?
+        .contains(FileAttributeFlags::REPARSE_POINT)
+    {
+        return Ok(None);
+    }
+
+    match entry.get_attribute_by_name(AttributeType::ReparsePoint, "")? {
+        Some(attribute) => attribute.get_reparse_point_tag().map(Some),
+        None => Ok(None),
+    }
+}
+
+impl Volume {
+    /// Iterates the reparse points of the volume, e.g. symbolic links, junctions and cloud
+    /// placeholders, with their tags.
+    ///
+    /// These are read from the `$R` index of `$Extend\$Reparse`, which is much faster than
+    /// opening every entry. Volumes without the index are scanned entry by entry instead, see
+    /// `iter_reparse_points_by_scan`. Tags unknown to `ReparsePointTag` are yielded as errors.
+    pub fn iter_reparse_points(
+        &self,
+    ) -> Result<impl Iterator<Item = Result<(FileReference, ReparsePointTag), Error>>, Error> {
+        let reparse = match self.get_file_entry_by_path(REPARSE_PATH, MatchMode::Exact) {
+            Ok(reparse) => reparse,
+            Err(e) if e.is_not_found() => return self.iter_reparse_points_by_scan(),
+            Err(e) => return Err(e),
+        };
+
+        let record = MftRecord::read(self, mft_entry_index(reparse.get_file_reference()?))?;
+        let mut reparse_points = Vec::new();
+
+        for node in read_index_nodes(self, &record, REPARSE_INDEX_NAME)? {
+            let node = node?;
+            let (bounds, _) = node.allocated_entries();
+
+            for bounds in bounds {
+                let bounds = bounds?;
+
+                let (tag, reference) =
+                    parse_index_entry(&node.data()[bounds.clone()]).ok_or_else(|| {
+                        Error::Other(format!("$R entry at offset {} is malformed", bounds.start))
+                    })?;
+
+                reparse_points.push(
+                    ReparsePointTag::try_from(tag).map(|tag| (FileReference(reference), tag)),
+                );
+            }
+        }
+
+        Ok(reparse_points.into_iter())
+    }
+
+    /// Iterates the reparse points of the volume like `iter_reparse_points`, but by opening
+    /// every allocated entry, regardless of `$Extend\$Reparse`.
+    pub fn iter_reparse_points_by_scan(
+        &self,
+    ) -> Result<std::vec::IntoIter<Result<(FileReference, ReparsePointTag), Error>>, Error> {
+        let mut reparse_points = Vec::new();
+
+        for entry in self.iter_entries()? {
+            let reparse_point = entry.and_then(|entry| {
+                if !entry.is_allocated()? {
+                    return Ok(None);
+                }
+
+                match reparse_point_tag(&entry)? {
+                    Some(tag) => Ok(Some((FileReference(entry.get_file_reference()?), tag))),
+                    None => Ok(None),
+                }
+            });
+
+            match reparse_point {
+                Ok(Some(reparse_point)) => reparse_points.push(Ok(reparse_point)),
+                Ok(None) => {}
+                Err(e) => reparse_points.push(Err(e)),
+            }
+        }
+
+        Ok(reparse_points.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    fn walked_reparse_points(volume: &Volume) -> usize {
+        volume
+            .walk()
+            .unwrap()
+            .map(|entry| entry.unwrap().1)
+            .filter(|entry| reparse_point_tag(entry).unwrap().is_some())
+            .count()
+    }
+
+    #[test]
+    fn test_iter_reparse_points_matches_walk() {
+        let volume = sample_volume().unwrap();
+
+        let indexed = volume
+            .iter_reparse_points()
+            .unwrap()
+            .collect::<Result<Vec<_>, Error>>()
+            .unwrap();
+
+        assert_eq!(indexed.len(), walked_reparse_points(&volume));
+        assert_eq!(
+            volume.iter_reparse_points_by_scan().unwrap().count(),
+            indexed.len()
+        );
+    }
+
+    #[test]
+    fn test_parse_index_entry() {
+        let mut entry = vec![0; 32];
+        entry[8..10].copy_from_slice(&32_u16.to_le_bytes());
+        entry[10..12].copy_from_slice(&(REPARSE_KEY_SIZE as u16).to_le_bytes());
+        entry[16..20].copy_from_slice(&(ReparsePointTag::MountPoint as u32).to_le_bytes());
+        entry[20..28].copy_from_slice(&(64_u64 | 2 << 48).to_le_bytes());
+
+        assert_eq!(
+            parse_index_entry(&entry),
+            Some((ReparsePointTag::MountPoint as u32, 64 | 2 << 48))
+        );
+
+        entry[10..12].copy_from_slice(&0_u16.to_le_bytes());
+        assert_eq!(parse_index_entry(&entry), None);
+    }
+
+    #[test]
+    fn test_iter_reparse_points_of_junction_volume() {
+        let volume = junction_volume().unwrap();
+        let junction = volume
+            .get_file_entry_by_path("\\dir-r-4", MatchMode::Exact)
+            .unwrap();
+
+        let indexed = volume
+            .iter_reparse_points()
+            .unwrap()
+            .collect::<Result<Vec<_>, Error>>()
+            .unwrap();
+
+        assert_eq!(
+            indexed,
+            vec![(
+                FileReference(junction.get_file_reference().unwrap()),
+                ReparsePointTag::MountPoint
+            )]
+        );
+        assert_eq!(indexed.len(), walked_reparse_points(&volume));
+    }
+}
@@ -0,0 +1,153 @@
+//! Resumable scanning of the MFT in batches.
+use crate::error::Error;
+use crate::ffi_error::ErrorScratch;
+use crate::metadata::FileEntryMetadata;
+use crate::volume::{MftEntryIndex, Volume};
+
+/// Reads the metadata of allocated MFT entries in batches, see `Volume::mft_scanner`.
+///
+/// The scan can be checkpointed by persisting `position` and resumed on a new scanner with
+/// `seek_to`.
+pub struct MftScanner<'a> {
+    volume: &'a Volume,
+    number_of_file_entries: MftEntryIndex,
+    position: MftEntryIndex,
+    scratch: ErrorScratch,
+}
+
+impl Volume {
+    /// Creates a scanner positioned at the first MFT entry.
+    pub fn mft_scanner(&self) -> Result<MftScanner, Error> {
+        Ok(MftScanner {
+            volume: self,
+            number_of_file_entries: self.get_number_of_file_entries()? as MftEntryIndex,
+            position: 0,
+            scratch: ErrorScratch::new(),
+        })
+    }
+}
+
+impl<'a> MftScanner<'a> {
+    /// Reads the metadata of up to `n` allocated entries, skipping unallocated ones. Returns an
+    /// empty batch once the end of the MFT has been reached.
+    ///
+    /// If an entry cannot be read, the batch ends before it and the next call returns the
+    /// error. The position is then left at the failing entry, so the scan can continue with
+    /// `seek_to(position() + 1)`.
+    pub fn next_batch(&mut self, n: usize) -> Result<Vec<FileEntryMetadata>, Error> {
+        let mut batch = Vec::with_capacity(n.min(1024));
+
+        while batch.len() < n && self.position < self.number_of_file_entries {
+            match self.read_entry(self.position) {
+                Ok(Some(metadata)) => batch.push(metadata),
+                Ok(None) => {}
+                Err(e) if batch.is_empty() => return Err(e),
+                Err(_) => break,
+            }
+
+            self.position += 1;
+        }
+
+        Ok(batch)
+    }
+
+    fn read_entry(&mut self, idx: MftEntryIndex) -> Result<Option<FileEntryMetadata>, Error> {
+        let entry = self
+            .volume
+            .get_file_entry_by_mft_idx_with(idx, &mut self.scratch)?;
+
+        if !entry.is_allocated()? {
+            return Ok(None);
+        }
+
+        Ok(Some(FileEntryMetadata::from_entry(&entry)?))
+    }
+
+    /// Index of the next MFT entry to be read.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Continues the scan at the MFT entry at `idx`.
+    pub fn seek_to(&mut self, idx: MftEntryIndex) {
+        self.position = idx;
+    }
+
+    /// Determines if all MFT entries have been read.
+    pub fn is_done(&self) -> bool {
+        self.position >= self.number_of_file_entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    fn full_scan(volume: &Volume) -> Vec<FileEntryMetadata> {
+        volume
+            .iter_entries()
+            .unwrap()
+            .map(|entry| entry.unwrap())
+            .filter(|entry| entry.is_allocated().unwrap())
+            .map(|entry| FileEntryMetadata::from_entry(&entry).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_batches_equal_full_scan() {
+        let volume = sample_volume().unwrap();
+        let mut scanner = volume.mft_scanner().unwrap();
+        let mut scanned = Vec::new();
+
+        loop {
+            let batch = scanner.next_batch(10).unwrap();
+
+            if batch.is_empty() {
+                break;
+            }
+
+            assert!(batch.len() <= 10);
+            scanned.extend(batch);
+        }
+
+        assert!(scanner.is_done());
+        assert_eq!(scanned, full_scan(&volume));
+    }
+
+    #[test]
+    fn test_resume_from_position() {
+        let volume = sample_volume().unwrap();
+
+        let mut scanner = volume.mft_scanner().unwrap();
+        let mut scanned = scanner.next_batch(10).unwrap();
+        let checkpoint = scanner.position();
+        drop(scanner);
+
+        let mut resumed = volume.mft_scanner().unwrap();
+        resumed.seek_to(checkpoint);
+
+        loop {
+            let batch = resumed.next_batch(10).unwrap();
+
+            if batch.is_empty() {
+                break;
+            }
+
+            scanned.extend(batch);
+        }
+
+        assert_eq!(scanned, full_scan(&volume));
+    }
+
+    #[test]
+    fn test_seek_past_end() {
+        let volume = sample_volume().unwrap();
+        let mut scanner = volume.mft_scanner().unwrap();
+
+        scanner.seek_to(u64::MAX);
+
+        assert!(scanner.is_done());
+        assert!(scanner.next_batch(10).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,236 @@
+//! Parsing of self-relative Windows security descriptors, as stored in `$Secure:$SDS` and
+//! `$SECURITY_DESCRIPTOR` attributes.
+use crate::error::Error;
+use crate::mft::{read_u16, read_u32, read_u64};
+use crate::name_match::MatchMode;
+use crate::volume::Volume;
+use std::fmt::{self, Display, Formatter};
+
+const SDS_BLOCK_SIZE: usize = 0x40000;
+const SDS_ENTRY_HEADER_SIZE: usize = 20;
+const SECURITY_DESCRIPTOR_HEADER_SIZE: usize = 20;
+
+/// A security identifier, e.g. `S-1-5-18`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Sid {
+    pub revision: u8,
+    pub identifier_authority: u64,
+    pub sub_authorities: Vec<u32>,
+}
+
+impl Sid {
+    pub(crate) fn from_bytes(data: &[u8], offset: usize) -> Result<Self, Error> {
+        let malformed = || Error::Other(format!("SID at {} is malformed", offset));
+
+        let header = data.get(offset..offset + 8).ok_or_else(malformed)?;
+        let count = header[1] as usize;
+
+        let identifier_authority = header[2..8]
+            .iter()
+            .fold(0_u64, |acc, b| (acc << 8) | u64::from(*b));
+
+        let sub_authorities = (0..count)
+            .map(|i| read_u32(data, offset + 8 + i * 4).ok_or_else(malformed))
+            .collect::<Result<Vec<u32>, Error>>()?;
+
+        Ok(Sid {
+            revision: header[0],
+            identifier_authority,
+            sub_authorities,
+        })
+    }
+}
+
+impl Display for Sid {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(f, "S-{}-{}", self.revision, self.identifier_authority)?;
+
+        for sub_authority in &self.sub_authorities {
+            write!(f, "-{}", sub_authority)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A parsed self-relative security descriptor.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SecurityDescriptor {
+    pub revision: u8,
+    pub control: u16,
+    pub owner: Option<Sid>,
+    pub group: Option<Sid>,
+    /// The complete descriptor, including the access control lists.
+    pub data: Vec<u8>,
+}
+
+impl SecurityDescriptor {
+    pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        if data.len() < SECURITY_DESCRIPTOR_HEADER_SIZE {
+            return Err(Error::Other("Security descriptor is truncated".to_owned()));
+        }
+
+        let sid_at = |offset: u32| -> Result<Option<Sid>, Error> {
+            match offset {
+                0 => Ok(None),
+                offset => Sid::from_bytes(data, offset as usize).map(Some),
+            }
+        };
+
+        let truncated = || Error::Other("Security descriptor is truncated".to_owned());
+
+        Ok(SecurityDescriptor {
+            revision: data[0],
+            control: read_u16(data, 2).ok_or_else(truncated)?,
+            owner: sid_at(read_u32(data, 4).ok_or_else(truncated)?)?,
+            group: sid_at(read_u32(data, 8).ok_or_else(truncated)?)?,
+            data: data.to_vec(),
+        })
+    }
+}
+
+/// Iterates the entries of a `$Secure:$SDS` stream, skipping the mirror copy of each block.
+pub struct IterSecurityDescriptors {
+    data: Vec<u8>,
+    offset: usize,
+}
+
+impl IterSecurityDescriptors {
+    pub(crate) fn new(data: Vec<u8>) -> Self {
+        IterSecurityDescriptors { data, offset: 0 }
+    }
+
+    /// Moves to the start of the next primary (even) block.
+    fn skip_to_next_block(&mut self) {
+        let block = self.offset / SDS_BLOCK_SIZE + 1;
+        self.offset = (block + block % 2) * SDS_BLOCK_SIZE;
+    }
+}
+
+impl Iterator for IterSecurityDescriptors {
+    type Item = Result<(u32, SecurityDescriptor), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.offset + SDS_ENTRY_HEADER_SIZE <= self.data.len() {
+            let header = (
+                read_u32(&self.data, self.offset + 4),
+                read_u64(&self.data, self.offset + 8),
+                read_u32(&self.data, self.offset + 16),
+            );
+
+            let (security_id, entry_offset, entry_size) = match header {
+                (Some(security_id), Some(entry_offset), Some(entry_size)) => {
+                    (security_id, entry_offset, entry_size as usize)
+                }
+                _ => break,
+            };
+
+            // Entries record their own offset, anything else is padding at the end of a block.
+            if entry_offset != self.offset as u64 || entry_size < SDS_ENTRY_HEADER_SIZE {
+                self.skip_to_next_block();
+                continue;
+            }
+
+            let descriptor = match self
+                .data
+                .get(self.offset + SDS_ENTRY_HEADER_SIZE..self.offset + entry_size)
+            {
+                Some(descriptor) => SecurityDescriptor::from_bytes(descriptor),
+                None => {
+                    let offset = self.offset;
+                    self.offset = self.data.len();
+
+                    return Some(Err(Error::Other(format!(
+                        "$SDS entry at {} is truncated",
+                        offset
+                    ))));
+                }
+            };
+
+            // Entries are aligned to 16 bytes.
+            self.offset = (self.offset + entry_size + 15) & !15;
+
+            return Some(descriptor.map(|descriptor| (security_id, descriptor)));
+        }
+
+        None
+    }
+}
+
+impl Volume {
+    /// Iterates all security descriptors stored in `$Secure:$SDS`, with their security
+    /// identifiers.
+    ///
+    /// libfsntfs does not expose index attributes, so entries are located by scanning `$SDS`
+    /// rather than through the `$SII` index.
+    pub fn iter_security_descriptors(&self) -> Result<IterSecurityDescriptors, Error> {
+        let secure = self.get_file_entry_by_path("\\$Secure", MatchMode::Exact)?;
+        let sds = secure.get_alternate_data_stream_by_name("$SDS")?;
+
+        let size = sds.get_size()? as usize;
+        let mut data = vec![0; size];
+        let mut read_count = 0;
+
+        while read_count < size {
+            match sds.read_at(read_count as u64, &mut data[read_count..])? {
+                0 => break,
+                count => read_count += count,
+            }
+        }
+
+        data.truncate(read_count);
+
+        Ok(IterSecurityDescriptors::new(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    #[test]
+    fn test_sid_display() {
+        let data = [1, 1, 0, 0, 0, 0, 0, 5, 18, 0, 0, 0];
+
+        assert_eq!(Sid::from_bytes(&data, 0).unwrap().to_string(), "S-1-5-18");
+    }
+
+    #[test]
+    fn test_malformed_security_descriptors_return_errors() {
+        assert!(SecurityDescriptor::from_bytes(&[]).is_err());
+
+        // Owner offset points past the end of the descriptor.
+        let mut data = [0; SECURITY_DESCRIPTOR_HEADER_SIZE];
+        data[4] = 0xff;
+        assert!(SecurityDescriptor::from_bytes(&data).is_err());
+
+        let mut iter = IterSecurityDescriptors::new(vec![0xff; SDS_ENTRY_HEADER_SIZE - 1]);
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_iter_security_descriptors() {
+        let volume = sample_volume().unwrap();
+
+        let descriptors: Vec<(u32, SecurityDescriptor)> = volume
+            .iter_security_descriptors()
+            .unwrap()
+            .map(|d| d.unwrap())
+            .collect();
+
+        let ids: Vec<u32> = descriptors.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, (256..=266).collect::<Vec<u32>>());
+
+        for idx in &[1, 9, 27, 28] {
+            let entry = volume.get_file_entry_by_mft_idx(*idx).unwrap();
+            let data = entry.get_security_descriptor_data().unwrap().unwrap();
+
+            assert!(
+                descriptors.iter().any(|(_, d)| d.data == data),
+                "descriptor of entry {} was not enumerated",
+                idx
+            );
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! Reading of sparse data streams without asking libfsntfs for the holes.
+use crate::error::Error;
+use crate::extent::Extent;
+use crate::file_entry::FileEntry;
+use std::cmp::min;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::ops::Range;
+
+/// Reads the default data stream of a file entry, filling sparse ranges with zeros in Rust and
+/// only reading the ranges backed by clusters through libfsntfs. See `FileEntry::sparse_reader`.
+pub struct SparseReader<'a> {
+    file_entry: &'a FileEntry<'a>,
+    size: u64,
+    sparse_ranges: Vec<Range<u64>>,
+    position: u64,
+}
+
+/// Determines where the segment starting at `position` ends, stopping at `end`, and whether it
+/// is a hole.
+fn next_segment(sparse_ranges: &[Range<u64>], position: u64, end: u64) -> (u64, bool) {
+    match sparse_ranges.iter().find(|range| range.end > position) {
+        Some(range) if range.start <= position => (min(range.end, end), true),
+        Some(range) => (min(range.start, end), false),
+        None => (end, false),
+    }
+}
+
+impl<'a> FileEntry<'a> {
+    /// Retrieves the ranges of the default data stream that are sparse, i.e. not backed by
+    /// clusters and read as zeros, relative to the start of the stream.
+    ///
+    /// Compressed streams have no sparse ranges, since their sparse runs are part of compression
+    /// units rather than holes.
+    pub fn sparse_ranges(&self) -> Result<Vec<Range<u64>>, Error> {
+        let extents = self.get_extents()?;
+
+        if extents.iter().any(Extent::is_compressed) {
+            return Ok(Vec::new());
+        }
+
+        let size = self.get_size()?;
+        let mut ranges: Vec<Range<u64>> = Vec::new();
+        let mut start = 0_u64;
+
+        for extent in &extents {
+            let end = start.saturating_add(extent.size);
+
+            if extent.is_sparse() && start < size {
+                let end = min(end, size);
+
+                match ranges.last_mut() {
+                    Some(last) if last.end == start => last.end = end,
+                    _ => ranges.push(start..end),
+                }
+            }
+
+            start = end;
+        }
+
+        Ok(ranges)
+    }
+
+    /// Creates a reader of the default data stream that skips libfsntfs for sparse ranges, which
+    /// makes reading large, mostly sparse files much cheaper.
+    pub fn sparse_reader(&self) -> Result<SparseReader, Error> {
+        Ok(SparseReader {
+            file_entry: self,
+            size: self.get_size()?,
+            sparse_ranges: self.sparse_ranges()?,
+            position: 0,
+        })
+    }
+}
+
+impl<'a> SparseReader<'a> {
+    /// Reads data at a specific offset, without moving the current offset of the reader.
+    pub fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<usize, Error> {
+        if offset >= self.size {
+            return Ok(0);
+        }
+
+        let end = offset + min(buf.len() as u64, self.size - offset);
+        let mut position = offset;
+
+        while position < end {
+            let (segment_end, is_hole) = next_segment(&self.sparse_ranges, position, end);
+            let out = &mut buf[(position - offset) as usize..(segment_end - offset) as usize];
+
+            if is_hole {
+                out.iter_mut().for_each(|b| *b = 0);
+            } else {
+                let read_count = self.file_entry.read_at(position, out)?;
+
+                if read_count < out.len() {
+                    return Ok((position - offset) as usize + read_count);
+                }
+            }
+
+            position = segment_end;
+        }
+
+        Ok((end - offset) as usize)
+    }
+
+    pub fn sparse_ranges(&self) -> &[Range<u64>] {
+        &self.sparse_ranges
+    }
+}
+
+impl<'a> Read for SparseReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, io::Error> {
+        let read_count = self
+            .read_at(self.position, buf)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("{}", e)))?;
+        self.position += read_count as u64;
+
+        Ok(read_count)
+    }
+}
+
+impl<'a> Seek for SparseReader<'a> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, io::Error> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(offset) => (0, offset as i128),
+            SeekFrom::End(offset) => (self.size, offset as i128),
+            SeekFrom::Current(offset) => (self.position, offset as i128),
+        };
+
+        let position = base as i128 + offset;
+
+        if position < 0 || position > u64::max_value() as i128 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            ));
+        }
+
+        self.position = position as u64;
+
+        Ok(self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    const CHUNK_SIZE: usize = 4096;
+
+    #[test]
+    fn test_next_segment() {
+        let ranges = vec![100..200, 300..400];
+
+        assert_eq!(next_segment(&ranges, 0, 1000), (100, false));
+        assert_eq!(next_segment(&ranges, 100, 1000), (200, true));
+        assert_eq!(next_segment(&ranges, 150, 160), (160, true));
+        assert_eq!(next_segment(&ranges, 200, 1000), (300, false));
+        assert_eq!(next_segment(&ranges, 400, 1000), (1000, false));
+        assert_eq!(next_segment(&[], 0, 10), (10, false));
+    }
+
+    #[test]
+    fn test_sparse_reader_matches_read_all_without_holes() {
+        let volume = sample_volume().unwrap();
+        let mut entry = file_entry(&volume).unwrap();
+        let expected = entry.read_all().unwrap();
+
+        let mut reader = entry.sparse_reader().unwrap();
+        assert!(reader.sparse_ranges().is_empty());
+
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).unwrap();
+
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn test_sparse_reader_skips_holes() {
+        let (image, expected) = sparse_image();
+        let (volume, bytes_read) = image.open_counting_reads().unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(33).unwrap();
+
+        let mut reader = entry.sparse_reader().unwrap();
+        assert_eq!(reader.sparse_ranges(), &[1024..64512]);
+
+        bytes_read.set(0);
+        let mut hole = vec![0xff; CHUNK_SIZE];
+        assert_eq!(reader.read_at(4096, &mut hole).unwrap(), CHUNK_SIZE);
+        assert!(hole.iter().all(|b| *b == 0));
+        assert_eq!(bytes_read.get(), 0);
+
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).unwrap();
+
+        assert_eq!(data, expected);
+    }
+}
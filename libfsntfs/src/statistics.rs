@@ -0,0 +1,222 @@
+//! Counts of the kinds and sizes of the file entries of a volume, for triage.
+use crate::error::Error;
+use crate::file_attributes::FileAttributeFlags;
+use crate::file_entry::FileEntry;
+use crate::volume::Volume;
+
+/// Exclusive upper bounds of the buckets of `VolumeStatistics::size_histogram`, in bytes.
+/// Sizes of at least the last bound fall in a final, unbounded bucket.
+pub const SIZE_BUCKET_BOUNDS: [u64; 7] =
+    [1, 4 << 10, 64 << 10, 1 << 20, 16 << 20, 256 << 20, 4 << 30];
+
+/// A bucket of `VolumeStatistics::size_histogram`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SizeBucket {
+    /// Exclusive upper bound of the sizes in the bucket, `None` for the last bucket.
+    pub upper_bound: Option<u64>,
+    pub count: usize,
+}
+
+/// The kinds and sizes of the file entries of a volume, see `Volume::statistics`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VolumeStatistics {
+    /// Allocated entries without a directory index.
+    pub files: usize,
+    /// Allocated entries with a directory index.
+    pub directories: usize,
+    /// Allocated entries that are reparse points, also counted as files or directories.
+    pub reparse_points: usize,
+    /// Files with at least one alternate data stream.
+    pub files_with_alternate_data_streams: usize,
+    /// Unallocated entries that still hold attributes, see `Volume::iter_deleted_entries`.
+    pub deleted_entries: usize,
+    /// Entries that could not be read, which are not counted otherwise.
+    pub unreadable_entries: usize,
+    /// Bytes of the clusters allocated to the default data streams of files. Resident data and
+    /// sparse extents take no clusters.
+    pub allocated_bytes: u64,
+    /// Number of files per size of their default data stream, one bucket per bound in
+    /// `SIZE_BUCKET_BOUNDS` followed by an unbounded one.
+    pub size_histogram: Vec<SizeBucket>,
+}
+
+impl Default for VolumeStatistics {
+    fn default() -> Self {
+        VolumeStatistics {
+            files: 0,
+            directories: 0,
+            reparse_points: 0,
+            files_with_alternate_data_streams: 0,
+            deleted_entries: 0,
+            unreadable_entries: 0,
+            allocated_bytes: 0,
+            size_histogram: SIZE_BUCKET_BOUNDS
+                .iter()
+                .map(|bound| Some(*bound))
+                .chain(Some(None))
+                .map(|upper_bound| SizeBucket {
+                    upper_bound,
+                    count: 0,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl VolumeStatistics {
+    /// Number of allocated entries, i.e. files and directories.
+    pub fn allocated_entries(&self) -> usize {
+        self.files + self.directories
+    }
+
+    fn add_file_size(&mut self, size: u64) {
+        let bucket = SIZE_BUCKET_BOUNDS
+            .iter()
+            .position(|bound| size < *bound)
+            .unwrap_or(SIZE_BUCKET_BOUNDS.len());
+
+        self.size_histogram[bucket].count += 1;
+    }
+
+    fn add(&mut self, entry: ScannedEntry) {
+        match entry {
+            ScannedEntry::Unused => {}
+            ScannedEntry::Deleted => self.deleted_entries += 1,
+            ScannedEntry::Directory { is_reparse_point } => {
+                self.directories += 1;
+                self.reparse_points += is_reparse_point as usize;
+            }
+            ScannedEntry::File {
+                size,
+                allocated_bytes,
+                has_alternate_data_streams,
+                is_reparse_point,
+            } => {
+                self.files += 1;
+                self.reparse_points += is_reparse_point as usize;
+                self.files_with_alternate_data_streams += has_alternate_data_streams as usize;
+                self.allocated_bytes += allocated_bytes;
+                self.add_file_size(size);
+            }
+        }
+    }
+}
+
+/// What `Volume::statistics` counts of a single entry, read in full before anything is counted.
+enum ScannedEntry {
+    /// An unallocated entry without attributes, which was never used or was wiped.
+    Unused,
+    Deleted,
+    Directory {
+        is_reparse_point: bool,
+    },
+    File {
+        size: u64,
+        allocated_bytes: u64,
+        has_alternate_data_streams: bool,
+        is_reparse_point: bool,
+    },
+}
+
+impl ScannedEntry {
+    fn scan(entry: &FileEntry) -> Result<Self, Error> {
+        if !entry.is_allocated()? {
+            return if entry.get_number_of_attributes()? > 0 {
+                Ok(ScannedEntry::Deleted)
+            } else {
+                Ok(ScannedEntry::Unused)
+            };
+        }
+
+        let is_reparse_point = entry
+            .get_file_attributes()?
+            .contains(FileAttributeFlags::REPARSE_POINT);
+
+        if entry.has_directory_entries_index()? {
+            return Ok(ScannedEntry::Directory { is_reparse_point });
+        }
+
+        Ok(ScannedEntry::File {
+            size: entry.get_size()?,
+            allocated_bytes: entry
+                .get_extents()?
+                .iter()
+                .filter(|extent| !extent.is_sparse())
+                .map(|extent| extent.size)
+                .sum(),
+            has_alternate_data_streams: entry.get_number_of_alternate_data_streams()? > 0,
+            is_reparse_point,
+        })
+    }
+}
+
+impl Volume {
+    /// Counts the kinds and sizes of all file entries with a single pass over the MFT.
+    ///
+    /// Entries that cannot be read are counted in `unreadable_entries` instead of failing the
+    /// whole scan.
+    pub fn statistics(&self) -> Result<VolumeStatistics, Error> {
+        let mut statistics = VolumeStatistics::default();
+
+        for entry in self.iter_entries()? {
+            match entry.and_then(|entry| ScannedEntry::scan(&entry)) {
+                Ok(scanned) => statistics.add(scanned),
+                Err(_) => statistics.unreadable_entries += 1,
+            }
+        }
+
+        Ok(statistics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    #[test]
+    fn test_statistics_of_sample_volume() {
+        let volume = sample_volume().unwrap();
+        let statistics = volume.statistics().unwrap();
+
+        let allocated_entries = volume
+            .iter_entries()
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.is_allocated().unwrap())
+            .count();
+
+        assert_eq!(statistics.unreadable_entries, 0);
+        assert_eq!(statistics.files + statistics.directories, allocated_entries);
+        assert_eq!(
+            statistics
+                .size_histogram
+                .iter()
+                .map(|b| b.count)
+                .sum::<usize>(),
+            statistics.files
+        );
+        assert_eq!(
+            statistics.deleted_entries,
+            volume.iter_deleted_entries().unwrap().count()
+        );
+        assert!(statistics.files_with_alternate_data_streams >= 1);
+    }
+
+    #[test]
+    fn test_size_histogram_buckets() {
+        let mut statistics = VolumeStatistics::default();
+
+        statistics.add_file_size(0);
+        statistics.add_file_size(4095);
+        statistics.add_file_size(4096);
+        statistics.add_file_size(u64::max_value());
+
+        let counts: Vec<usize> = statistics.size_histogram.iter().map(|b| b.count).collect();
+
+        assert_eq!(counts, vec![1, 1, 1, 0, 0, 0, 0, 1]);
+        assert_eq!(statistics.size_histogram.last().unwrap().upper_bound, None);
+    }
+}
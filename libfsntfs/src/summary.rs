@@ -0,0 +1,72 @@
+//! An fsstat-style overview of a volume.
+use crate::error::Error;
+use crate::name_match::MatchMode;
+use crate::usn_journal::USN_JOURNAL_PATH;
+use crate::volume::{SerialNumber, Volume};
+
+const QUOTA_PATH: &str = "\\$Extend\\$Quota";
+const OBJECT_ID_PATH: &str = "\\$Extend\\$ObjId";
+
+/// The header information of a volume, see `Volume::summary`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VolumeSummary {
+    pub label: String,
+    pub serial_number: SerialNumber,
+    /// The NTFS `(major, minor)` version.
+    pub version: (u8, u8),
+    pub cluster_size: usize,
+    pub sector_size: u16,
+    /// Size of the volume in bytes.
+    pub total_size: u64,
+    pub number_of_file_entries: usize,
+    pub has_usn_journal: bool,
+    pub has_quota: bool,
+    pub has_object_ids: bool,
+}
+
+impl Volume {
+    /// Collects the header information of the volume in one call.
+    pub fn summary(&self) -> Result<VolumeSummary, Error> {
+        Ok(VolumeSummary {
+            label: self.get_name()?,
+            serial_number: self.get_serial_number()?,
+            version: self.get_version()?,
+            cluster_size: self.get_cluster_block_size()?,
+            sector_size: self.get_bytes_per_sector()?,
+            total_size: self.get_size()?,
+            number_of_file_entries: self.get_number_of_file_entries()?,
+            has_usn_journal: self.has_entry(USN_JOURNAL_PATH)?,
+            has_quota: self.has_entry(QUOTA_PATH)?,
+            has_object_ids: self.has_entry(OBJECT_ID_PATH)?,
+        })
+    }
+
+    fn has_entry(&self, path: &str) -> Result<bool, Error> {
+        match self.get_file_entry_by_path(path, MatchMode::Exact) {
+            Ok(_) => Ok(true),
+            Err(e) if e.is_not_found() => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::*;
+
+    #[test]
+    fn test_summary_of_sample_volume() {
+        let volume = sample_volume().unwrap();
+        let summary = volume.summary().unwrap();
+
+        assert_eq!(summary.version, (3, 1));
+        assert_eq!(summary.label, "KW-SRCH-1");
+        assert_eq!(summary.sector_size, 512);
+        assert_eq!(summary.total_size, volume.get_size().unwrap());
+        assert!(summary.number_of_file_entries > 0);
+        assert!(!summary.has_usn_journal);
+        assert!(summary.has_quota);
+        assert!(summary.has_object_ids);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::error::Error;
+use crate::file_entry::FileEntry;
+use crate::volume::{mft_entry_index, Volume};
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::path::Path;
+use tar::{Builder, EntryType, Header};
+
+fn archive_error(e: io::Error) -> Error {
+    Error::Other(format!("Failed to write tar archive: {}", e))
+}
+
+impl Volume {
+    /// Writes every file and directory of the volume into a tar archive.
+    ///
+    /// Files are stored with the contents of their default data stream and their modification
+    /// time. Reparse points with a substitute name (symbolic links, junctions) are stored as
+    /// symbolic links.
+    pub fn export_tar<W: Write>(&self, out: W) -> Result<(), Error> {
+        let mut builder = Builder::new(out);
+        let root = self.get_root_directory()?;
+
+        let mut visited = HashSet::new();
+        visited.insert(mft_entry_index(root.get_file_reference()?));
+
+        export_directory(&mut builder, &root, Path::new(""), &mut visited)?;
+
+        builder.finish().map_err(archive_error)
+    }
+}
+
+fn export_directory<W: Write>(
+    builder: &mut Builder<W>,
+    directory: &FileEntry,
+    path: &Path,
+    visited: &mut HashSet<u64>,
+) -> Result<(), Error> {
+    for sub_entry in directory.iter_sub_entries()? {
+        let mut sub_entry = sub_entry?;
+        let name = sub_entry.get_name()?;
+
+        // The root directory lists itself as `.`.
+        if name == "." {
+            continue;
+        }
+
+        let sub_path = path.join(&name);
+        let mut header = Header::new_gnu();
+
+        let mtime = sub_entry
+            .get_modification_time()?
+            .map(|time| time.timestamp().max(0) as u64)
+            .unwrap_or(0);
+        header.set_mtime(mtime);
+
+        if let Some(target) = sub_entry.get_reparse_point_substitute_name()? {
+            header.set_entry_type(EntryType::Symlink);
+            header.set_mode(0o777);
+            header.set_size(0);
+
+            builder
+                .append_link(&mut header, &sub_path, &target)
+                .map_err(archive_error)?;
+        } else if sub_entry.has_directory_entries_index()? {
+            header.set_entry_type(EntryType::Directory);
+            header.set_mode(0o755);
+            header.set_size(0);
+
+            builder
+                .append_data(&mut header, &sub_path, io::empty())
+                .map_err(archive_error)?;
+
+            if visited.insert(mft_entry_index(sub_entry.get_file_reference()?)) {
+                export_directory(builder, &sub_entry, &sub_path, visited)?;
+            }
+        } else {
+            header.set_entry_type(EntryType::Regular);
+            header.set_mode(0o644);
+            header.set_size(sub_entry.get_size()?);
+
+            builder
+                .append_data(&mut header, &sub_path, &mut sub_entry)
+                .map_err(archive_error)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    fn count_entries(directory: &FileEntry) -> usize {
+        directory
+            .iter_sub_entries()
+            .unwrap()
+            .map(|e| e.unwrap())
+            .filter(|e| e.get_name().unwrap() != ".")
+            .map(|e| {
+                let is_directory = e.get_reparse_point_substitute_name().unwrap().is_none()
+                    && e.has_directory_entries_index().unwrap();
+
+                if is_directory {
+                    1 + count_entries(&e)
+                } else {
+                    1
+                }
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_export_tar() {
+        let volume = sample_volume().unwrap();
+
+        let mut archive = Vec::new();
+        volume.export_tar(&mut archive).unwrap();
+
+        let expected = count_entries(&volume.get_root_directory().unwrap());
+        let count = tar::Archive::new(archive.as_slice())
+            .entries()
+            .unwrap()
+            .count();
+
+        assert!(expected > 0);
+        assert_eq!(count, expected);
+    }
+}
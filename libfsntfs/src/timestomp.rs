@@ -0,0 +1,214 @@
+//! Detection of tampered `$STANDARD_INFORMATION` timestamps.
+use crate::attribute::{AttributeType, AttributeWithInformation, FileName, StandardInformation};
+use crate::error::Error;
+use crate::file_entry::FileEntry;
+use chrono::{DateTime, Timelike, Utc};
+
+/// One of the four MACB timestamps kept in both `$STANDARD_INFORMATION` and `$FILE_NAME`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimestampKind {
+    Creation,
+    Modification,
+    Access,
+    EntryModification,
+}
+
+const TIMESTAMP_KINDS: &[TimestampKind] = &[
+    TimestampKind::Creation,
+    TimestampKind::Modification,
+    TimestampKind::Access,
+    TimestampKind::EntryModification,
+];
+
+/// The suspicious `$STANDARD_INFORMATION` timestamps found by `FileEntry::detect_timestomp`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TimestompIndicators {
+    /// Timestamps that are earlier than the same timestamp of a `$FILE_NAME` attribute, which
+    /// tools that only rewrite `$STANDARD_INFORMATION` tend to leave behind.
+    pub predates_file_name: Vec<TimestampKind>,
+    /// Timestamps without a sub-second part, as set by tools that work in whole seconds.
+    pub zeroed_sub_second: Vec<TimestampKind>,
+}
+
+fn si_timestamp(si: &StandardInformation, kind: TimestampKind) -> Option<DateTime<Utc>> {
+    match kind {
+        TimestampKind::Creation => si.creation_time,
+        TimestampKind::Modification => si.modification_time,
+        TimestampKind::Access => si.access_time,
+        TimestampKind::EntryModification => si.entry_modification_time,
+    }
+}
+
+fn fn_timestamp(file_name: &FileName, kind: TimestampKind) -> Option<DateTime<Utc>> {
+    match kind {
+        TimestampKind::Creation => file_name.creation_time,
+        TimestampKind::Modification => file_name.modification_time,
+        TimestampKind::Access => file_name.access_time,
+        TimestampKind::EntryModification => file_name.entry_modification_time,
+    }
+}
+
+impl TimestompIndicators {
+    /// Compares the timestamps of `si` to those of `file_names`, returns `None` when nothing is
+    /// suspicious. Unset timestamps are never flagged.
+    pub fn compare(si: &StandardInformation, file_names: &[FileName]) -> Option<Self> {
+        let mut indicators = TimestompIndicators::default();
+
+        for &kind in TIMESTAMP_KINDS {
+            let si_time = match si_timestamp(si, kind) {
+                Some(time) => time,
+                None => continue,
+            };
+
+            let predates = file_names
+                .iter()
+                .filter_map(|file_name| fn_timestamp(file_name, kind))
+                .any(|fn_time| si_time < fn_time);
+
+            if predates {
+                indicators.predates_file_name.push(kind);
+            }
+
+            if si_time.nanosecond() == 0 {
+                indicators.zeroed_sub_second.push(kind);
+            }
+        }
+
+        if indicators.is_empty() {
+            None
+        } else {
+            Some(indicators)
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.predates_file_name.is_empty() && self.zeroed_sub_second.is_empty()
+    }
+}
+
+impl<'a> FileEntry<'a> {
+    /// Looks for signs that the `$STANDARD_INFORMATION` timestamps of the entry were
+    /// overwritten, by comparing them to the timestamps of its `$FILE_NAME` attributes.
+    ///
+    /// Returns `None` when nothing is suspicious, or when the entry has no
+    /// `$STANDARD_INFORMATION` attribute.
+    pub fn detect_timestomp(&self) -> Result<Option<TimestompIndicators>, Error> {
+        let mut si = None;
+        let mut file_names = Vec::new();
+
+        for attribute in self.iter_attributes()? {
+            let attribute = attribute?;
+
+            match attribute.get_type()? {
+                AttributeType::StandardInformation | AttributeType::FileName => {
+                    match attribute.get_data()? {
+                        AttributeWithInformation::StandardInformation(info) => si = Some(info),
+                        AttributeWithInformation::FileName(file_name) => file_names.push(file_name),
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(si.and_then(|si| TimestompIndicators::compare(&si, &file_names)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+    use chrono::TimeZone;
+
+    fn standard_information(time: DateTime<Utc>) -> StandardInformation {
+        StandardInformation {
+            creation_time: Some(time),
+            modification_time: Some(time),
+            access_time: Some(time),
+            entry_modification_time: Some(time),
+            file_attribute_flags: 0,
+            owner_identifier: 0,
+            security_descriptor_identifier: 0,
+            update_sequence_number: 0,
+        }
+    }
+
+    fn file_name(time: DateTime<Utc>) -> FileName {
+        FileName {
+            name: "file.txt".to_string(),
+            parent_file_reference: 5,
+            creation_time: Some(time),
+            modification_time: Some(time),
+            access_time: Some(time),
+            entry_modification_time: Some(time),
+            file_attribute_flags: 0,
+        }
+    }
+
+    #[test]
+    fn test_consistent_timestamps_are_not_flagged() {
+        let time = Utc.ymd(2019, 3, 14).and_hms_micro(12, 0, 0, 123_456);
+
+        assert_eq!(
+            TimestompIndicators::compare(&standard_information(time), &[file_name(time)]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_si_before_fn_is_flagged() {
+        let fn_time = Utc.ymd(2019, 3, 14).and_hms_micro(12, 0, 0, 123_456);
+        let si_time = Utc.ymd(2012, 1, 1).and_hms_micro(8, 30, 0, 654_321);
+
+        let mut si = standard_information(fn_time);
+        si.creation_time = Some(si_time);
+        si.modification_time = Some(si_time);
+
+        let indicators = TimestompIndicators::compare(&si, &[file_name(fn_time)]).unwrap();
+
+        assert_eq!(
+            indicators.predates_file_name,
+            vec![TimestampKind::Creation, TimestampKind::Modification]
+        );
+        assert!(indicators.zeroed_sub_second.is_empty());
+    }
+
+    #[test]
+    fn test_zeroed_sub_second_is_flagged() {
+        let time = Utc.ymd(2012, 1, 1).and_hms(8, 30, 0);
+
+        let indicators =
+            TimestompIndicators::compare(&standard_information(time), &[file_name(time)]).unwrap();
+
+        assert!(indicators.predates_file_name.is_empty());
+        assert_eq!(indicators.zeroed_sub_second, TIMESTAMP_KINDS.to_vec());
+    }
+
+    #[test]
+    fn test_unset_timestamps_are_not_flagged() {
+        let time = Utc.ymd(2019, 3, 14).and_hms_micro(12, 0, 0, 123_456);
+
+        let mut si = standard_information(time);
+        si.creation_time = None;
+
+        let mut fn_without_times = file_name(time);
+        fn_without_times.creation_time = None;
+
+        assert_eq!(TimestompIndicators::compare(&si, &[file_name(time)]), None);
+        assert_eq!(
+            TimestompIndicators::compare(&standard_information(time), &[fn_without_times]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_detect_timestomp_on_sample_entry() {
+        let volume = sample_volume().unwrap();
+        let entry = file_entry(&volume).unwrap();
+
+        if let Some(indicators) = entry.detect_timestomp().unwrap() {
+            assert!(!indicators.is_empty());
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use crate::error::Error;
+use crate::mft::read_u64;
+use crate::name_match::MatchMode;
+use crate::volume::Volume;
+use bitflags::bitflags;
+use std::fmt::{self, Display, Formatter};
+
+pub(crate) const USN_JOURNAL_PATH: &str = "\\$Extend\\$UsnJrnl";
+const MAX_STREAM_SIZE: usize = 32;
+
+bitflags! {
+    /// Reasons for a change recorded in the USN change journal.
+    pub struct UsnReason: u32 {
+        const DATA_OVERWRITE = 0x0000_0001;
+        const DATA_EXTEND = 0x0000_0002;
+        const DATA_TRUNCATION = 0x0000_0004;
+        const NAMED_DATA_OVERWRITE = 0x0000_0010;
+        const NAMED_DATA_EXTEND = 0x0000_0020;
+        const NAMED_DATA_TRUNCATION = 0x0000_0040;
+        const FILE_CREATE = 0x0000_0100;
+        const FILE_DELETE = 0x0000_0200;
+        const EA_CHANGE = 0x0000_0400;
+        const SECURITY_CHANGE = 0x0000_0800;
+        const RENAME_OLD_NAME = 0x0000_1000;
+        const RENAME_NEW_NAME = 0x0000_2000;
+        const INDEXABLE_CHANGE = 0x0000_4000;
+        const BASIC_INFO_CHANGE = 0x0000_8000;
+        const HARD_LINK_CHANGE = 0x0001_0000;
+        const COMPRESSION_CHANGE = 0x0002_0000;
+        const ENCRYPTION_CHANGE = 0x0004_0000;
+        const OBJECT_ID_CHANGE = 0x0008_0000;
+        const REPARSE_POINT_CHANGE = 0x0010_0000;
+        const STREAM_CHANGE = 0x0020_0000;
+        const TRANSACTED_CHANGE = 0x0040_0000;
+        const INTEGRITY_CHANGE = 0x0080_0000;
+        const DESIRED_STORAGE_CLASS_CHANGE = 0x0100_0000;
+        const CLOSE = 0x8000_0000;
+    }
+}
+
+const REASON_NAMES: &[(UsnReason, &str)] = &[
+    (UsnReason::DATA_OVERWRITE, "DataOverwrite"),
+    (UsnReason::DATA_EXTEND, "DataExtend"),
+    (UsnReason::DATA_TRUNCATION, "DataTruncation"),
+    (UsnReason::NAMED_DATA_OVERWRITE, "NamedDataOverwrite"),
+    (UsnReason::NAMED_DATA_EXTEND, "NamedDataExtend"),
+    (UsnReason::NAMED_DATA_TRUNCATION, "NamedDataTruncation"),
+    (UsnReason::FILE_CREATE, "FileCreate"),
+    (UsnReason::FILE_DELETE, "FileDelete"),
+    (UsnReason::EA_CHANGE, "EaChange"),
+    (UsnReason::SECURITY_CHANGE, "SecurityChange"),
+    (UsnReason::RENAME_OLD_NAME, "RenameOldName"),
+    (UsnReason::RENAME_NEW_NAME, "RenameNewName"),
+    (UsnReason::INDEXABLE_CHANGE, "IndexableChange"),
+    (UsnReason::BASIC_INFO_CHANGE, "BasicInfoChange"),
+    (UsnReason::HARD_LINK_CHANGE, "HardLinkChange"),
+    (UsnReason::COMPRESSION_CHANGE, "CompressionChange"),
+    (UsnReason::ENCRYPTION_CHANGE, "EncryptionChange"),
+    (UsnReason::OBJECT_ID_CHANGE, "ObjectIdChange"),
+    (UsnReason::REPARSE_POINT_CHANGE, "ReparsePointChange"),
+    (UsnReason::STREAM_CHANGE, "StreamChange"),
+    (UsnReason::TRANSACTED_CHANGE, "TransactedChange"),
+    (UsnReason::INTEGRITY_CHANGE, "IntegrityChange"),
+    (
+        UsnReason::DESIRED_STORAGE_CLASS_CHANGE,
+        "DesiredStorageClassChange",
+    ),
+    (UsnReason::CLOSE, "Close"),
+];
+
+impl UsnReason {
+    /// Names of the set reasons, in ascending order of their values.
+    pub fn to_string_list(&self) -> Vec<&'static str> {
+        REASON_NAMES
+            .iter()
+            .filter(|(reason, _)| self.contains(*reason))
+            .map(|(_, name)| *name)
+            .collect()
+    }
+}
+
+/// Renders the set reasons as `FileCreate, Close`.
+impl Display for UsnReason {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.to_string_list().join(", "))
+    }
+}
+
+/// Coverage information of the USN change journal, as stored in `$UsnJrnl:$Max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UsnJournalInfo {
+    pub journal_id: u64,
+    pub max_size: u64,
+    pub allocation_delta: u64,
+    /// Lowest USN still present in the journal.
+    pub first_usn: u64,
+    /// USN that will be assigned to the next record, i.e. the size of `$UsnJrnl:$J`.
+    pub next_usn: u64,
+}
+
+impl Volume {
+    /// Retrieves the USN change journal information.
+    pub fn get_usn_journal_info(&self) -> Result<UsnJournalInfo, Error> {
+        let usn_journal = self.get_file_entry_by_path(USN_JOURNAL_PATH, MatchMode::Exact)?;
+
+        let max_stream = usn_journal.get_alternate_data_stream_by_name("$Max")?;
+        let mut max = vec![0; MAX_STREAM_SIZE];
+
+        if max_stream.read_at(0, &mut max)? != MAX_STREAM_SIZE {
+            return Err(Error::Other("$UsnJrnl:$Max is truncated".to_owned()));
+        }
+
+        let next_usn = usn_journal
+            .get_alternate_data_stream_by_name("$J")?
+            .get_size()?;
+
+        let truncated = || Error::Other("$UsnJrnl:$Max is truncated".to_owned());
+
+        Ok(UsnJournalInfo {
+            max_size: read_u64(&max, 0).ok_or_else(truncated)?,
+            allocation_delta: read_u64(&max, 8).ok_or_else(truncated)?,
+            journal_id: read_u64(&max, 16).ok_or_else(truncated)?,
+            first_usn: read_u64(&max, 24).ok_or_else(truncated)?,
+            next_usn,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    #[test]
+    fn test_usn_reason_decoding() {
+        let reason = UsnReason::from_bits_truncate(0x8000_0102);
+
+        assert_eq!(
+            reason,
+            UsnReason::FILE_CREATE | UsnReason::DATA_EXTEND | UsnReason::CLOSE
+        );
+        assert_eq!(reason.to_string(), "DataExtend, FileCreate, Close");
+    }
+
+    #[test]
+    fn test_get_usn_journal_info_without_journal() {
+        let volume = sample_volume().unwrap();
+
+        assert!(volume.get_usn_journal_info().unwrap_err().is_not_found());
+    }
+
+    #[test]
+    fn test_get_usn_journal_info() {
+        let volume = usn_journal_volume().unwrap();
+        let info = volume.get_usn_journal_info().unwrap();
+
+        assert_eq!(
+            info,
+            UsnJournalInfo {
+                journal_id: USN_JOURNAL_ID,
+                max_size: 0x200_0000,
+                allocation_delta: 0x80_0000,
+                first_usn: 4096,
+                next_usn: 5120,
+            }
+        );
+    }
+}
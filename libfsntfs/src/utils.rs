@@ -1,6 +1,16 @@
 use chrono::prelude::*;
 use time::Duration;
 
+/// Normalizes the name of an enum variant for case-insensitive lookups, so that `FileName`,
+/// `FILE_NAME` and `$FILE_NAME` compare equal.
+pub fn normalize_variant_name(name: &str) -> String {
+    name.trim_start_matches('$')
+        .chars()
+        .filter(|c| *c != '_')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
 pub fn datetime_from_filetime(nanos_since_windows_epoch: u64) -> DateTime<Utc> {
     DateTime::from_utc(
         NaiveDate::from_ymd(1601, 1, 1).and_hms_nano(0, 0, 0, 0)
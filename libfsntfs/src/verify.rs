@@ -0,0 +1,115 @@
+use crate::attribute::{AttributeType, AttributeWithInformation};
+use crate::error::Error;
+use crate::file_entry::FileEntry;
+use crate::volume::{MftEntryIndex, Volume};
+use chrono::{DateTime, Utc};
+
+/// An MFT entry whose metadata could not be read.
+#[derive(Debug)]
+pub struct UnreadableEntry {
+    pub mft_entry_index: MftEntryIndex,
+    pub error: Error,
+}
+
+/// The outcome of `Volume::verify`.
+#[derive(Debug, Default)]
+pub struct VolumeReport {
+    /// Number of allocated entries whose metadata was read successfully.
+    pub readable_entries: usize,
+    pub unreadable_entries: Vec<UnreadableEntry>,
+    /// Entries whose `$STANDARD_INFORMATION` creation time differs from the creation time of
+    /// all of their `$FILE_NAME` attributes.
+    pub timestamp_mismatches: Vec<MftEntryIndex>,
+}
+
+impl VolumeReport {
+    pub fn is_healthy(&self) -> bool {
+        self.unreadable_entries.is_empty()
+    }
+}
+
+impl Volume {
+    /// Reads the metadata of every allocated MFT entry and reports the entries that failed,
+    /// along with entries that have mismatching `$SI`/`$FN` creation times.
+    pub fn verify(&self) -> Result<VolumeReport, Error> {
+        let mut report = VolumeReport::default();
+
+        for idx in 0..self.get_number_of_file_entries()? as MftEntryIndex {
+            let checked = self
+                .get_file_entry_by_mft_idx(idx)
+                .and_then(|entry| check_entry(&entry));
+
+            match checked {
+                Ok(None) => {}
+                Ok(Some(has_mismatch)) => {
+                    report.readable_entries += 1;
+
+                    if has_mismatch {
+                        report.timestamp_mismatches.push(idx);
+                    }
+                }
+                Err(error) => report.unreadable_entries.push(UnreadableEntry {
+                    mft_entry_index: idx,
+                    error,
+                }),
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Returns `None` for unallocated entries, otherwise whether the entry has a timestamp mismatch.
+fn check_entry(entry: &FileEntry) -> Result<Option<bool>, Error> {
+    if !entry.is_allocated()? {
+        return Ok(None);
+    }
+
+    entry.get_size()?;
+
+    let mut si_creation_time = None;
+    let mut fn_creation_times: Vec<Option<DateTime<Utc>>> = Vec::new();
+
+    for attribute in entry.iter_attributes()? {
+        let attribute = attribute?;
+
+        match attribute.get_type()? {
+            AttributeType::StandardInformation | AttributeType::FileName => {
+                match attribute.get_data()? {
+                    AttributeWithInformation::StandardInformation(si) => {
+                        si_creation_time = si.creation_time
+                    }
+                    AttributeWithInformation::FileName(file_name) => {
+                        fn_creation_times.push(file_name.creation_time)
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let has_mismatch = si_creation_time.is_some()
+        && !fn_creation_times.is_empty()
+        && !fn_creation_times.contains(&si_creation_time);
+
+    Ok(Some(has_mismatch))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::*;
+
+    #[test]
+    fn test_verify_sample_volume() {
+        let volume = sample_volume().unwrap();
+        let report = volume.verify().unwrap();
+
+        assert!(report.readable_entries > 0);
+        assert!(
+            report.unreadable_entries.is_empty(),
+            "{:?}",
+            report.unreadable_entries
+        );
+    }
+}
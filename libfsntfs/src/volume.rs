@@ -1,7 +1,14 @@
+use crate::boot_sector::BootSector;
+use crate::data_stream::DataStream;
 use crate::error::Error;
-use crate::ffi_error::{LibfsntfsError, LibfsntfsErrorRef, LibfsntfsErrorRefMut};
-use crate::file_entry::{FileEntry, FileEntryRef, FileEntryRefMut};
-use libbfio_rs::handle::{Handle, HandleRef};
+use crate::ffi_error::{ErrorScratch, LibfsntfsError, LibfsntfsErrorRef, LibfsntfsErrorRefMut};
+use crate::file_entry::{libfsntfs_file_entry_free, FileEntry, FileEntryRef, FileEntryRefMut};
+use crate::mft::{apply_fixups, read_u16, read_u64};
+#[cfg(feature = "mmap")]
+use crate::mmap::VolumeMapping;
+use crate::name_match::MatchMode;
+use crate::options::set_codepage;
+use libbfio_rs::handle::{Handle, HandleRef, LibbfioAccessFlags};
 use libfsntfs_sys::{
     libfsntfs_file_entry_t, size32_t, LIBFSNTFS_ACCESS_FLAGS,
     LIBFSNTFS_ACCESS_FLAGS_LIBFSNTFS_ACCESS_FLAG_READ,
@@ -9,14 +16,31 @@ use libfsntfs_sys::{
 };
 use libyal_rs_common::ffi::AsTypeRef;
 use log::error;
+use once_cell::unsync::OnceCell;
+use std::cmp::min;
 use std::convert::TryFrom;
 use std::ffi::{c_void, CStr, CString};
 use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
 use std::marker::PhantomData;
-use std::mem;
+use std::mem::{self, ManuallyDrop};
 use std::os::raw::c_int;
+#[cfg(unix)]
+use std::os::unix::fs::FileExt;
+#[cfg(unix)]
+use std::os::unix::io::{FromRawFd, RawFd};
+#[cfg(windows)]
+use std::os::windows::fs::FileExt;
 use std::path::{Iter, Path, PathBuf};
 use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[cfg(test)]
+thread_local! {
+    /// Number of times the current thread fetched a root directory for `cached_root`.
+    static ROOT_FETCHES: std::cell::Cell<usize> = std::cell::Cell::new(0);
+}
 
 #[repr(C)]
 pub struct __Volume(isize);
@@ -25,7 +49,25 @@ pub type VolumeRefMut = *mut __Volume;
 pub type VolumeRef = *const __Volume;
 
 #[repr(C)]
-pub struct Volume(VolumeRefMut);
+pub struct Volume {
+    ptr: VolumeRefMut,
+    /// IO handle created by the bindings, which libfsntfs reads from but does not own.
+    file_io_handle: Option<Handle>,
+    /// The image or device backing the volume, for raw reads.
+    image: Option<File>,
+    /// Offset of the volume inside `image`, e.g. of a partition inside a disk image.
+    image_offset: u64,
+    /// Size of the volume, read from its boot sector once it is opened, to bound raw reads.
+    size: Option<u64>,
+    /// Whether damaged MFT records were read from `$MFTMirr`, see `open_with_mft_recovery`.
+    used_mft_mirror: bool,
+    /// How the volume was opened by path, to open it again, see `reopen`.
+    open_parameters: Option<OpenParameters>,
+    #[cfg(feature = "mmap")]
+    mapping: Option<VolumeMapping>,
+    /// Root directory shared by path lookups, fetched on first use and freed before the volume.
+    root: OnceCell<FileEntryRefMut>,
+}
 
 impl AsTypeRef for Volume {
     type Ref = VolumeRef;
@@ -34,26 +76,95 @@ impl AsTypeRef for Volume {
     #[inline]
     fn as_type_ref(&self) -> Self::Ref {
         // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
-        self.0 as *const _
+        self.ptr as *const _
     }
 
     fn as_type_ref_mut(&mut self) -> Self::RefMut {
-        self.0
+        self.ptr
     }
 
     fn as_raw(&mut self) -> *mut Self::RefMut {
-        &mut self.0 as *mut _
+        &mut self.ptr as *mut _
     }
 }
 
 impl Volume {
     pub fn wrap_ptr(ptr: VolumeRefMut) -> Volume {
-        Volume(ptr)
+        Volume {
+            ptr,
+            file_io_handle: None,
+            image: None,
+            image_offset: 0,
+            size: None,
+            used_mft_mirror: false,
+            open_parameters: None,
+            #[cfg(feature = "mmap")]
+            mapping: None,
+            root: OnceCell::new(),
+        }
+    }
+
+    fn with_open_parameters(mut self, path: &Path, offset: u64, mode: AccessMode) -> Volume {
+        self.open_parameters = Some(OpenParameters {
+            path: path.to_owned(),
+            offset,
+            mode,
+            codepage: None,
+            recover_mft: false,
+        });
+        self
+    }
+
+    /// Records the size of the opened volume. Volumes whose boot sector cannot be read still
+    /// open, but have no raw reads.
+    fn with_size(mut self) -> Volume {
+        self.size = self.get_size().ok();
+        self
+    }
+
+    /// Records the codepage set by `VolumeOptions::open`, to set it again in `reopen`.
+    pub(crate) fn set_open_codepage(&mut self, codepage: Option<i32>) {
+        if let Some(parameters) = self.open_parameters.as_mut() {
+            parameters.codepage = codepage;
+        }
+    }
+
+    #[cfg(feature = "mmap")]
+    fn with_mapping(mut self, filename: &str) -> Volume {
+        self.mapping = VolumeMapping::open(filename).ok();
+        self
+    }
+
+    /// Maps the image again, if the volume was mapped when it was opened, see `open`.
+    #[cfg(feature = "mmap")]
+    fn remap(&mut self) {
+        if self.used_mft_mirror {
+            return;
+        }
+
+        self.mapping = self
+            .open_parameters
+            .as_ref()
+            .filter(|parameters| parameters.offset == 0)
+            .and_then(|parameters| VolumeMapping::open(&parameters.path).ok());
+    }
+
+    #[cfg(feature = "mmap")]
+    pub(crate) fn mapping(&self) -> Option<&VolumeMapping> {
+        self.mapping.as_ref()
     }
 }
 
 impl Drop for Volume {
     fn drop(&mut self) {
+        if let Some(mut root) = self.root.take() {
+            let mut error = ptr::null_mut();
+
+            if unsafe { libfsntfs_file_entry_free(&mut root, &mut error) } != 1 {
+                error!("`libfsntfs_file_entry_free` failed!");
+            }
+        }
+
         let mut error = ptr::null_mut();
 
         if unsafe { libfsntfs_volume_close(self.as_type_ref(), &mut error) } != 1 {
@@ -62,7 +173,7 @@ impl Drop for Volume {
 
         let mut error = ptr::null_mut();
         if unsafe { libfsntfs_volume_free(self.as_raw(), &mut error) } != 1 {
-            panic!("`libfsntfs_volume_free` failed!");
+            error!("`libfsntfs_volume_free` failed!");
         }
     }
 }
@@ -186,6 +297,7 @@ extern "C" {
     ) -> c_int;
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AccessMode {
     Read,
     Write,
@@ -199,24 +311,123 @@ impl AccessMode {
         }
     }
 }
+
 pub type MftEntryIndex = u64;
 
+/// MFT entry index of the root directory.
+pub const ROOT_DIRECTORY_MFT_ENTRY_INDEX: MftEntryIndex = 5;
+
+/// Extracts the MFT entry index (lower 48 bits) from a file reference.
+pub fn mft_entry_index(file_reference: u64) -> MftEntryIndex {
+    file_reference & 0xffff_ffff_ffff
+}
+
+/// Extracts the sequence number (upper 16 bits) from a file reference.
+pub fn sequence_number(file_reference: u64) -> u16 {
+    (file_reference >> 48) as u16
+}
+
+/// A file reference, identifying an MFT entry together with the sequence number of its use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileReference(pub u64);
+
+impl FileReference {
+    pub fn mft_entry_index(self) -> MftEntryIndex {
+        mft_entry_index(self.0)
+    }
+
+    pub fn sequence_number(self) -> u16 {
+        sequence_number(self.0)
+    }
+
+    /// Formats the reference in the `entry-sequence` notation, e.g. `27-1`, for lining it up
+    /// with the output of The Sleuth Kit.
+    ///
+    /// Note that `icat` and `istat` take addresses of the form `entry[-type[-id]]` instead, to
+    /// which only `mft_entry_index` can be passed.
+    pub fn to_tsk_inode(&self) -> String {
+        format!("{}-{}", self.mft_entry_index(), self.sequence_number())
+    }
+
+    /// Parses a reference in the `entry-sequence` notation, see `to_tsk_inode`.
+    ///
+    /// Addresses of the form `entry-type-id`, as printed by `fls`, lack the sequence number and
+    /// are rejected.
+    pub fn from_tsk_inode(inode: &str) -> Result<Self, Error> {
+        let invalid = || {
+            Error::Other(format!(
+                "Invalid inode {:?}, expected entry-sequence",
+                inode
+            ))
+        };
+
+        let mut parts = inode.trim().split('-');
+
+        let (entry, sequence) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(entry), Some(sequence), None) => (entry, sequence),
+            _ => return Err(invalid()),
+        };
+
+        let entry: MftEntryIndex = entry.parse().map_err(|_| invalid())?;
+        let sequence: u16 = sequence.parse().map_err(|_| invalid())?;
+
+        if mft_entry_index(entry) != entry {
+            return Err(invalid());
+        }
+
+        Ok(FileReference(entry | u64::from(sequence) << 48))
+    }
+}
+
+impl From<u64> for FileReference {
+    fn from(file_reference: u64) -> Self {
+        FileReference(file_reference)
+    }
+}
+
+/// What a file reference points to, see `Volume::resolve_reference`.
+#[derive(Debug)]
+pub enum ReferenceResolution<'a> {
+    /// The referenced file still occupies its MFT entry.
+    Live(FileEntry<'a>),
+    /// The referenced file was deleted, and its MFT entry was since reused by another file.
+    SlotReused { current: FileEntry<'a> },
+    /// The MFT entry is not in use, e.g. because the referenced file was deleted.
+    Free,
+}
+
 pub type SerialNumber = u64;
 
 pub struct IterFileEntries<'a> {
     handle: &'a Volume,
     number_of_file_entries: usize,
     idx: usize,
+    scratch: ErrorScratch,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl<'a> IterFileEntries<'a> {
+    /// Ends the iteration as soon as `token` is set, checked before every entry.
+    pub fn with_cancel(mut self, token: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(token);
+        self
+    }
 }
 
 impl<'a> Iterator for IterFileEntries<'a> {
     type Item = Result<FileEntry<'a>, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(token) = &self.cancel {
+            if token.load(Ordering::Relaxed) {
+                self.idx = self.number_of_file_entries;
+            }
+        }
+
         if self.idx < self.number_of_file_entries {
             let entry = self
                 .handle
-                .get_file_entry_by_mft_idx(self.idx as MftEntryIndex);
+                .get_file_entry_by_mft_idx_with(self.idx as MftEntryIndex, &mut self.scratch);
             self.idx += 1;
 
             return Some(entry);
@@ -226,8 +437,276 @@ impl<'a> Iterator for IterFileEntries<'a> {
     }
 }
 
+/// A view of `inner` starting at `offset`, which it presents as offset 0.
+struct OffsetReader<R> {
+    inner: R,
+    offset: u64,
+}
+
+impl<R: Seek> OffsetReader<R> {
+    fn new(mut inner: R, offset: u64) -> io::Result<Self> {
+        inner.seek(SeekFrom::Start(offset))?;
+
+        Ok(OffsetReader { inner, offset })
+    }
+}
+
+impl<R: Read> Read for OffsetReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<R: Seek> Seek for OffsetReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(position) => {
+                let position = position.checked_add(self.offset).ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "seek position overflows")
+                })?;
+
+                self.inner.seek(SeekFrom::Start(position))?
+            }
+            // Only seek the inner stream once the position is known to be valid.
+            SeekFrom::Current(delta) => {
+                let current = self.inner.seek(SeekFrom::Current(0))?;
+                let position = current as i128 + delta as i128;
+
+                if position < self.offset as i128 || position > u64::max_value() as i128 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "invalid seek to before the start of the volume",
+                    ));
+                }
+
+                self.inner.seek(SeekFrom::Start(position as u64))?
+            }
+            SeekFrom::End(delta) => {
+                let end = self.inner.seek(SeekFrom::End(0))?;
+                let position = end as i128 + delta as i128;
+
+                if position < self.offset as i128 || position > u64::max_value() as i128 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "invalid seek to before the start of the volume",
+                    ));
+                }
+
+                self.inner.seek(SeekFrom::Start(position as u64))?
+            }
+        };
+
+        Ok(position - self.offset)
+    }
+}
+
+/// A view of `file` starting at `offset`, read with positional reads. Unlike a clone of the file,
+/// which shares its cursor with the original, it can be used alongside other handles.
+struct PositionalReader {
+    file: File,
+    offset: u64,
+    position: u64,
+}
+
+impl Read for PositionalReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let offset = self.offset.checked_add(self.position).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "read position overflows")
+        })?;
+
+        #[cfg(unix)]
+        let count = self.file.read_at(buf, offset)?;
+        #[cfg(windows)]
+        let count = self.file.seek_read(buf, offset)?;
+
+        self.position += count as u64;
+
+        Ok(count)
+    }
+}
+
+impl Seek for PositionalReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(position) => i128::from(position),
+            SeekFrom::Current(delta) => i128::from(self.position) + i128::from(delta),
+            SeekFrom::End(delta) => {
+                let end = self.file.metadata()?.len().saturating_sub(self.offset);
+                i128::from(end) + i128::from(delta)
+            }
+        };
+
+        if position < 0 || position > i128::from(u64::max_value()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to before the start of the volume",
+            ));
+        }
+
+        self.position = position as u64;
+
+        Ok(self.position)
+    }
+}
+
+/// The arguments a volume was opened by path with, see `Volume::reopen`.
+#[derive(Debug, Clone)]
+struct OpenParameters {
+    path: PathBuf,
+    offset: u64,
+    mode: AccessMode,
+    codepage: Option<i32>,
+    /// Whether the volume was opened with `Volume::open_with_mft_recovery`.
+    recover_mft: bool,
+}
+
+impl OpenParameters {
+    fn open(&self) -> Result<Volume, Error> {
+        if let Some(codepage) = self.codepage {
+            set_codepage(codepage)?;
+        }
+
+        let mut volume = if self.recover_mft {
+            Volume::open_with_mft_recovery(&self.path)?
+        } else if self.offset != 0 {
+            Volume::open_at_offset(&self.path, self.offset)?
+        } else {
+            let path = self.path.to_str().ok_or_else(|| {
+                Error::Other(format!("Path {} is not valid UTF-8", self.path.display()))
+            })?;
+
+            Volume::open(path, self.mode)?
+        };
+
+        volume.open_parameters = Some(self.clone());
+
+        Ok(volume)
+    }
+}
+
+/// The image backing a volume, which can be sent to another thread to open an independent
+/// handle to the same volume there, see `Volume::image_source`.
+pub(crate) struct ImageSource {
+    file: File,
+    offset: u64,
+}
+
+impl ImageSource {
+    pub(crate) fn open(self) -> Result<Volume, Error> {
+        let image = self.file.try_clone().ok();
+        let offset = self.offset;
+
+        let mut volume = Volume::open_stream(PositionalReader {
+            file: self.file,
+            offset,
+            position: 0,
+        })?;
+        volume.image = image;
+        volume.image_offset = offset;
+
+        Ok(volume)
+    }
+}
+
+/// A view of `inner` in which the bytes starting at `offset` are replaced by `data`.
+struct OverlayReader<R> {
+    inner: R,
+    offset: u64,
+    data: Vec<u8>,
+    position: u64,
+}
+
+impl<R: Seek> OverlayReader<R> {
+    fn new(mut inner: R, offset: u64, data: Vec<u8>) -> io::Result<Self> {
+        let position = inner.seek(SeekFrom::Start(0))?;
+
+        Ok(OverlayReader {
+            inner,
+            offset,
+            data,
+            position,
+        })
+    }
+}
+
+impl<R: Read + Seek> Read for OverlayReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let overlay_end = self.offset + self.data.len() as u64;
+
+        let count = if self.position >= self.offset && self.position < overlay_end {
+            let start = (self.position - self.offset) as usize;
+            let count = min(buf.len(), self.data.len() - start);
+
+            buf[..count].copy_from_slice(&self.data[start..start + count]);
+            self.inner
+                .seek(SeekFrom::Start(self.position + count as u64))?;
+
+            count
+        } else if self.position < self.offset {
+            // Stop at the start of the overlay, which the next read serves.
+            let limit = min(buf.len() as u64, self.offset - self.position) as usize;
+            self.inner.read(&mut buf[..limit])?
+        } else {
+            self.inner.read(buf)?
+        };
+
+        self.position += count as u64;
+
+        Ok(count)
+    }
+}
+
+impl<R: Seek> Seek for OverlayReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.position = self.inner.seek(pos)?;
+
+        Ok(self.position)
+    }
+}
+
+/// Number of MFT records mirrored in `$MFTMirr`, those of `$MFT`, `$MFTMirr`, `$LogFile` and
+/// `$Volume`.
+const MIRRORED_MFT_RECORDS: usize = 4;
+
+fn is_intact_mft_record(record: &[u8]) -> bool {
+    record.starts_with(b"FILE") && apply_fixups(&mut record.to_vec()).is_ok()
+}
+
+/// MFT entry index of `$Boot`, which holds the boot sector of the volume.
+const BOOT_MFT_ENTRY_INDEX: MftEntryIndex = 7;
+
+/// Iterates unallocated MFT entries that still hold attributes, see
+/// `Volume::iter_deleted_entries`.
+pub struct IterDeletedEntries<'a>(IterFileEntries<'a>);
+
+impl<'a> Iterator for IterDeletedEntries<'a> {
+    type Item = Result<FileEntry<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for entry in &mut self.0 {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => return Some(Err(e)),
+            };
+
+            let is_deleted = entry.is_allocated().and_then(|is_allocated| {
+                Ok(!is_allocated && entry.get_number_of_attributes()? > 0)
+            });
+
+            match is_deleted {
+                Ok(true) => return Some(Ok(entry)),
+                Ok(false) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        None
+    }
+}
+
 impl<'a> Volume {
-    /// Opens a volume by filename.
+    /// Opens a volume by filename. See `VolumeOptions` for further options, e.g. opening a
+    /// partition inside a disk image.
     pub fn open(filename: impl AsRef<str>, mode: AccessMode) -> Result<Self, Error> {
         let mut handle = ptr::null_mut();
 
@@ -242,7 +721,7 @@ impl<'a> Volume {
             return Err(Error::try_from(init_error)?);
         }
 
-        let volume = Volume::wrap_ptr(handle);
+        let mut volume = Volume::wrap_ptr(handle);
 
         let mut error = ptr::null_mut();
 
@@ -255,10 +734,19 @@ impl<'a> Volume {
             )
         } != 1
         {
-            Err(Error::try_from(error)?)
-        } else {
-            Ok(volume)
+            return Err(Error::try_from(error)?);
         }
+
+        volume.image = File::open(filename.as_ref()).ok();
+        let volume = volume
+            .with_size()
+            .with_open_parameters(Path::new(filename.as_ref()), 0, mode);
+
+        // Mapping is best-effort, reads fall back to libfsntfs if it is unavailable.
+        #[cfg(feature = "mmap")]
+        let volume = volume.with_mapping(filename.as_ref());
+
+        Ok(volume)
     }
 
     pub fn open_file_object(file_handle: &Handle) -> Result<Self, Error> {
@@ -287,8 +775,208 @@ impl<'a> Volume {
         {
             Err(Error::try_from(error)?)
         } else {
-            Ok(volume)
+            Ok(volume.with_size())
+        }
+    }
+
+    /// Opens a volume from an open file descriptor, e.g. of a block device.
+    ///
+    /// The descriptor is duplicated, so the caller retains ownership of `fd` and may close it
+    /// independently of the volume. Note that the duplicate shares its file offset with `fd`.
+    #[cfg(unix)]
+    pub fn open_fd(fd: RawFd) -> Result<Self, Error> {
+        let duplicate = unsafe { libc::dup(fd) };
+
+        if duplicate == -1 {
+            return Err(Error::Other(format!(
+                "Failed to duplicate file descriptor {}: {}",
+                fd,
+                io::Error::last_os_error()
+            )));
         }
+
+        let file = unsafe { File::from_raw_fd(duplicate) };
+        let image = file.try_clone().ok();
+        let file_io_handle = Handle::from_file(file, LibbfioAccessFlags::Read)
+            .map_err(|e| Error::Other(format!("Failed to create IO handle: {}", e)))?;
+
+        let mut volume = Volume::open_file_object(&file_io_handle)?;
+        volume.file_io_handle = Some(file_io_handle);
+        volume.image = image;
+
+        Ok(volume)
+    }
+
+    /// Opens a live volume or disk by its device path, e.g. `\\.\C:` or `\\.\PhysicalDrive0`.
+    ///
+    /// The device is opened for reading while allowing others to keep reading and writing it,
+    /// as the mounted volume is in use. Since the volume may change while it is read, the results
+    /// are only as consistent as a snapshot of a running system can be.
+    ///
+    /// Opening devices requires an elevated process, otherwise this fails with
+    /// `Error::PermissionDenied`. Raw reads through `read_at` must be aligned to sectors.
+    #[cfg(windows)]
+    pub fn open_physical_drive(device: &str) -> Result<Self, Error> {
+        use std::fs::OpenOptions;
+        use std::os::windows::fs::OpenOptionsExt;
+
+        const FILE_SHARE_READ: u32 = 0x1;
+        const FILE_SHARE_WRITE: u32 = 0x2;
+
+        let file = OpenOptions::new()
+            .read(true)
+            .share_mode(FILE_SHARE_READ | FILE_SHARE_WRITE)
+            .open(device)
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::PermissionDenied => Error::PermissionDenied(device.to_owned()),
+                _ => Error::Other(format!("Failed to open {}: {}", device, e)),
+            })?;
+
+        let image = file.try_clone().ok();
+        let file_io_handle = Handle::from_file(file, LibbfioAccessFlags::Read)
+            .map_err(|e| Error::Other(format!("Failed to create IO handle: {}", e)))?;
+
+        let mut volume = Volume::open_file_object(&file_io_handle)?;
+        volume.file_io_handle = Some(file_io_handle);
+        volume.image = image;
+
+        Ok(volume)
+    }
+
+    /// Opens a volume from any seekable stream, e.g. a partition inside a disk image container.
+    ///
+    /// Raw reads through `read_at` are not available for volumes opened from a stream.
+    pub fn open_stream(stream: impl Read + Seek + 'static) -> Result<Self, Error> {
+        let file_io_handle = Handle::from_reader(stream)
+            .map_err(|e| Error::Other(format!("Failed to create IO handle: {}", e)))?;
+
+        let mut volume = Volume::open_file_object(&file_io_handle)?;
+        volume.file_io_handle = Some(file_io_handle);
+
+        Ok(volume)
+    }
+
+    /// Opens a volume that starts `volume_offset` bytes into the file at `path`, e.g. a partition
+    /// inside a raw disk image, without carving it out first.
+    pub fn open_at_offset(path: impl AsRef<Path>, volume_offset: u64) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let open_error =
+            |e: io::Error| Error::Other(format!("Failed to open {}: {}", path.display(), e));
+
+        let file = File::open(path).map_err(open_error)?;
+        let image = file.try_clone().ok();
+        let stream = OffsetReader::new(file, volume_offset).map_err(open_error)?;
+
+        let mut volume = Volume::open_stream(stream)?;
+        volume.image = image;
+        volume.image_offset = volume_offset;
+
+        Ok(volume.with_open_parameters(path, volume_offset, AccessMode::Read))
+    }
+
+    /// Opens a volume for reading like `open`, but reads any of the first MFT records that is
+    /// damaged from its copy in `$MFTMirr`. These records are needed to locate all other entries,
+    /// so a volume whose `$MFT` record is damaged cannot be opened otherwise.
+    ///
+    /// The image is not modified, so raw reads through `read_at` still return the damaged
+    /// records. Use `used_mft_mirror` to tell whether the mirror was needed.
+    pub fn open_with_mft_recovery(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let open_error =
+            |e: io::Error| Error::Other(format!("Failed to open {}: {}", path.display(), e));
+
+        let mut file = File::open(path).map_err(open_error)?;
+
+        let mut boot_sector = vec![0; 512];
+        file.read_exact(&mut boot_sector).map_err(open_error)?;
+        let boot_sector = BootSector::parse(&boot_sector)?;
+
+        let invalid_layout = || Error::Other("Boot sector describes an invalid MFT".to_owned());
+        let record_size = boot_sector.mft_record_size().ok_or_else(invalid_layout)? as usize;
+        let mft_offset = boot_sector
+            .mft_lcn
+            .checked_mul(boot_sector.cluster_size())
+            .ok_or_else(invalid_layout)?;
+        let mirror_offset = boot_sector
+            .mft_mirr_lcn
+            .checked_mul(boot_sector.cluster_size())
+            .ok_or_else(invalid_layout)?;
+
+        let mut read_records = |offset| -> Result<Vec<u8>, Error> {
+            let mut records = vec![0; record_size * MIRRORED_MFT_RECORDS];
+            file.seek(SeekFrom::Start(offset)).map_err(open_error)?;
+            file.read_exact(&mut records).map_err(open_error)?;
+
+            Ok(records)
+        };
+
+        let mut records = read_records(mft_offset)?;
+        let mirror = read_records(mirror_offset)?;
+        let mut used_mft_mirror = false;
+
+        for (record, mirrored) in records
+            .chunks_exact_mut(record_size)
+            .zip(mirror.chunks_exact(record_size))
+        {
+            if !is_intact_mft_record(record) && is_intact_mft_record(mirrored) {
+                record.copy_from_slice(mirrored);
+                used_mft_mirror = true;
+            }
+        }
+
+        if !used_mft_mirror {
+            let path = path.to_str().ok_or_else(|| {
+                Error::Other(format!("Path {} is not valid UTF-8", path.display()))
+            })?;
+
+            let mut volume = Volume::open(path, AccessMode::Read)?;
+
+            if let Some(parameters) = volume.open_parameters.as_mut() {
+                parameters.recover_mft = true;
+            }
+
+            return Ok(volume);
+        }
+
+        let image = file.try_clone().ok();
+        let stream = OverlayReader::new(file, mft_offset, records).map_err(open_error)?;
+
+        let mut volume =
+            Volume::open_stream(stream)?.with_open_parameters(path, 0, AccessMode::Read);
+        volume.image = image;
+        volume.used_mft_mirror = true;
+
+        if let Some(parameters) = volume.open_parameters.as_mut() {
+            parameters.recover_mft = true;
+        }
+
+        Ok(volume)
+    }
+
+    /// Opens a fresh, independent handle to the same volume, e.g. for another worker thread.
+    ///
+    /// Volumes opened by path are opened again with the same path, offset, access mode and
+    /// codepage (see `VolumeOptions`), and with MFT recovery if they were. Volumes opened from a
+    /// descriptor or device are opened again from a clone of its handle. Whether the image is
+    /// memory mapped is carried over. Volumes opened from a stream or a libbfio handle cannot be
+    /// reopened.
+    pub fn reopen(&self) -> Result<Volume, Error> {
+        #[allow(unused_mut)]
+        let mut volume = match self.open_parameters.as_ref() {
+            Some(parameters) => parameters.open()?,
+            None => self.image_source()?.open()?,
+        };
+
+        #[cfg(feature = "mmap")]
+        volume.set_memory_mapping(self.is_memory_mapped());
+
+        Ok(volume)
+    }
+
+    /// Whether damaged MFT records were read from `$MFTMirr` when the volume was opened, see
+    /// `open_with_mft_recovery`.
+    pub fn used_mft_mirror(&self) -> bool {
+        self.used_mft_mirror
     }
 
     pub fn iter_entries(&self) -> Result<IterFileEntries, Error> {
@@ -296,9 +984,32 @@ impl<'a> Volume {
             handle: self,
             number_of_file_entries: self.get_number_of_file_entries()?,
             idx: 0,
+            scratch: ErrorScratch::new(),
+            cancel: None,
         })
     }
 
+    /// Determines if uncompressed non-resident data is read from a memory mapping of the image,
+    /// see `set_memory_mapping`.
+    #[cfg(feature = "mmap")]
+    pub fn is_memory_mapped(&self) -> bool {
+        self.mapping.is_some()
+    }
+
+    /// Enables or disables reading uncompressed non-resident data from a memory mapping of the
+    /// image instead of through libfsntfs. Only volumes opened by path at offset 0 are mapped,
+    /// which they are by default.
+    ///
+    /// Disabling the mapping releases it, and enabling it again maps the image again.
+    #[cfg(feature = "mmap")]
+    pub fn set_memory_mapping(&mut self, enabled: bool) {
+        if !enabled {
+            self.mapping = None;
+        } else if self.mapping.is_none() {
+            self.remap();
+        }
+    }
+
     /// Retrieves the volume serial number.
     pub fn get_serial_number(&self) -> Result<SerialNumber, Error> {
         let mut serial_number = 0_u64;
@@ -314,21 +1025,98 @@ impl<'a> Volume {
         }
     }
 
-    /// Retrieves a file entry specified by the path.
-    pub fn get_file_entry_by_path(&self, path: impl AsRef<Path>) -> Result<FileEntry, Error> {
-        let mut file_entry = ptr::null_mut();
-        let mut error = ptr::null_mut();
-
+    /// Retrieves a file entry specified by the path, using `\` as separator, comparing the
+    /// components of the path to the names of the entries as per `mode`.
+    ///
+    /// The lookup starts from a root directory handle that is cached by the volume, so repeated
+    /// lookups do not fetch the root directory again.
+    pub fn get_file_entry_by_path(
+        &self,
+        path: impl AsRef<Path>,
+        mode: MatchMode,
+    ) -> Result<FileEntry, Error> {
         let path_as_str = path
             .as_ref()
             .to_str()
             .ok_or_else(|| Error::Other("String is invalid UTF-8".to_owned()))?;
 
+        let mut components = path_as_str.split('\\').filter(|c| !c.is_empty());
+
+        let first = match components.next() {
+            Some(first) => first,
+            None => return self.get_root_directory(),
+        };
+
+        let not_found = || Error::not_found(path_as_str);
+
+        let root = self.cached_root()?;
+        let mut file_entry = root.find_sub_entry(first, mode)?.ok_or_else(not_found)?;
+
+        for component in components {
+            file_entry = file_entry
+                .find_sub_entry(component, mode)?
+                .ok_or_else(not_found)?;
+        }
+
+        Ok(file_entry)
+    }
+
+    /// Opens a data stream specified in the `path:stream` notation of Windows, e.g.
+    /// `\Users\user\Downloads\setup.exe:Zone.Identifier`. The path is split off at the last
+    /// `:` and looked up like `get_file_entry_by_path` with `MatchMode::Exact`. A spec without a
+    /// stream name opens the default data stream.
+    ///
+    /// Components can be separated by `\` or `/`, so names that hold a `/` cannot be opened this
+    /// way.
+    pub fn get_data_stream_by_path(&self, spec: &str) -> Result<DataStream, Error> {
+        let (path, stream_name) = match spec.rfind(':') {
+            Some(separator) => (&spec[..separator], &spec[separator + 1..]),
+            None => (spec, ""),
+        };
+
+        let file_entry = self.get_file_entry_by_path(path.replace('/', "\\"), MatchMode::Exact)?;
+
+        if stream_name.is_empty() {
+            return Ok(DataStream::owning(file_entry, ptr::null_mut()));
+        }
+
+        let data_stream = file_entry.get_alternate_data_stream_ptr_by_name(stream_name)?;
+
+        Ok(DataStream::owning(file_entry, data_stream))
+    }
+
+    /// The root directory cached by the volume. The handle is freed when the volume is dropped,
+    /// so the returned `FileEntry` must not be.
+    fn cached_root(&self) -> Result<ManuallyDrop<FileEntry>, Error> {
+        let root = self.root.get_or_try_init(|| {
+            #[cfg(test)]
+            ROOT_FETCHES.with(|count| count.set(count.get() + 1));
+
+            let mut file_entry = ptr::null_mut();
+            let mut error = ptr::null_mut();
+
+            if unsafe {
+                libfsntfs_volume_get_root_directory(self.as_type_ref(), &mut file_entry, &mut error)
+            } != 1
+            {
+                Err(Error::try_from(error)?)
+            } else {
+                Ok(file_entry)
+            }
+        })?;
+
+        Ok(ManuallyDrop::new(FileEntry::wrap_ptr(self, *root)))
+    }
+
+    /// Retrieves a specific file entry.
+    pub fn get_file_entry_by_mft_idx(&self, idx: MftEntryIndex) -> Result<FileEntry, Error> {
+        let mut file_entry = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
         if unsafe {
-            libfsntfs_volume_get_file_entry_by_utf8_path(
+            libfsntfs_volume_get_file_entry_by_index(
                 self.as_type_ref(),
-                path_as_str.as_ptr(),
-                path_as_str.len(),
+                idx,
                 &mut file_entry,
                 &mut error,
             )
@@ -340,21 +1128,199 @@ impl<'a> Volume {
         }
     }
 
-    /// Retrieves a specific file entry.
-    pub fn get_file_entry_by_mft_idx(&self, idx: MftEntryIndex) -> Result<FileEntry, Error> {
-        let mut file_entry = ptr::null_mut();
+    /// Resolves a file reference to the file entry it identifies, telling apart references to
+    /// files whose MFT entry was since freed or reused by another file, e.g. stale parent
+    /// references of deleted files.
+    pub fn resolve_reference(
+        &self,
+        reference: FileReference,
+    ) -> Result<ReferenceResolution, Error> {
+        let entry = self.get_file_entry_by_mft_idx(reference.mft_entry_index())?;
+
+        if !entry.is_allocated()? {
+            return Ok(ReferenceResolution::Free);
+        }
+
+        let current = FileReference::from(entry.get_file_reference()?);
+
+        if current.sequence_number() == reference.sequence_number() {
+            Ok(ReferenceResolution::Live(entry))
+        } else {
+            Ok(ReferenceResolution::SlotReused { current: entry })
+        }
+    }
+
+    /// Iterates the file entries of MFT records that are no longer in use, but were not wiped.
+    ///
+    /// Records that were never used are skipped. The data of a deleted entry may have been
+    /// overwritten since, in which case reading it fails or returns unrelated content.
+    pub fn iter_deleted_entries(&self) -> Result<IterDeletedEntries, Error> {
+        Ok(IterDeletedEntries(self.iter_entries()?))
+    }
+
+    /// Retrieves the size of the volume in bytes, as recorded in its boot sector.
+    ///
+    /// libfsntfs does not expose the number of sectors, so the boot sector is read through the
+    /// `$Boot` file entry.
+    pub fn get_size(&self) -> Result<u64, Error> {
+        let boot_sector = self.read_boot_sector()?;
+
+        let bytes_per_sector = read_u16(&boot_sector, 0x0b);
+        let number_of_sectors = read_u64(&boot_sector, 0x28);
+
+        match (bytes_per_sector, number_of_sectors) {
+            (Some(bytes_per_sector), Some(number_of_sectors)) => {
+                Ok(u64::from(bytes_per_sector) * number_of_sectors)
+            }
+            _ => Err(Error::Other("Boot sector is truncated".to_owned())),
+        }
+    }
+
+    /// Retrieves the number of bytes per sector, as recorded in its boot sector.
+    pub fn get_bytes_per_sector(&self) -> Result<u16, Error> {
+        read_u16(&self.read_boot_sector()?, 0x0b)
+            .ok_or_else(|| Error::Other("Boot sector is truncated".to_owned()))
+    }
+
+    pub(crate) fn read_boot_sector(&self) -> Result<Vec<u8>, Error> {
+        let boot = self.get_file_entry_by_mft_idx(BOOT_MFT_ENTRY_INDEX)?;
+
+        let mut boot_sector = vec![0; 512];
+        let read_count = boot.read_at(0, &mut boot_sector)?;
+        boot_sector.truncate(read_count);
+
+        Ok(boot_sector)
+    }
+
+    /// Retrieves the cluster block size in bytes.
+    pub fn get_cluster_block_size(&self) -> Result<usize, Error> {
+        let mut cluster_block_size = 0;
         let mut error = ptr::null_mut();
 
+        if unsafe {
+            libfsntfs_volume_get_cluster_block_size(
+                self.as_type_ref(),
+                &mut cluster_block_size,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(cluster_block_size)
+        }
+    }
+
+    /// Reads raw data at an absolute offset of the volume, regardless of file structure.
+    ///
+    /// Only available for volumes opened by filename, file descriptor or `open_at_offset`, since
+    /// libfsntfs does not expose its IO handle. Reads are truncated at the end of the volume.
+    pub fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<usize, Error> {
+        let image = self.image.as_ref().ok_or_else(|| {
+            Error::Other("Raw reads need a volume opened by filename or descriptor".to_owned())
+        })?;
+
+        let size = self
+            .size
+            .ok_or_else(|| Error::Other("Size of the volume is unknown".to_owned()))?;
+
+        if offset >= size {
+            return Err(Error::Other(format!(
+                "Offset {} is beyond the end of the volume ({} bytes)",
+                offset, size
+            )));
+        }
+
+        let count = min(buf.len() as u64, size - offset) as usize;
+        let buf = &mut buf[..count];
+        let mut read_count = 0;
+
+        while read_count < count {
+            let image_offset = self.image_offset + offset + read_count as u64;
+
+            #[cfg(unix)]
+            let result = image.read_at(&mut buf[read_count..], image_offset);
+            #[cfg(windows)]
+            let result = image.seek_read(&mut buf[read_count..], image_offset);
+
+            match result {
+                Ok(0) => break,
+                Ok(n) => read_count += n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    return Err(Error::Other(format!(
+                        "Failed to read volume at offset {}: {}",
+                        offset, e
+                    )))
+                }
+            }
+        }
+
+        Ok(read_count)
+    }
+
+    /// Clones the handle of the image backing the volume, to reopen the volume from it.
+    ///
+    /// Only available for volumes with raw reads, see `read_at`. Volumes opened with
+    /// `open_with_mft_recovery` are reopened without the records recovered from `$MFTMirr`.
+    pub(crate) fn image_source(&self) -> Result<ImageSource, Error> {
+        let image = self.image.as_ref().ok_or_else(|| {
+            Error::Other("Reopening needs a volume opened by filename or descriptor".to_owned())
+        })?;
+
+        Ok(ImageSource {
+            file: image
+                .try_clone()
+                .map_err(|e| Error::Other(format!("Failed to clone image handle: {}", e)))?,
+            offset: self.image_offset,
+        })
+    }
+
+    /// Reads raw data starting at a cluster.
+    pub fn read_cluster(&self, cluster: u64, buf: &mut [u8]) -> Result<usize, Error> {
+        let cluster_block_size = self.get_cluster_block_size()? as u64;
+        let offset = cluster.checked_mul(cluster_block_size).ok_or_else(|| {
+            Error::Other(format!(
+                "Cluster {} is beyond the end of the volume",
+                cluster
+            ))
+        })?;
+
+        self.read_at(offset, buf)
+    }
+
+    /// Retrieves the MFT entry size.
+    pub fn get_mft_entry_size(&self) -> Result<u32, Error> {
+        let mut mft_entry_size = 0;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libfsntfs_volume_get_mft_entry_size(self.as_type_ref(), &mut mft_entry_size, &mut error)
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(mft_entry_size)
+        }
+    }
+
+    pub(crate) fn get_file_entry_by_mft_idx_with(
+        &self,
+        idx: MftEntryIndex,
+        scratch: &mut ErrorScratch,
+    ) -> Result<FileEntry, Error> {
+        let mut file_entry = ptr::null_mut();
+
         if unsafe {
             libfsntfs_volume_get_file_entry_by_index(
                 self.as_type_ref(),
                 idx,
                 &mut file_entry,
-                &mut error,
+                scratch.as_out_ptr(),
             )
         } != 1
         {
-            Err(Error::try_from(error)?)
+            Err(scratch.take_error())
         } else {
             Ok(FileEntry::wrap_ptr(self, file_entry))
         }
@@ -369,9 +1335,26 @@ impl<'a> Volume {
         )
     }
 
-    /// Closes a volume.
-    fn close(&self) {
-        unimplemented!();
+    /// Retrieves the NTFS `(major, minor)` version, e.g. `(3, 1)` for volumes formatted by
+    /// Windows XP and later.
+    pub fn get_version(&self) -> Result<(u8, u8), Error> {
+        let mut major_version = 0;
+        let mut minor_version = 0;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libfsntfs_volume_get_version(
+                self.as_type_ref(),
+                &mut major_version,
+                &mut minor_version,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok((major_version, minor_version))
+        }
     }
 
     /// Retrieves the root directory.
@@ -408,14 +1391,15 @@ impl<'a> Volume {
         }
     }
 
-    /// Retrieves the USN change journal.
-    fn get_usn_change_journal(&self) {
-        unimplemented!();
-    }
-
     /// Signals the volume to abort the current activity.
-    fn signal_abort(&self) {
-        unimplemented!();
+    pub fn signal_abort(&self) -> Result<(), Error> {
+        let mut error = ptr::null_mut();
+
+        if unsafe { libfsntfs_volume_signal_abort(self.as_type_ref(), &mut error) } != 1 {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(())
+        }
     }
 }
 
@@ -424,7 +1408,9 @@ mod tests {
     use super::*;
     use crate::fixtures::*;
     use log::{info, trace};
+    use std::io::Write;
     use std::path::PathBuf;
+    use tempdir::TempDir;
 
     #[test]
     fn test_opens_volume_file_io_works() {
@@ -462,6 +1448,460 @@ mod tests {
         assert_eq!(volume_name_result.unwrap(), 13425491701870188067)
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_open_fd() {
+        use std::os::unix::io::{AsRawFd, OwnedFd};
+
+        let fd = OwnedFd::from(File::open(sample_volume_path()).unwrap());
+        let volume = Volume::open_fd(fd.as_raw_fd()).unwrap();
+
+        // The volume keeps its own descriptor.
+        drop(fd);
+
+        assert_eq!(volume.get_name().unwrap(), "KW-SRCH-1");
+    }
+
+    #[test]
+    fn test_open_stream() {
+        let image = std::fs::read(sample_volume_path()).unwrap();
+        let volume = Volume::open_stream(io::Cursor::new(image)).unwrap();
+
+        assert_eq!(volume.get_name().unwrap(), "KW-SRCH-1");
+        assert_eq!(
+            volume
+                .get_file_entry_by_mft_idx(27)
+                .unwrap()
+                .get_size()
+                .unwrap(),
+            120
+        );
+    }
+
+    #[test]
+    fn test_read_cluster_reads_boot_sector() {
+        let volume = sample_volume().unwrap();
+
+        let mut buf = vec![0; volume.get_cluster_block_size().unwrap()];
+        assert_eq!(volume.read_cluster(0, &mut buf).unwrap(), buf.len());
+
+        assert_eq!(&buf[3..11], b"NTFS    ");
+    }
+
+    #[test]
+    fn test_read_at_is_bounded_by_volume_size() {
+        let volume = sample_volume().unwrap();
+        let size = volume.get_size().unwrap();
+
+        let mut buf = [0; 16];
+        assert_eq!(volume.read_at(size - 4, &mut buf).unwrap(), 4);
+        assert!(volume.read_at(size, &mut buf).is_err());
+    }
+
+    #[test]
+    fn test_get_size() {
+        let volume = sample_volume().unwrap();
+        let size = volume.get_size().unwrap();
+
+        assert_eq!(size, 8224768);
+        assert!(size > file_entry(&volume).unwrap().get_size().unwrap());
+    }
+
+    #[test]
+    fn test_get_missing_file_entry_by_path_is_not_found() {
+        let volume = sample_volume().unwrap();
+
+        let result = volume.get_file_entry_by_path("\\does\\not\\exist", MatchMode::Exact);
+
+        assert!(result.unwrap_err().is_not_found());
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_unmapped_volume_reads_entries() {
+        let volume = sample_volume().unwrap();
+        let mut unmapped_volume = sample_volume().unwrap();
+        unmapped_volume.set_memory_mapping(false);
+
+        assert!(!unmapped_volume.is_memory_mapped());
+
+        let contents = |volume: &Volume| -> Vec<(String, Vec<u8>)> {
+            volume
+                .iter_entries()
+                .unwrap()
+                .map(|e| {
+                    let entry = e.unwrap();
+                    let mut data = vec![0; 4096];
+                    let read_count = entry.read_at(0, &mut data).unwrap_or(0);
+                    data.truncate(read_count);
+
+                    (entry.get_name().unwrap(), data)
+                })
+                .collect()
+        };
+
+        assert_eq!(contents(&volume), contents(&unmapped_volume));
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_memory_mapping_can_be_enabled_again() {
+        let mut volume = sample_volume().unwrap();
+        assert!(volume.is_memory_mapped());
+
+        volume.set_memory_mapping(false);
+        assert!(!volume.is_memory_mapped());
+
+        volume.set_memory_mapping(true);
+        assert!(volume.is_memory_mapped());
+    }
+
+    #[test]
+    fn test_iter_entries_does_not_allocate() {
+        let volume = sample_volume().unwrap();
+        let mut entries = volume.iter_entries().unwrap();
+
+        // The first entry may initialize state shared by the others, e.g. of the logger.
+        entries.next().unwrap().unwrap();
+
+        let (count, allocations) = count_allocations(|| {
+            let mut count = 1;
+            while let Some(Ok(_)) = entries.next() {
+                count += 1;
+            }
+            count
+        });
+
+        assert_eq!(count, volume.get_number_of_file_entries().unwrap());
+        // Allowing for incidental allocations, but not for one per entry.
+        assert!(allocations < count - 1);
+    }
+
+    #[test]
+    fn test_iter_deleted_entries() {
+        let volume = sample_volume().unwrap();
+
+        let mut names: Vec<String> = volume
+            .iter_deleted_entries()
+            .unwrap()
+            .map(|e| e.unwrap())
+            .map(|e| {
+                assert!(!e.is_allocated().unwrap());
+                e.get_name().unwrap()
+            })
+            .collect();
+
+        names.sort();
+        assert_eq!(names, vec![".test.log.swp", "file-r-2.dat"]);
+    }
+
+    #[test]
+    fn test_iter_entries_stops_when_cancelled() {
+        let volume = sample_volume().unwrap();
+        let token = Arc::new(AtomicBool::new(false));
+
+        let mut entries = volume.iter_entries().unwrap().with_cancel(token.clone());
+
+        assert!(entries.next().is_some());
+        token.store(true, Ordering::Relaxed);
+
+        assert!(entries.next().is_none());
+    }
+
+    #[test]
+    fn test_file_reference_as_map_key() {
+        use std::collections::HashMap;
+
+        let volume = sample_volume().unwrap();
+        let mut names = HashMap::new();
+
+        for entry in volume.iter_entries().unwrap().filter_map(Result::ok) {
+            if let (Ok(reference), Ok(name)) = (entry.get_file_reference(), entry.get_name()) {
+                names.insert(FileReference::from(reference), name);
+            }
+        }
+
+        let reference = FileReference(
+            volume
+                .get_file_entry_by_mft_idx(27)
+                .unwrap()
+                .get_file_reference()
+                .unwrap(),
+        );
+
+        assert_eq!(reference.mft_entry_index(), 27);
+        assert_eq!(reference.sequence_number(), 1);
+        assert_eq!(names[&reference], "file-r-1.dat");
+        assert!(!names.contains_key(&FileReference(27)));
+    }
+
+    // Must be run from an elevated prompt:
+    // `cargo test test_open_physical_drive -- --ignored`
+    #[test]
+    #[cfg(windows)]
+    #[ignore]
+    fn test_open_physical_drive() {
+        let volume = Volume::open_physical_drive(r"\\.\C:").unwrap();
+
+        assert!(volume.get_number_of_file_entries().unwrap() > 0);
+        assert_eq!(
+            volume.get_root_directory().unwrap().get_name().unwrap(),
+            "."
+        );
+    }
+
+    #[test]
+    fn test_tsk_inode_round_trip() {
+        let volume = sample_volume().unwrap();
+        let entry = volume.get_file_entry_by_mft_idx(27).unwrap();
+        let reference = FileReference::from(entry.get_file_reference().unwrap());
+
+        assert_eq!(reference.to_tsk_inode(), "27-1");
+        assert_eq!(FileReference::from_tsk_inode("27-1").unwrap(), reference);
+
+        let reference = FileReference(0xffff_ffff_ffff | 0xffff << 48);
+        assert_eq!(
+            FileReference::from_tsk_inode(&reference.to_tsk_inode()).unwrap(),
+            reference
+        );
+    }
+
+    #[test]
+    fn test_from_tsk_inode_rejects_other_notations() {
+        for inode in &[
+            "27",
+            "27-128-1",
+            "27-",
+            "-1",
+            "27-65536",
+            "281474976710656-1",
+            "a-b",
+        ] {
+            assert!(FileReference::from_tsk_inode(inode).is_err(), "{}", inode);
+        }
+    }
+
+    #[test]
+    fn test_reopen() {
+        #[allow(unused_mut)]
+        let mut volume = sample_volume().unwrap();
+        #[cfg(feature = "mmap")]
+        volume.set_memory_mapping(false);
+
+        let reopened = volume.reopen().unwrap();
+        drop(volume);
+
+        let original = sample_volume().unwrap();
+        let read_entry_0 = |volume: &Volume| {
+            let mut data = vec![0; 1024];
+            let entry = volume.get_file_entry_by_mft_idx(0).unwrap();
+            entry.read_at(0, &mut data).unwrap();
+
+            (entry.get_name().unwrap(), data)
+        };
+
+        assert_eq!(read_entry_0(&reopened), read_entry_0(&original));
+        #[cfg(feature = "mmap")]
+        assert!(!reopened.is_memory_mapped());
+        assert!(reopened.reopen().is_ok());
+    }
+
+    #[test]
+    fn test_reopen_of_stream_fails() {
+        let file = File::open(sample_volume_path()).unwrap();
+        let volume = Volume::open_stream(file).unwrap();
+
+        assert!(volume.reopen().is_err());
+    }
+
+    #[test]
+    fn test_open_with_mft_recovery() {
+        let sample = sample_volume().unwrap();
+        let boot_sector = sample.boot_sector().unwrap();
+        let mft_offset = (boot_sector.mft_lcn * boot_sector.cluster_size()) as usize;
+
+        // Damage the signature of the `$MFT` record.
+        let mut image = std::fs::read(sample_volume_path()).unwrap();
+        image[mft_offset..mft_offset + 4].copy_from_slice(b"XXXX");
+
+        let dir = TempDir::new("mft_recovery").unwrap();
+        let image_path = dir.path().join("damaged.dd");
+        std::fs::write(&image_path, image).unwrap();
+
+        let volume = Volume::open_with_mft_recovery(&image_path).unwrap();
+        assert!(volume.used_mft_mirror());
+
+        assert_eq!(
+            volume.get_root_directory().unwrap().get_name().unwrap(),
+            "."
+        );
+        assert_eq!(
+            volume
+                .get_file_entry_by_mft_idx(27)
+                .unwrap()
+                .get_name()
+                .unwrap(),
+            "file-r-1.dat"
+        );
+
+        let volume = Volume::open_with_mft_recovery(sample_volume_path()).unwrap();
+        assert!(!volume.used_mft_mirror());
+    }
+
+    #[test]
+    fn test_open_at_offset() {
+        const PARTITION_OFFSET: u64 = 2048 * 512;
+
+        let dir = TempDir::new("open_at_offset").unwrap();
+        let disk_path = dir.path().join("disk.dd");
+
+        let mut disk = File::create(&disk_path).unwrap();
+        disk.write_all(&vec![0; PARTITION_OFFSET as usize]).unwrap();
+        disk.write_all(&std::fs::read(sample_volume_path()).unwrap())
+            .unwrap();
+        drop(disk);
+
+        let volume = Volume::open_at_offset(&disk_path, PARTITION_OFFSET).unwrap();
+        let sample = sample_volume().unwrap();
+
+        assert_eq!(volume.get_name().unwrap(), "KW-SRCH-1");
+        assert_eq!(volume.get_size().unwrap(), sample.get_size().unwrap());
+
+        let mut entry = volume.get_file_entry_by_mft_idx(27).unwrap();
+        let mut sample_entry = sample.get_file_entry_by_mft_idx(27).unwrap();
+        assert_eq!(entry.read_all().unwrap(), sample_entry.read_all().unwrap());
+
+        let mut boot_sector = [0; 512];
+        volume.read_at(0, &mut boot_sector).unwrap();
+        assert_eq!(&boot_sector[3..11], b"NTFS    ");
+    }
+
+    #[test]
+    fn test_offset_reader_seek() {
+        let mut reader = OffsetReader::new(io::Cursor::new(vec![1, 2, 3, 4, 5]), 2).unwrap();
+
+        let mut byte = [0; 1];
+        reader.read_exact(&mut byte).unwrap();
+        assert_eq!(byte, [3]);
+
+        assert_eq!(reader.seek(SeekFrom::End(-1)).unwrap(), 2);
+        assert_eq!(reader.seek(SeekFrom::Current(-2)).unwrap(), 0);
+        assert!(reader.seek(SeekFrom::Current(-1)).is_err());
+        assert!(reader.seek(SeekFrom::End(-4)).is_err());
+        assert_eq!(reader.seek(SeekFrom::Start(1)).unwrap(), 1);
+
+        reader.read_exact(&mut byte).unwrap();
+        assert_eq!(byte, [4]);
+    }
+
+    #[test]
+    fn test_path_lookups_fetch_root_once() {
+        let volume = sample_volume().unwrap();
+        let fetches_before = ROOT_FETCHES.with(|count| count.get());
+
+        for _ in 0..1000 {
+            volume
+                .get_file_entry_by_path("\\file-r-1.dat", MatchMode::Exact)
+                .unwrap();
+        }
+
+        assert_eq!(ROOT_FETCHES.with(|count| count.get()) - fetches_before, 1);
+    }
+
+    #[test]
+    fn test_get_file_entry_by_path() {
+        let volume = sample_volume().unwrap();
+
+        let entry = volume
+            .get_file_entry_by_path("\\$Extend\\$Quota", MatchMode::Exact)
+            .unwrap();
+        assert_eq!(mft_entry_index(entry.get_file_reference().unwrap()), 24);
+
+        let root = volume
+            .get_file_entry_by_path("\\", MatchMode::Exact)
+            .unwrap();
+        assert_eq!(
+            mft_entry_index(root.get_file_reference().unwrap()),
+            ROOT_DIRECTORY_MFT_ENTRY_INDEX
+        );
+
+        let error = volume
+            .get_file_entry_by_path("\\$Extend\\missing", MatchMode::Exact)
+            .unwrap_err();
+        assert!(error.is_not_found());
+        assert_eq!(error.to_string(), "\\$Extend\\missing was not found");
+    }
+
+    #[test]
+    fn test_get_file_entry_by_path_case_insensitive() {
+        let volume = sample_volume().unwrap();
+
+        let entry = volume
+            .get_file_entry_by_path("\\$EXTEND\\$quota", MatchMode::CaseInsensitive)
+            .unwrap();
+
+        assert_eq!(entry.get_name().unwrap(), "$Quota");
+    }
+
+    #[test]
+    fn test_get_data_stream_by_path() {
+        let volume = sample_volume().unwrap();
+
+        let mut default_stream = volume.get_data_stream_by_path("\\file-r-3.dat").unwrap();
+        assert!(default_stream.is_default());
+        assert_eq!(default_stream.get_size().unwrap(), 512);
+
+        let mut data = Vec::new();
+        default_stream.read_to_end(&mut data).unwrap();
+        assert_eq!(data.len(), 512);
+
+        let named = volume
+            .get_data_stream_by_path("\\file-r-3.dat:here")
+            .unwrap();
+        assert_eq!(named.get_name().unwrap(), "here");
+        assert_eq!(named.get_size().unwrap(), 120);
+
+        let named = volume
+            .get_data_stream_by_path("/file-r-3.dat:here")
+            .unwrap();
+        assert_eq!(named.get_name().unwrap(), "here");
+
+        assert!(volume
+            .get_data_stream_by_path("\\file-r-3.dat:missing")
+            .unwrap_err()
+            .is_not_found());
+        assert!(volume
+            .get_data_stream_by_path("\\missing.dat:here")
+            .unwrap_err()
+            .is_not_found());
+    }
+
+    #[test]
+    fn test_get_zone_identifier_by_path() {
+        let volume = zone_identifier_volume().unwrap();
+
+        let default_stream = volume.get_data_stream_by_path("\\file-r-1.dat").unwrap();
+        assert!(default_stream.is_default());
+
+        let mut zone_identifier = volume
+            .get_data_stream_by_path("\\file-r-1.dat:Zone.Identifier")
+            .unwrap();
+        let mut data = String::new();
+        zone_identifier.read_to_string(&mut data).unwrap();
+
+        assert_eq!(data, ZONE_IDENTIFIER);
+    }
+
+    #[test]
+    fn test_get_file_entry_by_huge_index_is_err() {
+        let volume = sample_volume().unwrap();
+
+        assert!(volume.get_file_entry_by_mft_idx(u64::MAX).is_err());
+        assert!(volume
+            .get_file_entry_by_mft_idx(volume.get_number_of_file_entries().unwrap() as u64)
+            .is_err());
+    }
+
     #[test]
     fn test_iter_entries() {
         let volume = sample_volume().unwrap();
@@ -471,4 +1911,43 @@ mod tests {
             println!("{:?}", entry);
         }
     }
+
+    #[test]
+    fn test_resolve_reference() {
+        let volume = sample_volume().unwrap();
+        let reference = FileReference(
+            volume
+                .get_file_entry_by_mft_idx(27)
+                .unwrap()
+                .get_file_reference()
+                .unwrap(),
+        );
+
+        match volume.resolve_reference(reference).unwrap() {
+            ReferenceResolution::Live(entry) => {
+                assert_eq!(entry.get_name().unwrap(), "file-r-1.dat")
+            }
+            other => panic!("expected a live entry, got {:?}", other),
+        }
+
+        // A reference to a previous file in the same MFT entry.
+        let stale = FileReference(27 | u64::from(reference.sequence_number() - 1) << 48);
+
+        match volume.resolve_reference(stale).unwrap() {
+            ReferenceResolution::SlotReused { current } => {
+                assert_eq!(current.get_file_reference().unwrap(), reference.0)
+            }
+            other => panic!("expected a reused entry, got {:?}", other),
+        }
+
+        // MFT entry 34 held a file that was deleted.
+        assert!(matches!(
+            volume
+                .resolve_reference(FileReference(34 | 3 << 48))
+                .unwrap(),
+            ReferenceResolution::Free
+        ));
+
+        assert!(volume.resolve_reference(FileReference(u64::MAX)).is_err());
+    }
 }
@@ -0,0 +1,591 @@
+//! Recursive traversal of the directory tree of a volume.
+use crate::attribute::{AttributeType, ReparsePointTag};
+use crate::error::Error;
+use crate::ffi_error::ErrorScratch;
+use crate::file_entry::FileEntry;
+use crate::metadata::{EntryMetadata, FileEntryMetadata};
+use crate::name_match::MatchMode;
+use crate::volume::{mft_entry_index, FileReference, MftEntryIndex, Volume};
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// MFT entries below this index are reserved for the metadata files of NTFS, e.g. `$MFT` and
+/// `$Extend`.
+const FIRST_USER_MFT_ENTRY_INDEX: MftEntryIndex = 24;
+
+/// A predicate on the entries of a walk, see `Walk::filter` and `Walk::prune`.
+type Predicate<'a> = Box<dyn FnMut(&FileEntry) -> bool + 'a>;
+
+/// A directory whose sub entries are being yielded.
+struct Frame<'a> {
+    directory: FileEntry<'a>,
+    path: Vec<OsString>,
+    /// Depth of the directory, the root directory is at depth 0.
+    depth: usize,
+    number_of_sub_entries: i32,
+    idx: i32,
+}
+
+/// Iterates all entries below the root directory in depth-first order, yielding their path
+/// relative to the root alongside the entry. Paths are yielded as components, like
+/// `FileEntry::full_path_components`, since names may hold characters that are separators on
+/// the host, e.g. `/`.
+///
+/// Reparse points are not followed unless enabled with `follow_mount_points`, and directories
+/// reachable through more than one path are only descended into once. Directories are tracked on
+/// an explicit stack, so arbitrarily deep trees do not exhaust the call stack.
+pub struct Walk<'a> {
+    volume: &'a Volume,
+    stack: Vec<Frame<'a>>,
+    min_depth: usize,
+    max_depth: usize,
+    cancel: Option<Arc<AtomicBool>>,
+    follow_mount_points: bool,
+    skip_system_files: bool,
+    filter: Option<Predicate<'a>>,
+    prune: Option<Predicate<'a>>,
+    visited: HashSet<MftEntryIndex>,
+    scratch: ErrorScratch,
+}
+
+impl<'a> Walk<'a> {
+    fn new(volume: &'a Volume) -> Result<Self, Error> {
+        let mut walk = Walk {
+            volume,
+            stack: Vec::new(),
+            min_depth: 0,
+            max_depth: usize::max_value(),
+            cancel: None,
+            follow_mount_points: false,
+            skip_system_files: false,
+            filter: None,
+            prune: None,
+            visited: HashSet::new(),
+            scratch: ErrorScratch::new(),
+        };
+
+        walk.push(volume.get_root_directory()?, Vec::new(), 0)?;
+
+        Ok(walk)
+    }
+
+    /// Does not descend below `depth`. Direct children of the root directory are at depth 1, so
+    /// a depth of 1 only yields those.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Skips entries above `depth`, while still descending through them.
+    pub fn min_depth(mut self, depth: usize) -> Self {
+        self.min_depth = depth;
+        self
+    }
+
+    /// Ends the walk as soon as `token` is set, checked before every entry.
+    pub fn with_cancel(mut self, token: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Descends into the targets of mount points (junctions) and symbolic links, when they
+    /// resolve to a directory of the walked volume. Entries below a followed link are yielded
+    /// under the path of the link. Off by default, in which case links are yielded but not
+    /// descended into.
+    pub fn follow_mount_points(mut self, follow: bool) -> Self {
+        self.follow_mount_points = follow;
+        self
+    }
+
+    /// Skips the metadata files of NTFS, i.e. the entries in the reserved MFT range below 24,
+    /// and does not descend into `$Extend`. Off by default, so that nothing is left out.
+    pub fn skip_system_files(mut self, skip: bool) -> Self {
+        self.skip_system_files = skip;
+        self
+    }
+
+    /// Only yields the entries for which `predicate` returns true, e.g. to skip hidden files.
+    /// Directories that are not yielded are still descended into, see `prune` for that.
+    pub fn filter(mut self, predicate: impl FnMut(&FileEntry) -> bool + 'a) -> Self {
+        self.filter = Some(Box::new(predicate));
+        self
+    }
+
+    /// Does not descend into the directories (and followed links) for which `predicate`
+    /// returns true. Whether they are yielded themselves is up to `filter`.
+    pub fn prune(mut self, predicate: impl FnMut(&FileEntry) -> bool + 'a) -> Self {
+        self.prune = Some(Box::new(predicate));
+        self
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .map_or(false, |token| token.load(Ordering::Relaxed))
+    }
+
+    /// Yields the metadata of each entry instead of the entry itself, freeing every entry as
+    /// soon as its metadata was read.
+    pub fn metadata_only(self) -> WalkMetadata<'a> {
+        WalkMetadata(self)
+    }
+
+    fn push(
+        &mut self,
+        directory: FileEntry<'a>,
+        path: Vec<OsString>,
+        depth: usize,
+    ) -> Result<(), Error> {
+        if !self
+            .visited
+            .insert(mft_entry_index(directory.get_file_reference()?))
+        {
+            return Ok(());
+        }
+
+        self.stack.push(Frame {
+            number_of_sub_entries: directory.get_number_of_sub_file_entries()?,
+            directory,
+            path,
+            depth,
+            idx: 0,
+        });
+
+        Ok(())
+    }
+
+    fn next_entry(&mut self) -> Option<Result<(Vec<OsString>, FileEntry<'a>), Error>> {
+        loop {
+            if self.is_cancelled() {
+                self.stack.clear();
+                return None;
+            }
+
+            let frame = self.stack.last_mut()?;
+
+            if frame.idx >= frame.number_of_sub_entries {
+                self.stack.pop();
+                continue;
+            }
+
+            let idx = frame.idx;
+            frame.idx += 1;
+
+            let sub_entry = match frame
+                .directory
+                .get_sub_file_entry_with(idx, &mut self.scratch)
+            {
+                Ok(sub_entry) => sub_entry,
+                Err(e) => return Some(Err(e)),
+            };
+
+            if self.skip_system_files {
+                match sub_entry.get_file_reference() {
+                    // `$Extend` is entry 11, so its subtree is pruned along with it.
+                    Ok(reference) if mft_entry_index(reference) < FIRST_USER_MFT_ENTRY_INDEX => {
+                        continue
+                    }
+                    Ok(_) => {}
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+
+            let name = match sub_entry.get_name() {
+                Ok(name) => name,
+                Err(e) => return Some(Err(e)),
+            };
+
+            // The root directory lists itself as `.`.
+            if name == "." {
+                continue;
+            }
+
+            let mut path = frame.path.clone();
+            path.push(OsString::from(name));
+            let depth = frame.depth + 1;
+
+            match sub_entry.is_dir() {
+                Ok(true) if depth < self.max_depth && !is_pruned(&mut self.prune, &sub_entry) => {
+                    // The frame keeps its own handle, the yielded entry is owned by the caller.
+                    let directory = frame
+                        .directory
+                        .get_sub_file_entry_with(idx, &mut self.scratch)
+                        .and_then(|directory| self.push(directory, path.clone(), depth));
+
+                    if let Err(e) = directory {
+                        return Some(Err(e));
+                    }
+                }
+                Ok(false)
+                    if self.follow_mount_points
+                        && depth < self.max_depth
+                        && !is_pruned(&mut self.prune, &sub_entry) =>
+                {
+                    let target = self
+                        .link_target(&sub_entry)
+                        .and_then(|target| match target {
+                            Some(target) => self.push(target, path.clone(), depth),
+                            None => Ok(()),
+                        });
+
+                    if let Err(e) = target {
+                        return Some(Err(e));
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => return Some(Err(e)),
+            }
+
+            if depth < self.min_depth {
+                continue;
+            }
+
+            if let Some(filter) = self.filter.as_mut() {
+                if !filter(&sub_entry) {
+                    continue;
+                }
+            }
+
+            return Some(Ok((path, sub_entry)));
+        }
+    }
+}
+
+impl<'a> Walk<'a> {
+    /// Resolves the target directory of a mount point or symbolic link. Returns `None` for other
+    /// entries, and for targets outside of the volume (e.g. `\??\Volume{..}\`) or relative ones.
+    fn link_target(&self, entry: &FileEntry<'a>) -> Result<Option<FileEntry<'a>>, Error> {
+        let tag = match entry.get_attribute_by_name(AttributeType::ReparsePoint, "")? {
+            Some(attribute) => attribute.get_reparse_point_tag(),
+            None => return Ok(None),
+        };
+
+        match tag {
+            Ok(ReparsePointTag::MountPoint) | Ok(ReparsePointTag::Symlink) => {}
+            _ => return Ok(None),
+        }
+
+        let path = match entry
+            .get_reparse_point_substitute_name()?
+            .as_ref()
+            .and_then(|target| volume_relative_path(target))
+        {
+            Some(path) => path.to_owned(),
+            None => return Ok(None),
+        };
+
+        match self.volume.get_file_entry_by_path(path, MatchMode::Exact) {
+            Ok(target) if target.is_dir()? => Ok(Some(target)),
+            Ok(_) => Ok(None),
+            Err(ref e) if e.is_not_found() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+fn is_pruned(prune: &mut Option<Predicate>, entry: &FileEntry) -> bool {
+    prune.as_mut().map_or(false, |prune| prune(entry))
+}
+
+/// Strips the NT namespace prefix and drive letter of a link target, e.g. `\??\C:\dir` becomes
+/// `\dir`. The target is assumed to be on the walked volume.
+fn volume_relative_path(target: &str) -> Option<&str> {
+    let path = target.strip_prefix("\\??\\")?;
+    let mut chars = path.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(letter), Some(':')) if letter.is_ascii_alphabetic() => {
+            Some(&path[2..]).filter(|path| path.starts_with('\\'))
+        }
+        _ => None,
+    }
+}
+
+impl<'a> Iterator for Walk<'a> {
+    type Item = Result<(Vec<OsString>, FileEntry<'a>), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry()
+    }
+}
+
+/// Adapter returned by `Walk::metadata_only`.
+pub struct WalkMetadata<'a>(Walk<'a>);
+
+impl<'a> Iterator for WalkMetadata<'a> {
+    type Item = Result<(Vec<OsString>, FileEntryMetadata), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|entry| {
+            let (path, entry) = entry?;
+
+            Ok((path, FileEntryMetadata::from_entry(&entry)?))
+        })
+    }
+}
+
+impl Volume {
+    /// Walks the directory tree, starting from the root directory.
+    pub fn walk(&self) -> Result<Walk, Error> {
+        Walk::new(self)
+    }
+
+    /// Finds the files with the extension `ext` (with or without its leading dot) anywhere in the
+    /// directory tree, comparing extensions case-insensitively. Directories are skipped.
+    pub fn find_by_extension(
+        &self,
+        ext: &str,
+    ) -> Result<Vec<(Vec<OsString>, FileReference)>, Error> {
+        let ext = ext.trim_start_matches('.');
+        let mut found = Vec::new();
+
+        for entry in self.walk()? {
+            let (path, entry) = entry?;
+
+            let matches = path
+                .last()
+                .and_then(|name| Path::new(name).extension())
+                .and_then(|extension| extension.to_str())
+                .map_or(false, |extension| {
+                    MatchMode::CaseInsensitive.matches(extension, ext)
+                });
+
+            if matches && !entry.is_dir()? {
+                found.push((path, FileReference::from(entry.get_file_reference()?)));
+            }
+        }
+
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_attributes::FileAttributeFlags;
+    use crate::fixtures::*;
+
+    fn path(components: &[&str]) -> Vec<OsString> {
+        components.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn test_walk_yields_nested_entries() {
+        let volume = sample_volume().unwrap();
+
+        let paths: Vec<Vec<OsString>> = volume.walk().unwrap().map(|e| e.unwrap().0).collect();
+
+        assert!(paths.contains(&path(&["$MFT"])));
+        assert!(paths.contains(&path(&["$Extend", "$ObjId"])));
+    }
+
+    #[test]
+    fn test_walk_paths_match_full_path_components() {
+        let volume = sample_volume().unwrap();
+
+        for entry in volume.walk().unwrap() {
+            let (path, entry) = entry.unwrap();
+            assert_eq!(path, entry.full_path_components().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_walk_max_depth_yields_direct_children_of_root() {
+        let volume = sample_volume().unwrap();
+        let root = volume.get_root_directory().unwrap();
+
+        let mut expected: Vec<Vec<OsString>> = root
+            .iter_sub_entries()
+            .unwrap()
+            .map(|e| e.unwrap().get_name().unwrap())
+            .filter(|name| name != ".")
+            .map(|name| vec![OsString::from(name)])
+            .collect();
+
+        let mut paths: Vec<Vec<OsString>> = volume
+            .walk()
+            .unwrap()
+            .max_depth(1)
+            .map(|e| e.unwrap().0)
+            .collect();
+
+        expected.sort();
+        paths.sort();
+        assert_eq!(paths, expected);
+    }
+
+    #[test]
+    fn test_walk_min_depth_skips_shallow_entries() {
+        let volume = sample_volume().unwrap();
+
+        let paths: Vec<Vec<OsString>> = volume
+            .walk()
+            .unwrap()
+            .min_depth(2)
+            .map(|e| e.unwrap().0)
+            .collect();
+
+        assert!(paths.iter().all(|path| path.len() >= 2));
+        assert!(paths.contains(&path(&["$Extend", "$ObjId"])));
+    }
+
+    #[test]
+    fn test_walk_skips_system_files() {
+        let volume = sample_volume().unwrap();
+
+        let paths: Vec<Vec<OsString>> = volume
+            .walk()
+            .unwrap()
+            .skip_system_files(true)
+            .map(|e| e.unwrap().0)
+            .collect();
+
+        assert!(paths.contains(&path(&["file-r-1.dat"])));
+        assert!(!paths.contains(&path(&["$MFT"])));
+        assert!(!paths.iter().any(|path| path[0] == "$Extend"));
+    }
+
+    #[test]
+    fn test_walk_filter_yields_non_hidden_files() {
+        let volume = sample_volume().unwrap();
+
+        let is_hidden = |entry: &FileEntry| {
+            entry
+                .get_file_attributes()
+                .unwrap()
+                .contains(FileAttributeFlags::HIDDEN)
+        };
+
+        let entries: Vec<(Vec<OsString>, FileEntry)> = volume
+            .walk()
+            .unwrap()
+            .filter(move |entry| !entry.is_dir().unwrap() && !is_hidden(entry))
+            .map(|e| e.unwrap())
+            .collect();
+        let paths: Vec<&Vec<OsString>> = entries.iter().map(|(path, _)| path).collect();
+
+        assert!(paths.contains(&&path(&["file-r-1.dat"])));
+        // `$MFT` is hidden, and `$Extend` is a directory.
+        assert!(!paths.contains(&&path(&["$MFT"])));
+        assert!(!paths.contains(&&path(&["$Extend"])));
+        assert!(entries
+            .iter()
+            .all(|(_, entry)| !entry.is_dir().unwrap() && !is_hidden(entry)));
+    }
+
+    #[test]
+    fn test_walk_prune_does_not_descend() {
+        let volume = sample_volume().unwrap();
+
+        let paths: Vec<Vec<OsString>> = volume
+            .walk()
+            .unwrap()
+            .prune(|entry| entry.get_name().unwrap() == "$Extend")
+            .map(|e| e.unwrap().0)
+            .collect();
+
+        assert!(paths.contains(&path(&["$Extend"])));
+        assert!(paths.contains(&path(&["file-r-1.dat"])));
+        assert!(!paths
+            .iter()
+            .any(|path| path[0] == "$Extend" && path.len() > 1));
+    }
+
+    #[test]
+    fn test_walk_stops_when_cancelled() {
+        let volume = sample_volume().unwrap();
+        let token = Arc::new(AtomicBool::new(false));
+
+        let mut walk = volume.walk().unwrap().with_cancel(token.clone());
+
+        assert!(walk.next().is_some());
+        token.store(true, Ordering::Relaxed);
+
+        assert!(walk.next().is_none());
+        assert!(walk.next().is_none());
+    }
+
+    #[test]
+    fn test_volume_relative_path() {
+        assert_eq!(volume_relative_path("\\??\\C:\\target"), Some("\\target"));
+        assert_eq!(
+            volume_relative_path("\\??\\Volume{6f1a2b3c-0000-0000-0000-000000000000}\\"),
+            None
+        );
+        assert_eq!(volume_relative_path("target"), None);
+    }
+
+    #[test]
+    fn test_walk_does_not_follow_junction_by_default() {
+        let volume = junction_volume().unwrap();
+        let junction = path(&["dir-r-4"]);
+
+        let paths: Vec<Vec<OsString>> = volume.walk().unwrap().map(|e| e.unwrap().0).collect();
+
+        assert!(paths.contains(&junction));
+        assert!(!paths
+            .iter()
+            .any(|path| path.starts_with(&junction) && path != &junction));
+
+        let followed: Vec<Vec<OsString>> = volume
+            .walk()
+            .unwrap()
+            .follow_mount_points(true)
+            .map(|e| e.unwrap().0)
+            .collect();
+
+        // The junction sorts before `System Volume Information`, so the directory is descended
+        // through the junction and not again through its own path.
+        assert!(followed.contains(&path(&["dir-r-4", "tracking.log"])));
+        assert!(!followed.contains(&path(&["System Volume Information", "tracking.log"])));
+        assert_eq!(followed.len(), paths.len());
+    }
+
+    #[test]
+    fn test_find_by_extension() {
+        let volume = sample_volume().unwrap();
+
+        let found = volume.find_by_extension("dat").unwrap();
+        let paths: Vec<&Vec<OsString>> = found.iter().map(|(path, _)| path).collect();
+
+        assert!(paths.contains(&&path(&["file-r-1.dat"])));
+        assert!(paths
+            .iter()
+            .all(|path| Path::new(path.last().unwrap()).extension().unwrap() == "dat"));
+        assert!(found
+            .iter()
+            .any(|(_, reference)| reference.mft_entry_index() == 27));
+
+        assert_eq!(volume.find_by_extension(".DAT").unwrap(), found);
+
+        // The sample has no text files.
+        assert!(volume.find_by_extension("txt").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_walk_metadata_only_matches_full_walk() {
+        let volume = sample_volume().unwrap();
+
+        let full: Vec<(Vec<OsString>, u64)> = volume
+            .walk()
+            .unwrap()
+            .map(|e| e.unwrap())
+            .map(|(path, entry)| (path, entry.get_size().unwrap()))
+            .collect();
+
+        let metadata_only: Vec<(Vec<OsString>, u64)> = volume
+            .walk()
+            .unwrap()
+            .metadata_only()
+            .map(|e| e.unwrap())
+            .map(|(path, metadata)| (path, metadata.size))
+            .collect();
+
+        assert!(!full.is_empty());
+        assert_eq!(metadata_only.len(), full.len());
+        assert_eq!(metadata_only, full);
+    }
+}
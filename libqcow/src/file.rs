@@ -0,0 +1,537 @@
+use crate::error::Error;
+use crate::ffi_error::LibqcowErrorRefMut;
+use libbfio_rs::handle::{Handle, HandleRef};
+use libqcow_sys::{
+    LIBQCOW_ACCESS_FLAGS, LIBQCOW_ACCESS_FLAGS_LIBQCOW_ACCESS_FLAG_READ,
+    LIBQCOW_ACCESS_FLAGS_LIBQCOW_ACCESS_FLAG_WRITE,
+};
+use libyal_rs_common::ffi::AsTypeRef;
+use log::error;
+use std::convert::TryFrom;
+use std::ffi::{c_void, CString};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::os::raw::c_int;
+use std::path::Path;
+use std::ptr;
+
+#[repr(C)]
+pub struct __File(isize);
+
+pub type FileRefMut = *mut __File;
+pub type FileRef = *const __File;
+
+/// A QCOW image, read as the disk it contains.
+pub struct File {
+    ptr: FileRefMut,
+    /// The backing image of a derived image, which must outlive this file.
+    parent: Option<Box<File>>,
+}
+
+impl AsTypeRef for File {
+    type Ref = FileRef;
+    type RefMut = FileRefMut;
+
+    #[inline]
+    fn as_type_ref(&self) -> Self::Ref {
+        // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
+        self.ptr as *const _
+    }
+
+    fn as_type_ref_mut(&mut self) -> Self::RefMut {
+        self.ptr
+    }
+
+    fn as_raw(&mut self) -> *mut Self::RefMut {
+        &mut self.ptr as *mut _
+    }
+}
+
+impl File {
+    pub fn wrap_ptr(ptr: FileRefMut) -> File {
+        File { ptr, parent: None }
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        let mut error = ptr::null_mut();
+
+        if unsafe { libqcow_file_close(self.as_type_ref(), &mut error) } != 0 {
+            error!("`libqcow_file_close` failed!");
+        }
+
+        let mut error = ptr::null_mut();
+        if unsafe { libqcow_file_free(self.as_raw(), &mut error) } != 1 {
+            error!("`libqcow_file_free` failed!");
+        }
+    }
+}
+
+extern "C" {
+    pub fn libqcow_file_initialize(file: *mut FileRefMut, error: *mut LibqcowErrorRefMut) -> c_int;
+    pub fn libqcow_file_free(file: *mut FileRefMut, error: *mut LibqcowErrorRefMut) -> c_int;
+    pub fn libqcow_file_signal_abort(file: FileRef, error: *mut LibqcowErrorRefMut) -> c_int;
+    pub fn libqcow_file_open(
+        file: FileRef,
+        filename: *const ::std::os::raw::c_char,
+        access_flags: c_int,
+        error: *mut LibqcowErrorRefMut,
+    ) -> c_int;
+    pub fn libqcow_file_open_file_io_handle(
+        file: FileRef,
+        file_io_handle: HandleRef,
+        access_flags: c_int,
+        error: *mut LibqcowErrorRefMut,
+    ) -> c_int;
+    pub fn libqcow_file_close(file: FileRef, error: *mut LibqcowErrorRefMut) -> c_int;
+    pub fn libqcow_file_set_parent_file(
+        file: FileRef,
+        parent_file: FileRef,
+        error: *mut LibqcowErrorRefMut,
+    ) -> c_int;
+    pub fn libqcow_file_set_keys(
+        file: FileRef,
+        key: *const u8,
+        key_size: usize,
+        error: *mut LibqcowErrorRefMut,
+    ) -> c_int;
+    pub fn libqcow_file_set_utf8_password(
+        file: FileRef,
+        utf8_string: *const u8,
+        utf8_string_length: usize,
+        error: *mut LibqcowErrorRefMut,
+    ) -> c_int;
+    pub fn libqcow_file_read_buffer(
+        file: FileRef,
+        buffer: *mut c_void,
+        buffer_size: usize,
+        error: *mut LibqcowErrorRefMut,
+    ) -> isize;
+    pub fn libqcow_file_seek_offset(
+        file: FileRef,
+        offset: i64,
+        whence: c_int,
+        error: *mut LibqcowErrorRefMut,
+    ) -> i64;
+    pub fn libqcow_file_get_format_version(
+        file: FileRef,
+        format_version: *mut u32,
+        error: *mut LibqcowErrorRefMut,
+    ) -> c_int;
+    pub fn libqcow_file_get_encryption_method(
+        file: FileRef,
+        encryption_method: *mut u32,
+        error: *mut LibqcowErrorRefMut,
+    ) -> c_int;
+    pub fn libqcow_file_get_media_size(
+        file: FileRef,
+        media_size: *mut u64,
+        error: *mut LibqcowErrorRefMut,
+    ) -> c_int;
+    pub fn libqcow_file_get_utf8_backing_filename_size(
+        file: FileRef,
+        utf8_string_size: *mut usize,
+        error: *mut LibqcowErrorRefMut,
+    ) -> c_int;
+    pub fn libqcow_file_get_utf8_backing_filename(
+        file: FileRef,
+        utf8_string: *mut u8,
+        utf8_string_size: usize,
+        error: *mut LibqcowErrorRefMut,
+    ) -> c_int;
+}
+
+pub enum AccessMode {
+    Read,
+    Write,
+}
+
+impl AccessMode {
+    fn as_flag(&self) -> LIBQCOW_ACCESS_FLAGS {
+        match self {
+            AccessMode::Read => LIBQCOW_ACCESS_FLAGS_LIBQCOW_ACCESS_FLAG_READ,
+            AccessMode::Write => LIBQCOW_ACCESS_FLAGS_LIBQCOW_ACCESS_FLAG_WRITE,
+        }
+    }
+}
+
+#[derive(PartialOrd, PartialEq, Debug, Clone)]
+#[repr(C)]
+pub enum EncryptionMethod {
+    None = 0,
+    AesCbc128 = 1,
+    Luks = 2,
+}
+
+impl TryFrom<u32> for EncryptionMethod {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(EncryptionMethod::None),
+            1 => Ok(EncryptionMethod::AesCbc128),
+            2 => Ok(EncryptionMethod::Luks),
+            _ => Err(Error::UnknownEncryptionMethodEnumVariant(value)),
+        }
+    }
+}
+
+/// The secret used to decrypt an encrypted image, see `File::open_encrypted`.
+#[derive(Clone, Copy)]
+pub enum EncryptionKey<'a> {
+    /// The raw AES key.
+    Key(&'a [u8]),
+    /// The password the key is derived from.
+    Password(&'a str),
+}
+
+impl File {
+    /// Opens an image file by filename.
+    ///
+    /// The backing chain of a derived image is opened as well, looking up each backing file
+    /// relative to the directory of the image that refers to it.
+    pub fn open(filename: impl AsRef<str>, mode: AccessMode) -> Result<Self, Error> {
+        File::open_with_key(filename.as_ref(), mode, None)
+    }
+
+    /// Opens an encrypted image file by filename. The key is used for the backing chain too.
+    pub fn open_encrypted(
+        filename: impl AsRef<str>,
+        mode: AccessMode,
+        key: EncryptionKey,
+    ) -> Result<Self, Error> {
+        File::open_with_key(filename.as_ref(), mode, Some(key))
+    }
+
+    fn open_with_key(
+        filename: &str,
+        mode: AccessMode,
+        key: Option<EncryptionKey>,
+    ) -> Result<Self, Error> {
+        let c_string = CString::new(filename).map_err(Error::StringContainsNul)?;
+
+        let mut file = File::initialize()?;
+
+        match key {
+            Some(EncryptionKey::Key(key)) => file.set_keys(key)?,
+            Some(EncryptionKey::Password(password)) => file.set_password(password)?,
+            None => {}
+        }
+
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libqcow_file_open(
+                file.as_type_ref(),
+                c_string.as_ptr(),
+                mode.as_flag() as c_int,
+                &mut error as _,
+            )
+        } != 1
+        {
+            return Err(Error::try_from(error)?);
+        }
+
+        if let Some(backing_filename) = file.get_backing_filename()? {
+            let parent = File::open_backing_file(Path::new(filename), &backing_filename, key)?;
+            file.set_parent(parent)?;
+        }
+
+        Ok(file)
+    }
+
+    /// Opens an image file using a libbfio handle.
+    ///
+    /// The backing file of a derived image cannot be located from a handle, and has to be
+    /// provided with `set_parent` before reading.
+    pub fn open_file_object(file_handle: &Handle) -> Result<Self, Error> {
+        let file = File::initialize()?;
+
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libqcow_file_open_file_io_handle(
+                file.as_type_ref(),
+                file_handle.as_type_ref(),
+                AccessMode::Read.as_flag() as c_int,
+                &mut error as _,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(file)
+        }
+    }
+
+    fn initialize() -> Result<File, Error> {
+        let mut handle = ptr::null_mut();
+        let mut init_error = ptr::null_mut();
+
+        let retcode = unsafe { libqcow_file_initialize(&mut handle as _, &mut init_error as _) };
+
+        if retcode != 1 {
+            Err(Error::try_from(init_error)?)
+        } else {
+            Ok(File::wrap_ptr(handle))
+        }
+    }
+
+    fn open_backing_file(
+        path: &Path,
+        backing_filename: &str,
+        key: Option<EncryptionKey>,
+    ) -> Result<File, Error> {
+        // Relative backing filenames are resolved against the directory of the derived image,
+        // the same way QEMU does.
+        let backing_path = path
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(backing_filename);
+
+        let backing_path = backing_path.to_str().ok_or_else(|| {
+            Error::Other(format!(
+                "Backing path {:?} is not valid UTF-8",
+                backing_path
+            ))
+        })?;
+
+        File::open_with_key(backing_path, AccessMode::Read, key)
+    }
+
+    fn set_keys(&self, key: &[u8]) -> Result<(), Error> {
+        let mut error = ptr::null_mut();
+
+        if unsafe { libqcow_file_set_keys(self.as_type_ref(), key.as_ptr(), key.len(), &mut error) }
+            != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn set_password(&self, password: &str) -> Result<(), Error> {
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libqcow_file_set_utf8_password(
+                self.as_type_ref(),
+                password.as_ptr(),
+                password.len(),
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Sets the backing image of a derived image.
+    pub fn set_parent(&mut self, parent: File) -> Result<(), Error> {
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libqcow_file_set_parent_file(self.as_type_ref(), parent.as_type_ref(), &mut error)
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            self.parent = Some(Box::new(parent));
+            Ok(())
+        }
+    }
+
+    /// Retrieves the backing image of a derived image, if it was set.
+    pub fn get_parent(&self) -> Option<&File> {
+        self.parent.as_deref()
+    }
+
+    /// Retrieves the format version, e.g. 2 for QCOW2.
+    pub fn get_format_version(&self) -> Result<u32, Error> {
+        let mut format_version = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libqcow_file_get_format_version(self.as_type_ref(), &mut format_version, &mut error)
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(format_version)
+        }
+    }
+
+    /// Retrieves the encryption method.
+    pub fn get_encryption_method(&self) -> Result<EncryptionMethod, Error> {
+        let mut method_as_num = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libqcow_file_get_encryption_method(self.as_type_ref(), &mut method_as_num, &mut error)
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(EncryptionMethod::try_from(method_as_num)?)
+        }
+    }
+
+    /// Retrieves the size of the contained disk.
+    pub fn get_media_size(&self) -> Result<u64, Error> {
+        get_u64_field!(self, libqcow_file_get_media_size)
+    }
+
+    /// Retrieves the backing filename of a derived image, as stored in the image.
+    /// Returns `None` if the image has no backing file.
+    pub fn get_backing_filename(&self) -> Result<Option<String>, Error> {
+        let mut name_size = 0_usize;
+        let mut error = ptr::null_mut();
+
+        match unsafe {
+            libqcow_file_get_utf8_backing_filename_size(
+                self.as_type_ref(),
+                &mut name_size,
+                &mut error,
+            )
+        } {
+            1 => {}
+            0 => return Ok(None),
+            _ => return Err(Error::try_from(error)?),
+        }
+
+        get_sized_utf8_string!(
+            self,
+            libqcow_file_get_utf8_backing_filename_size,
+            libqcow_file_get_utf8_backing_filename
+        )
+        .map(Some)
+    }
+}
+
+fn io_error(error: LibqcowErrorRefMut) -> io::Error {
+    match Error::try_from(error) {
+        Ok(e) => io::Error::new(io::ErrorKind::Other, format!("{}", e)),
+        Err(_e) => io::Error::new(
+            io::ErrorKind::Other,
+            "error while getting error information",
+        ),
+    }
+}
+
+impl Read for File {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut error = ptr::null_mut();
+
+        let read_count = unsafe {
+            libqcow_file_read_buffer(
+                self.as_type_ref(),
+                buf.as_mut_ptr() as *mut c_void,
+                buf.len(),
+                &mut error,
+            )
+        };
+
+        if read_count < 0 {
+            Err(io_error(error))
+        } else {
+            Ok(read_count as usize)
+        }
+    }
+}
+
+impl Seek for File {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (offset, whence) = match pos {
+            SeekFrom::Start(p) => (p as i64, libc_seek::SEEK_SET),
+            SeekFrom::Current(p) => (p, libc_seek::SEEK_CUR),
+            SeekFrom::End(p) => (p, libc_seek::SEEK_END),
+        };
+
+        let mut error = ptr::null_mut();
+
+        let offset =
+            unsafe { libqcow_file_seek_offset(self.as_type_ref(), offset, whence, &mut error) };
+
+        if offset < 0 {
+            Err(io_error(error))
+        } else {
+            Ok(offset as u64)
+        }
+    }
+}
+
+/// `whence` values of `libqcow_file_seek_offset`, as defined by the C library.
+mod libc_seek {
+    use std::os::raw::c_int;
+
+    pub const SEEK_SET: c_int = 0;
+    pub const SEEK_CUR: c_int = 1;
+    pub const SEEK_END: c_int = 2;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    const MEDIA_SIZE: u64 = 1024 * 1024;
+
+    #[test]
+    fn test_opens_file_io_works() {
+        let handle = sample_image_io_handle().unwrap();
+        let file = File::open_file_object(&handle).unwrap();
+
+        assert_eq!(file.get_media_size().unwrap(), MEDIA_SIZE);
+    }
+
+    #[test]
+    fn test_image_metadata() {
+        let file = sample_image().unwrap();
+
+        assert_eq!(file.get_media_size().unwrap(), MEDIA_SIZE);
+        assert_eq!(file.get_format_version().unwrap(), 2);
+        assert_eq!(
+            file.get_encryption_method().unwrap(),
+            EncryptionMethod::None
+        );
+        assert!(file.get_backing_filename().unwrap().is_none());
+        assert!(file.get_parent().is_none());
+    }
+
+    #[test]
+    fn test_read_and_seek() {
+        let mut file = sample_image().unwrap();
+        let mut buf = [0; 29];
+
+        file.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"libqcow-rs qcow2 disk fixture");
+
+        assert_eq!(file.seek(SeekFrom::End(-4)).unwrap(), MEDIA_SIZE - 4);
+
+        let mut rest = vec![];
+        file.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, vec![0; 4]);
+    }
+
+    #[test]
+    fn test_opens_backing_file_of_derived_image() {
+        let path = sample_image_path().replace("small.qcow2", "derived.qcow2");
+        let mut file = File::open(&path, AccessMode::Read).unwrap();
+
+        assert_eq!(
+            file.get_backing_filename().unwrap().as_deref(),
+            Some("small.qcow2")
+        );
+        assert_eq!(
+            file.get_parent().unwrap().get_media_size().unwrap(),
+            MEDIA_SIZE
+        );
+
+        let mut buf = [0; 29];
+        file.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"libqcow-rs qcow2 disk fixture");
+    }
+}
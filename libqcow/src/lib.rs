@@ -0,0 +1,13 @@
+#![allow(unused_imports)]
+#![allow(unused_variables)]
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate libyal_rs_common;
+
+pub mod error;
+pub mod ffi_error;
+pub mod file;
+
+#[cfg(test)]
+mod fixtures;
@@ -0,0 +1 @@
+#include "libregf.h"
\ No newline at end of file
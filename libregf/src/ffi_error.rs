@@ -0,0 +1,111 @@
+use crate::error::Error;
+use libregf_sys::FILE;
+use libyal_rs_common::ffi::AsTypeRef;
+use log::trace;
+use std::convert::TryFrom;
+use std::ffi::CStr;
+
+#[repr(C)]
+pub struct __LibregfError(isize);
+
+pub type LibregfErrorRefMut = *mut __LibregfError;
+pub type LibregfErrorRef = *const __LibregfError;
+
+#[repr(C)]
+pub struct LibregfError(LibregfErrorRefMut);
+
+impl AsTypeRef for LibregfError {
+    type Ref = LibregfErrorRef;
+    type RefMut = LibregfErrorRefMut;
+
+    #[inline]
+    fn as_type_ref(&self) -> Self::Ref {
+        self.0 as *const _
+    }
+
+    #[inline]
+    fn as_type_ref_mut(&mut self) -> Self::RefMut {
+        self.0
+    }
+
+    #[inline]
+    fn as_raw(&mut self) -> *mut Self::RefMut {
+        &mut self.0 as *mut _
+    }
+}
+
+extern "C" {
+    pub fn libregf_error_free(error: *mut LibregfErrorRefMut);
+    pub fn libregf_error_fprint(error: LibregfErrorRef, stream: *mut FILE)
+        -> ::std::os::raw::c_int;
+    pub fn libregf_error_sprint(
+        error: LibregfErrorRef,
+        string: *mut ::std::os::raw::c_char,
+        size: usize,
+    ) -> ::std::os::raw::c_int;
+    pub fn libregf_error_backtrace_fprint(
+        error: LibregfErrorRef,
+        stream: *mut FILE,
+    ) -> ::std::os::raw::c_int;
+    pub fn libregf_error_backtrace_sprint(
+        error: LibregfErrorRef,
+        string: *mut ::std::os::raw::c_char,
+        size: usize,
+    ) -> ::std::os::raw::c_int;
+}
+
+impl Drop for LibregfError {
+    fn drop(&mut self) {
+        trace!("Calling `libregf_error_free`");
+
+        unsafe { libregf_error_free(self.as_raw()) };
+    }
+}
+
+impl TryFrom<*mut __LibregfError> for Error {
+    type Error = Error;
+
+    fn try_from(err: *mut __LibregfError) -> Result<Self, Self::Error> {
+        if err.is_null() {
+            return Err(Error::Other("Error pointer cannot be NULL".to_owned()));
+        }
+
+        let mut buffer = vec![0; 1024];
+
+        let retcode = unsafe {
+            libregf_error_backtrace_sprint(err as *const _, buffer.as_mut_ptr(), buffer.len())
+        };
+
+        if retcode == -1 {
+            Err(Error::FFI("Failed to print error".to_owned()))
+        } else {
+            let repr = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+            Ok(Error::FFI(repr.to_string_lossy().to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::file::{AccessMode, File};
+
+    #[test]
+    fn test_error() {
+        let result = File::open("non-existent", AccessMode::Read);
+        assert!(result.is_err());
+
+        if let Err(e) = result {
+            if let Error::FFI(s) = e {
+                assert!(
+                    s.find("libregf_file_open").is_some(),
+                    "should contain FFI function name"
+                );
+
+                return;
+            }
+        }
+
+        panic!("Test should not reach here!");
+    }
+}
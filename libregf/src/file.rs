@@ -0,0 +1,245 @@
+use crate::error::Error;
+use crate::ffi_error::LibregfErrorRefMut;
+use crate::key::{Key, KeyRefMut};
+use libbfio_rs::handle::{Handle, HandleRef};
+use libregf_sys::{
+    LIBREGF_ACCESS_FLAGS, LIBREGF_ACCESS_FLAGS_LIBREGF_ACCESS_FLAG_READ,
+    LIBREGF_ACCESS_FLAGS_LIBREGF_ACCESS_FLAG_WRITE,
+};
+use libyal_rs_common::ffi::AsTypeRef;
+use log::error;
+use std::convert::TryFrom;
+use std::ffi::CString;
+use std::os::raw::c_int;
+use std::ptr;
+
+#[repr(C)]
+pub struct __File(isize);
+
+pub type FileRefMut = *mut __File;
+pub type FileRef = *const __File;
+
+#[repr(C)]
+pub struct File(FileRefMut);
+
+impl AsTypeRef for File {
+    type Ref = FileRef;
+    type RefMut = FileRefMut;
+
+    #[inline]
+    fn as_type_ref(&self) -> Self::Ref {
+        // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
+        self.0 as *const _
+    }
+
+    fn as_type_ref_mut(&mut self) -> Self::RefMut {
+        self.0
+    }
+
+    fn as_raw(&mut self) -> *mut Self::RefMut {
+        &mut self.0 as *mut _
+    }
+}
+
+impl File {
+    pub fn wrap_ptr(ptr: FileRefMut) -> File {
+        File(ptr)
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        let mut error = ptr::null_mut();
+
+        if unsafe { libregf_file_close(self.as_type_ref(), &mut error) } != 0 {
+            error!("`libregf_file_close` failed!");
+        }
+
+        let mut error = ptr::null_mut();
+        if unsafe { libregf_file_free(self.as_raw(), &mut error) } != 1 {
+            panic!("`libregf_file_free` failed!");
+        }
+    }
+}
+
+extern "C" {
+    pub fn libregf_file_initialize(file: *mut FileRefMut, error: *mut LibregfErrorRefMut) -> c_int;
+    pub fn libregf_file_free(file: *mut FileRefMut, error: *mut LibregfErrorRefMut) -> c_int;
+    pub fn libregf_file_signal_abort(file: FileRef, error: *mut LibregfErrorRefMut) -> c_int;
+    pub fn libregf_file_open(
+        file: FileRef,
+        filename: *const ::std::os::raw::c_char,
+        access_flags: c_int,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_file_open_file_io_handle(
+        file: FileRef,
+        file_io_handle: HandleRef,
+        access_flags: c_int,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_file_close(file: FileRef, error: *mut LibregfErrorRefMut) -> c_int;
+    pub fn libregf_file_get_root_key(
+        file: FileRef,
+        root_key: *mut KeyRefMut,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_file_get_key_by_utf8_path(
+        file: FileRef,
+        utf8_string: *const u8,
+        utf8_string_length: usize,
+        key: *mut KeyRefMut,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+}
+
+pub enum AccessMode {
+    Read,
+    Write,
+}
+
+impl AccessMode {
+    fn as_flag(&self) -> LIBREGF_ACCESS_FLAGS {
+        match self {
+            AccessMode::Read => LIBREGF_ACCESS_FLAGS_LIBREGF_ACCESS_FLAG_READ,
+            AccessMode::Write => LIBREGF_ACCESS_FLAGS_LIBREGF_ACCESS_FLAG_WRITE,
+        }
+    }
+}
+
+impl File {
+    /// Opens a registry file by filename.
+    pub fn open(filename: impl AsRef<str>, mode: AccessMode) -> Result<Self, Error> {
+        let mut handle = ptr::null_mut();
+
+        let c_string = CString::new(filename.as_ref()).map_err(Error::StringContainsNul)?;
+
+        let mut init_error = ptr::null_mut();
+
+        let retcode = unsafe { libregf_file_initialize(&mut handle as _, &mut init_error as _) };
+
+        if retcode != 1 {
+            return Err(Error::try_from(init_error)?);
+        }
+
+        let file = File::wrap_ptr(handle);
+
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libregf_file_open(
+                file.as_type_ref(),
+                c_string.as_ptr(),
+                mode.as_flag() as c_int,
+                &mut error as _,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(file)
+        }
+    }
+
+    /// Opens a registry file using a libbfio handle.
+    pub fn open_file_object(file_handle: &Handle) -> Result<Self, Error> {
+        let mut handle = ptr::null_mut();
+        let mut init_error = ptr::null_mut();
+
+        let retcode = unsafe { libregf_file_initialize(&mut handle as _, &mut init_error as _) };
+
+        if retcode != 1 {
+            return Err(Error::try_from(init_error)?);
+        }
+
+        let file = File::wrap_ptr(handle);
+
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libregf_file_open_file_io_handle(
+                file.as_type_ref(),
+                file_handle.as_type_ref(),
+                AccessMode::Read.as_flag() as c_int,
+                &mut error as _,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(file)
+        }
+    }
+
+    /// Retrieves the root key.
+    /// Returns `None` if the file has no root key.
+    pub fn get_root_key(&self) -> Result<Option<Key>, Error> {
+        let mut key = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        match unsafe { libregf_file_get_root_key(self.as_type_ref(), &mut key, &mut error) } {
+            1 => Ok(Some(Key::wrap_ptr(self, key))),
+            0 => Ok(None),
+            _ => Err(Error::try_from(error)?),
+        }
+    }
+
+    /// Retrieves the key specified by the path, using `\` as the separator.
+    /// Returns `None` if no such key exists.
+    pub fn get_key_by_path(&self, path: &str) -> Result<Option<Key>, Error> {
+        let mut key = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        match unsafe {
+            libregf_file_get_key_by_utf8_path(
+                self.as_type_ref(),
+                path.as_ptr(),
+                path.len(),
+                &mut key,
+                &mut error,
+            )
+        } {
+            1 => Ok(Some(Key::wrap_ptr(self, key))),
+            0 => Ok(None),
+            _ => Err(Error::try_from(error)?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    #[test]
+    fn test_opens_file_io_works() {
+        let handle = sample_hive_io_handle().unwrap();
+        let file_from_io = File::open_file_object(&handle).unwrap();
+
+        let root_from_io = file_from_io.get_root_key().unwrap().unwrap();
+        let root_from_disk = sample_hive()
+            .unwrap()
+            .get_root_key()
+            .unwrap()
+            .unwrap()
+            .get_name()
+            .unwrap();
+
+        assert_eq!(root_from_io.get_name().unwrap(), root_from_disk);
+    }
+
+    #[test]
+    fn test_get_key_by_path() {
+        let file = sample_hive().unwrap();
+        let key = file.get_key_by_path("ControlSet001\\Control").unwrap();
+
+        assert_eq!(key.unwrap().get_name().unwrap(), "Control");
+    }
+
+    #[test]
+    fn test_get_key_by_path_missing() {
+        let file = sample_hive().unwrap();
+
+        assert!(file.get_key_by_path("NonExistent\\Key").unwrap().is_none());
+    }
+}
@@ -0,0 +1,367 @@
+use crate::error::Error;
+use crate::ffi_error::LibregfErrorRefMut;
+use crate::file::File;
+use crate::value::{Value, ValueRefMut};
+use chrono::{DateTime, Utc};
+use libyal_rs_common::ffi::AsTypeRef;
+use std::convert::TryFrom;
+use std::fmt::{self, Debug};
+use std::os::raw::c_int;
+use std::ptr;
+
+#[repr(C)]
+pub struct __Key(isize);
+
+pub type KeyRefMut = *mut __Key;
+pub type KeyRef = *const __Key;
+
+#[repr(C)]
+pub struct Key<'a>(KeyRefMut, &'a File);
+
+impl<'a> AsTypeRef for Key<'a> {
+    type Ref = KeyRef;
+    type RefMut = KeyRefMut;
+
+    #[inline]
+    fn as_type_ref(&self) -> Self::Ref {
+        // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
+        self.0 as *const _
+    }
+
+    #[inline]
+    fn as_type_ref_mut(&mut self) -> Self::RefMut {
+        self.0
+    }
+
+    #[inline]
+    fn as_raw(&mut self) -> *mut Self::RefMut {
+        &mut self.0 as *mut _
+    }
+}
+
+impl<'a> Key<'a> {
+    pub fn wrap_ptr(file: &'a File, ptr: KeyRefMut) -> Self {
+        Key(ptr, file)
+    }
+}
+
+impl<'a> Drop for Key<'a> {
+    fn drop(&mut self) {
+        use log::trace;
+
+        let mut error = ptr::null_mut();
+
+        trace!("Calling `libregf_key_free`");
+
+        unsafe {
+            libregf_key_free(self.as_raw(), &mut error);
+        }
+
+        debug_assert!(error.is_null(), "`libregf_key_free` failed!");
+    }
+}
+
+impl<'a> Debug for Key<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("Key")
+            .field("Name", &self.get_name().unwrap_or("".to_string()))
+            .finish()
+    }
+}
+
+extern "C" {
+    pub fn libregf_key_free(key: *mut KeyRefMut, error: *mut LibregfErrorRefMut) -> c_int;
+    pub fn libregf_key_get_utf8_name_size(
+        key: KeyRef,
+        utf8_string_size: *mut usize,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_key_get_utf8_name(
+        key: KeyRef,
+        utf8_string: *mut u8,
+        utf8_string_size: usize,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_key_get_last_written_time(
+        key: KeyRef,
+        filetime: *mut u64,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_key_get_number_of_sub_keys(
+        key: KeyRef,
+        number_of_sub_keys: *mut c_int,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_key_get_sub_key_by_index(
+        key: KeyRef,
+        sub_key_index: c_int,
+        sub_key: *mut KeyRefMut,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_key_get_sub_key_by_utf8_name(
+        key: KeyRef,
+        utf8_string: *const u8,
+        utf8_string_length: usize,
+        sub_key: *mut KeyRefMut,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_key_get_number_of_values(
+        key: KeyRef,
+        number_of_values: *mut c_int,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_key_get_value_by_index(
+        key: KeyRef,
+        value_index: c_int,
+        value: *mut ValueRefMut,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_key_get_value_by_utf8_name(
+        key: KeyRef,
+        utf8_string: *const u8,
+        utf8_string_length: usize,
+        value: *mut ValueRefMut,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+}
+
+pub struct IterSubKeys<'a: 'b, 'b> {
+    handle: &'b Key<'a>,
+    num_sub_keys: u32,
+    idx: u32,
+}
+
+impl<'a: 'b, 'b> Iterator for IterSubKeys<'a, 'b> {
+    type Item = Result<Key<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.num_sub_keys {
+            let sub_key = self.handle.get_sub_key(self.idx as i32);
+            self.idx += 1;
+
+            return Some(sub_key);
+        }
+
+        None
+    }
+}
+
+pub struct IterValues<'a: 'b, 'b> {
+    handle: &'b Key<'a>,
+    num_values: u32,
+    idx: u32,
+}
+
+impl<'a: 'b, 'b> Iterator for IterValues<'a, 'b> {
+    type Item = Result<Value<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.num_values {
+            let value = self.handle.get_value(self.idx as i32);
+            self.idx += 1;
+
+            return Some(value);
+        }
+
+        None
+    }
+}
+
+impl<'a> Key<'a> {
+    /// Retrieves the key name.
+    pub fn get_name(&self) -> Result<String, Error> {
+        get_sized_utf8_string!(
+            self,
+            libregf_key_get_utf8_name_size,
+            libregf_key_get_utf8_name
+        )
+    }
+
+    /// Retrieves the last written date and time.
+    pub fn get_last_written_time(&self) -> Result<Option<DateTime<Utc>>, Error> {
+        get_date_field!(self, libregf_key_get_last_written_time)
+    }
+
+    /// Retrieves the number of sub keys.
+    pub fn get_number_of_sub_keys(&self) -> Result<c_int, Error> {
+        let mut number_of_sub_keys = 0;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libregf_key_get_number_of_sub_keys(
+                self.as_type_ref(),
+                &mut number_of_sub_keys,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(number_of_sub_keys)
+        }
+    }
+
+    /// Retrieves a specific sub key.
+    pub fn get_sub_key(&self, sub_key_index: i32) -> Result<Key<'a>, Error> {
+        let mut sub_key = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libregf_key_get_sub_key_by_index(
+                self.as_type_ref(),
+                sub_key_index,
+                &mut sub_key,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(Key::wrap_ptr(self.1, sub_key))
+        }
+    }
+
+    /// Retrieves the sub key specified by the name.
+    /// Returns `None` if no such sub key exists.
+    pub fn get_sub_key_by_name(&self, name: &str) -> Result<Option<Key<'a>>, Error> {
+        let mut sub_key = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        match unsafe {
+            libregf_key_get_sub_key_by_utf8_name(
+                self.as_type_ref(),
+                name.as_ptr(),
+                name.len(),
+                &mut sub_key,
+                &mut error,
+            )
+        } {
+            1 => Ok(Some(Key::wrap_ptr(self.1, sub_key))),
+            0 => Ok(None),
+            _ => Err(Error::try_from(error)?),
+        }
+    }
+
+    pub fn iter_sub_keys<'c>(&'c self) -> Result<IterSubKeys<'a, 'c>, Error> {
+        let number_of_sub_keys = self.get_number_of_sub_keys()? as u32;
+
+        Ok(IterSubKeys {
+            handle: self,
+            num_sub_keys: number_of_sub_keys,
+            idx: 0,
+        })
+    }
+
+    /// Retrieves the number of values.
+    pub fn get_number_of_values(&self) -> Result<c_int, Error> {
+        let mut number_of_values = 0;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libregf_key_get_number_of_values(self.as_type_ref(), &mut number_of_values, &mut error)
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(number_of_values)
+        }
+    }
+
+    /// Retrieves a specific value.
+    pub fn get_value(&self, value_index: i32) -> Result<Value<'a>, Error> {
+        let mut value = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libregf_key_get_value_by_index(self.as_type_ref(), value_index, &mut value, &mut error)
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(Value::wrap_ptr(self.1, value))
+        }
+    }
+
+    /// Retrieves the value specified by the name.
+    /// Returns `None` if no such value exists.
+    pub fn get_value_by_name(&self, name: &str) -> Result<Option<Value<'a>>, Error> {
+        let mut value = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        match unsafe {
+            libregf_key_get_value_by_utf8_name(
+                self.as_type_ref(),
+                name.as_ptr(),
+                name.len(),
+                &mut value,
+                &mut error,
+            )
+        } {
+            1 => Ok(Some(Value::wrap_ptr(self.1, value))),
+            0 => Ok(None),
+            _ => Err(Error::try_from(error)?),
+        }
+    }
+
+    pub fn iter_values<'c>(&'c self) -> Result<IterValues<'a, 'c>, Error> {
+        let number_of_values = self.get_number_of_values()? as u32;
+
+        Ok(IterValues {
+            handle: self,
+            num_values: number_of_values,
+            idx: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    #[test]
+    fn test_iter_sub_keys() {
+        let file = sample_hive().unwrap();
+        let root = file.get_root_key().unwrap().unwrap();
+
+        let names: Vec<String> = root
+            .iter_sub_keys()
+            .unwrap()
+            .map(|k| k.unwrap().get_name().unwrap())
+            .collect();
+
+        assert!(names.iter().any(|name| name == "Select"));
+    }
+
+    #[test]
+    fn test_read_select_current() {
+        let file = sample_hive().unwrap();
+        let select = file.get_key_by_path("Select").unwrap().unwrap();
+
+        let current = select.get_value_by_name("Current").unwrap().unwrap();
+
+        assert!(current.get_value_32bit().unwrap() >= 1);
+        assert!(select.get_last_written_time().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_iter_values() {
+        let file = sample_hive().unwrap();
+        let select = file.get_key_by_path("Select").unwrap().unwrap();
+
+        let values: Vec<(String, u32)> = select
+            .iter_values()
+            .unwrap()
+            .map(|v| {
+                let v = v.unwrap();
+                (v.get_name().unwrap(), v.get_value_32bit().unwrap())
+            })
+            .collect();
+
+        assert_eq!(
+            values.len(),
+            select.get_number_of_values().unwrap() as usize
+        );
+        assert!(values.contains(&("LastKnownGood".to_string(), 2)));
+    }
+}
@@ -0,0 +1,16 @@
+#![allow(unused_imports)]
+#![allow(unused_variables)]
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate libyal_rs_common;
+
+pub mod error;
+pub mod ffi_error;
+pub mod file;
+pub mod key;
+mod utils;
+pub mod value;
+
+#[cfg(test)]
+mod fixtures;
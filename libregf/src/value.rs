@@ -0,0 +1,229 @@
+use crate::error::Error;
+use crate::ffi_error::LibregfErrorRefMut;
+use crate::file::File;
+use libyal_rs_common::ffi::AsTypeRef;
+use std::convert::TryFrom;
+use std::fmt::{self, Debug};
+use std::os::raw::c_int;
+use std::ptr;
+
+#[repr(C)]
+pub struct __Value(isize);
+
+pub type ValueRefMut = *mut __Value;
+pub type ValueRef = *const __Value;
+
+#[repr(C)]
+pub struct Value<'a>(ValueRefMut, &'a File);
+
+impl<'a> AsTypeRef for Value<'a> {
+    type Ref = ValueRef;
+    type RefMut = ValueRefMut;
+
+    #[inline]
+    fn as_type_ref(&self) -> Self::Ref {
+        // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
+        self.0 as *const _
+    }
+
+    #[inline]
+    fn as_type_ref_mut(&mut self) -> Self::RefMut {
+        self.0
+    }
+
+    #[inline]
+    fn as_raw(&mut self) -> *mut Self::RefMut {
+        &mut self.0 as *mut _
+    }
+}
+
+impl<'a> Value<'a> {
+    pub fn wrap_ptr(file: &'a File, ptr: ValueRefMut) -> Self {
+        Value(ptr, file)
+    }
+}
+
+impl<'a> Drop for Value<'a> {
+    fn drop(&mut self) {
+        use log::trace;
+
+        let mut error = ptr::null_mut();
+
+        trace!("Calling `libregf_value_free`");
+
+        unsafe {
+            libregf_value_free(self.as_raw(), &mut error);
+        }
+
+        debug_assert!(error.is_null(), "`libregf_value_free` failed!");
+    }
+}
+
+impl<'a> Debug for Value<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("Value")
+            .field("Name", &self.get_name().unwrap_or("".to_string()))
+            .field(
+                "Type",
+                &self
+                    .get_value_type()
+                    .and_then(|t| Ok(format!("{:?}", t)))
+                    .unwrap_or_else(|_| "".to_string()),
+            )
+            .finish()
+    }
+}
+
+#[derive(PartialOrd, PartialEq, Debug, Clone)]
+#[repr(C)]
+pub enum ValueType {
+    Undefined = 0,
+    String = 1,
+    ExpandableString = 2,
+    BinaryData = 3,
+    Integer32BitLittleEndian = 4,
+    Integer32BitBigEndian = 5,
+    SymbolicLink = 6,
+    MultiValueString = 7,
+    ResourceList = 8,
+    FullResourceDescriptor = 9,
+    ResourceRequirementsList = 10,
+    Integer64BitLittleEndian = 11,
+}
+
+impl TryFrom<u32> for ValueType {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ValueType::Undefined),
+            1 => Ok(ValueType::String),
+            2 => Ok(ValueType::ExpandableString),
+            3 => Ok(ValueType::BinaryData),
+            4 => Ok(ValueType::Integer32BitLittleEndian),
+            5 => Ok(ValueType::Integer32BitBigEndian),
+            6 => Ok(ValueType::SymbolicLink),
+            7 => Ok(ValueType::MultiValueString),
+            8 => Ok(ValueType::ResourceList),
+            9 => Ok(ValueType::FullResourceDescriptor),
+            10 => Ok(ValueType::ResourceRequirementsList),
+            11 => Ok(ValueType::Integer64BitLittleEndian),
+            _ => Err(Error::UnknownValueTypeEnumVariant(value)),
+        }
+    }
+}
+
+extern "C" {
+    pub fn libregf_value_free(value: *mut ValueRefMut, error: *mut LibregfErrorRefMut) -> c_int;
+    pub fn libregf_value_get_utf8_name_size(
+        value: ValueRef,
+        utf8_string_size: *mut usize,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_value_get_utf8_name(
+        value: ValueRef,
+        utf8_string: *mut u8,
+        utf8_string_size: usize,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_value_get_value_type(
+        value: ValueRef,
+        value_type: *mut u32,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_value_get_value_32bit(
+        value: ValueRef,
+        value_32bit: *mut u32,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_value_get_value_64bit(
+        value: ValueRef,
+        value_64bit: *mut u64,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_value_get_value_utf8_string_size(
+        value: ValueRef,
+        utf8_string_size: *mut usize,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_value_get_value_utf8_string(
+        value: ValueRef,
+        utf8_string: *mut u8,
+        utf8_string_size: usize,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_value_get_value_binary_data_size(
+        value: ValueRef,
+        size: *mut usize,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+    pub fn libregf_value_get_value_binary_data(
+        value: ValueRef,
+        binary_data: *mut u8,
+        size: usize,
+        error: *mut LibregfErrorRefMut,
+    ) -> c_int;
+}
+
+impl<'a> Value<'a> {
+    /// Retrieves the value name.
+    pub fn get_name(&self) -> Result<String, Error> {
+        get_sized_utf8_string!(
+            self,
+            libregf_value_get_utf8_name_size,
+            libregf_value_get_utf8_name
+        )
+    }
+
+    /// Retrieves the value type.
+    pub fn get_value_type(&self) -> Result<ValueType, Error> {
+        let mut type_as_num = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe { libregf_value_get_value_type(self.as_type_ref(), &mut type_as_num, &mut error) }
+            != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(ValueType::try_from(type_as_num)?)
+        }
+    }
+
+    /// Retrieves the 32-bit value.
+    pub fn get_value_32bit(&self) -> Result<u32, Error> {
+        let mut value_32bit = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libregf_value_get_value_32bit(self.as_type_ref(), &mut value_32bit, &mut error)
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(value_32bit)
+        }
+    }
+
+    /// Retrieves the 64-bit value.
+    pub fn get_value_64bit(&self) -> Result<u64, Error> {
+        get_u64_field!(self, libregf_value_get_value_64bit)
+    }
+
+    /// Retrieves the string value.
+    pub fn get_value_utf8_string(&self) -> Result<String, Error> {
+        get_sized_utf8_string!(
+            self,
+            libregf_value_get_value_utf8_string_size,
+            libregf_value_get_value_utf8_string
+        )
+    }
+
+    /// Retrieves the binary data value.
+    pub fn get_value_binary(&self) -> Result<Vec<u8>, Error> {
+        get_sized_bytes!(
+            self,
+            libregf_value_get_value_binary_data_size,
+            libregf_value_get_value_binary_data
+        )
+    }
+}
@@ -0,0 +1 @@
+#include "libscca.h"
\ No newline at end of file
@@ -0,0 +1,111 @@
+use crate::error::Error;
+use libscca_sys::FILE;
+use libyal_rs_common::ffi::AsTypeRef;
+use log::trace;
+use std::convert::TryFrom;
+use std::ffi::CStr;
+
+#[repr(C)]
+pub struct __LibsccaError(isize);
+
+pub type LibsccaErrorRefMut = *mut __LibsccaError;
+pub type LibsccaErrorRef = *const __LibsccaError;
+
+#[repr(C)]
+pub struct LibsccaError(LibsccaErrorRefMut);
+
+impl AsTypeRef for LibsccaError {
+    type Ref = LibsccaErrorRef;
+    type RefMut = LibsccaErrorRefMut;
+
+    #[inline]
+    fn as_type_ref(&self) -> Self::Ref {
+        self.0 as *const _
+    }
+
+    #[inline]
+    fn as_type_ref_mut(&mut self) -> Self::RefMut {
+        self.0
+    }
+
+    #[inline]
+    fn as_raw(&mut self) -> *mut Self::RefMut {
+        &mut self.0 as *mut _
+    }
+}
+
+extern "C" {
+    pub fn libscca_error_free(error: *mut LibsccaErrorRefMut);
+    pub fn libscca_error_fprint(error: LibsccaErrorRef, stream: *mut FILE)
+        -> ::std::os::raw::c_int;
+    pub fn libscca_error_sprint(
+        error: LibsccaErrorRef,
+        string: *mut ::std::os::raw::c_char,
+        size: usize,
+    ) -> ::std::os::raw::c_int;
+    pub fn libscca_error_backtrace_fprint(
+        error: LibsccaErrorRef,
+        stream: *mut FILE,
+    ) -> ::std::os::raw::c_int;
+    pub fn libscca_error_backtrace_sprint(
+        error: LibsccaErrorRef,
+        string: *mut ::std::os::raw::c_char,
+        size: usize,
+    ) -> ::std::os::raw::c_int;
+}
+
+impl Drop for LibsccaError {
+    fn drop(&mut self) {
+        trace!("Calling `libscca_error_free`");
+
+        unsafe { libscca_error_free(self.as_raw()) };
+    }
+}
+
+impl TryFrom<*mut __LibsccaError> for Error {
+    type Error = Error;
+
+    fn try_from(err: *mut __LibsccaError) -> Result<Self, Self::Error> {
+        if err.is_null() {
+            return Err(Error::Other("Error pointer cannot be NULL".to_owned()));
+        }
+
+        let mut buffer = vec![0; 1024];
+
+        let retcode = unsafe {
+            libscca_error_backtrace_sprint(err as *const _, buffer.as_mut_ptr(), buffer.len())
+        };
+
+        if retcode == -1 {
+            Err(Error::FFI("Failed to print error".to_owned()))
+        } else {
+            let repr = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+            Ok(Error::FFI(repr.to_string_lossy().to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::file::{AccessMode, File};
+
+    #[test]
+    fn test_error() {
+        let result = File::open("non-existent", AccessMode::Read);
+        assert!(result.is_err());
+
+        if let Err(e) = result {
+            if let Error::FFI(s) = e {
+                assert!(
+                    s.find("libscca_file_open").is_some(),
+                    "should contain FFI function name"
+                );
+
+                return;
+            }
+        }
+
+        panic!("Test should not reach here!");
+    }
+}
@@ -0,0 +1,473 @@
+use crate::error::Error;
+use crate::ffi_error::LibsccaErrorRefMut;
+use crate::volume_information::{VolumeInformation, VolumeInformationRefMut};
+use chrono::{DateTime, Utc};
+use libbfio_rs::handle::{Handle, HandleRef};
+use libscca_sys::{
+    LIBSCCA_ACCESS_FLAGS, LIBSCCA_ACCESS_FLAGS_LIBSCCA_ACCESS_FLAG_READ,
+    LIBSCCA_ACCESS_FLAGS_LIBSCCA_ACCESS_FLAG_WRITE,
+};
+use libyal_rs_common::ffi::AsTypeRef;
+use log::error;
+use std::convert::TryFrom;
+use std::ffi::CString;
+use std::os::raw::c_int;
+use std::ptr;
+
+#[repr(C)]
+pub struct __File(isize);
+
+pub type FileRefMut = *mut __File;
+pub type FileRef = *const __File;
+
+#[repr(C)]
+pub struct File(FileRefMut);
+
+impl AsTypeRef for File {
+    type Ref = FileRef;
+    type RefMut = FileRefMut;
+
+    #[inline]
+    fn as_type_ref(&self) -> Self::Ref {
+        // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
+        self.0 as *const _
+    }
+
+    fn as_type_ref_mut(&mut self) -> Self::RefMut {
+        self.0
+    }
+
+    fn as_raw(&mut self) -> *mut Self::RefMut {
+        &mut self.0 as *mut _
+    }
+}
+
+impl File {
+    pub fn wrap_ptr(ptr: FileRefMut) -> File {
+        File(ptr)
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        let mut error = ptr::null_mut();
+
+        if unsafe { libscca_file_close(self.as_type_ref(), &mut error) } != 0 {
+            error!("`libscca_file_close` failed!");
+        }
+
+        let mut error = ptr::null_mut();
+        if unsafe { libscca_file_free(self.as_raw(), &mut error) } != 1 {
+            panic!("`libscca_file_free` failed!");
+        }
+    }
+}
+
+extern "C" {
+    pub fn libscca_file_initialize(file: *mut FileRefMut, error: *mut LibsccaErrorRefMut) -> c_int;
+    pub fn libscca_file_free(file: *mut FileRefMut, error: *mut LibsccaErrorRefMut) -> c_int;
+    pub fn libscca_file_signal_abort(file: FileRef, error: *mut LibsccaErrorRefMut) -> c_int;
+    pub fn libscca_file_open(
+        file: FileRef,
+        filename: *const ::std::os::raw::c_char,
+        access_flags: c_int,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+    pub fn libscca_file_open_file_io_handle(
+        file: FileRef,
+        file_io_handle: HandleRef,
+        access_flags: c_int,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+    pub fn libscca_file_close(file: FileRef, error: *mut LibsccaErrorRefMut) -> c_int;
+    pub fn libscca_file_get_format_version(
+        file: FileRef,
+        format_version: *mut u32,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+    pub fn libscca_file_get_prefetch_hash(
+        file: FileRef,
+        prefetch_hash: *mut u32,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+    pub fn libscca_file_get_utf8_executable_filename_size(
+        file: FileRef,
+        utf8_string_size: *mut usize,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+    pub fn libscca_file_get_utf8_executable_filename(
+        file: FileRef,
+        utf8_string: *mut u8,
+        utf8_string_size: usize,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+    pub fn libscca_file_get_run_count(
+        file: FileRef,
+        run_count: *mut u32,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+    pub fn libscca_file_get_last_run_time(
+        file: FileRef,
+        last_run_time_index: c_int,
+        filetime: *mut u64,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+    pub fn libscca_file_get_number_of_filenames(
+        file: FileRef,
+        number_of_filenames: *mut c_int,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+    pub fn libscca_file_get_utf8_filename_size(
+        file: FileRef,
+        filename_index: c_int,
+        utf8_string_size: *mut usize,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+    pub fn libscca_file_get_utf8_filename(
+        file: FileRef,
+        filename_index: c_int,
+        utf8_string: *mut u8,
+        utf8_string_size: usize,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+    pub fn libscca_file_get_number_of_volumes(
+        file: FileRef,
+        number_of_volumes: *mut c_int,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+    pub fn libscca_file_get_volume_information(
+        file: FileRef,
+        volume_index: c_int,
+        volume_information: *mut VolumeInformationRefMut,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+}
+
+pub enum AccessMode {
+    Read,
+    Write,
+}
+
+impl AccessMode {
+    fn as_flag(&self) -> LIBSCCA_ACCESS_FLAGS {
+        match self {
+            AccessMode::Read => LIBSCCA_ACCESS_FLAGS_LIBSCCA_ACCESS_FLAG_READ,
+            AccessMode::Write => LIBSCCA_ACCESS_FLAGS_LIBSCCA_ACCESS_FLAG_WRITE,
+        }
+    }
+}
+
+pub struct IterFilenames<'a> {
+    handle: &'a File,
+    number_of_filenames: u32,
+    idx: u32,
+}
+
+impl<'a> Iterator for IterFilenames<'a> {
+    type Item = Result<String, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.number_of_filenames {
+            let filename = self.handle.get_filename(self.idx as i32);
+            self.idx += 1;
+
+            return Some(filename);
+        }
+
+        None
+    }
+}
+
+pub struct IterVolumes<'a> {
+    handle: &'a File,
+    number_of_volumes: u32,
+    idx: u32,
+}
+
+impl<'a> Iterator for IterVolumes<'a> {
+    type Item = Result<VolumeInformation<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.number_of_volumes {
+            let volume = self.handle.get_volume_information(self.idx as i32);
+            self.idx += 1;
+
+            return Some(volume);
+        }
+
+        None
+    }
+}
+
+impl File {
+    /// Opens a prefetch file by filename.
+    pub fn open(filename: impl AsRef<str>, mode: AccessMode) -> Result<Self, Error> {
+        let mut handle = ptr::null_mut();
+
+        let c_string = CString::new(filename.as_ref()).map_err(Error::StringContainsNul)?;
+
+        let mut init_error = ptr::null_mut();
+
+        let retcode = unsafe { libscca_file_initialize(&mut handle as _, &mut init_error as _) };
+
+        if retcode != 1 {
+            return Err(Error::try_from(init_error)?);
+        }
+
+        let file = File::wrap_ptr(handle);
+
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libscca_file_open(
+                file.as_type_ref(),
+                c_string.as_ptr(),
+                mode.as_flag() as c_int,
+                &mut error as _,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(file)
+        }
+    }
+
+    /// Opens a prefetch file using a libbfio handle.
+    pub fn open_file_object(file_handle: &Handle) -> Result<Self, Error> {
+        let mut handle = ptr::null_mut();
+        let mut init_error = ptr::null_mut();
+
+        let retcode = unsafe { libscca_file_initialize(&mut handle as _, &mut init_error as _) };
+
+        if retcode != 1 {
+            return Err(Error::try_from(init_error)?);
+        }
+
+        let file = File::wrap_ptr(handle);
+
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libscca_file_open_file_io_handle(
+                file.as_type_ref(),
+                file_handle.as_type_ref(),
+                AccessMode::Read.as_flag() as c_int,
+                &mut error as _,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(file)
+        }
+    }
+
+    /// Retrieves the format version.
+    pub fn get_format_version(&self) -> Result<u32, Error> {
+        let mut format_version = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libscca_file_get_format_version(self.as_type_ref(), &mut format_version, &mut error)
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(format_version)
+        }
+    }
+
+    /// Retrieves the prefetch hash.
+    pub fn get_prefetch_hash(&self) -> Result<u32, Error> {
+        let mut prefetch_hash = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libscca_file_get_prefetch_hash(self.as_type_ref(), &mut prefetch_hash, &mut error)
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(prefetch_hash)
+        }
+    }
+
+    /// Retrieves the executable filename.
+    pub fn get_executable_filename(&self) -> Result<String, Error> {
+        get_sized_utf8_string!(
+            self,
+            libscca_file_get_utf8_executable_filename_size,
+            libscca_file_get_utf8_executable_filename
+        )
+    }
+
+    /// Retrieves the run count.
+    pub fn get_run_count(&self) -> Result<u32, Error> {
+        let mut run_count = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe { libscca_file_get_run_count(self.as_type_ref(), &mut run_count, &mut error) }
+            != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(run_count)
+        }
+    }
+
+    /// Retrieves a specific last run time.
+    /// Prefetch format version 26 and later store up to 8 last run times.
+    pub fn get_last_run_time(&self, index: i32) -> Result<Option<DateTime<Utc>>, Error> {
+        use crate::utils::datetime_from_filetime;
+
+        let mut filetime = 0_u64;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libscca_file_get_last_run_time(self.as_type_ref(), index, &mut filetime, &mut error)
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else if filetime > 0 {
+            Ok(Some(datetime_from_filetime(filetime)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Retrieves the number of filenames.
+    pub fn get_number_of_filenames(&self) -> Result<c_int, Error> {
+        let mut number_of_filenames = 0;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libscca_file_get_number_of_filenames(
+                self.as_type_ref(),
+                &mut number_of_filenames,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(number_of_filenames)
+        }
+    }
+
+    /// Retrieves a specific filename.
+    pub fn get_filename(&self, filename_index: i32) -> Result<String, Error> {
+        get_sized_utf8_string_by_index!(
+            self,
+            libscca_file_get_utf8_filename_size,
+            libscca_file_get_utf8_filename,
+            filename_index
+        )
+    }
+
+    pub fn iter_filenames(&self) -> Result<IterFilenames, Error> {
+        Ok(IterFilenames {
+            handle: self,
+            number_of_filenames: self.get_number_of_filenames()? as u32,
+            idx: 0,
+        })
+    }
+
+    /// Retrieves the number of volumes.
+    pub fn get_number_of_volumes(&self) -> Result<c_int, Error> {
+        let mut number_of_volumes = 0;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libscca_file_get_number_of_volumes(
+                self.as_type_ref(),
+                &mut number_of_volumes,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(number_of_volumes)
+        }
+    }
+
+    /// Retrieves a specific volume information.
+    pub fn get_volume_information(&self, volume_index: i32) -> Result<VolumeInformation, Error> {
+        let mut volume_information = ptr::null_mut();
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libscca_file_get_volume_information(
+                self.as_type_ref(),
+                volume_index,
+                &mut volume_information,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(VolumeInformation::wrap_ptr(self, volume_information))
+        }
+    }
+
+    pub fn iter_volumes(&self) -> Result<IterVolumes, Error> {
+        Ok(IterVolumes {
+            handle: self,
+            number_of_volumes: self.get_number_of_volumes()? as u32,
+            idx: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    #[test]
+    fn test_opens_file_io_works() {
+        let handle = sample_prefetch_io_handle().unwrap();
+        let file_from_io = File::open_file_object(&handle).unwrap();
+
+        assert_eq!(
+            sample_prefetch()
+                .unwrap()
+                .get_executable_filename()
+                .unwrap(),
+            file_from_io.get_executable_filename().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_executable_filename() {
+        let file = sample_prefetch().unwrap();
+
+        assert_eq!(file.get_executable_filename().unwrap(), "CMD.EXE");
+    }
+
+    #[test]
+    fn test_iter_filenames_and_volumes() {
+        let file = sample_prefetch().unwrap();
+
+        let filenames: Vec<String> = file.iter_filenames().unwrap().map(|f| f.unwrap()).collect();
+        assert_eq!(
+            filenames.len(),
+            file.get_number_of_filenames().unwrap() as usize
+        );
+
+        assert_eq!(
+            filenames[0],
+            "\\DEVICE\\HARDDISKVOLUME1\\WINDOWS\\SYSTEM32\\NTDLL.DLL"
+        );
+
+        let volumes: Vec<VolumeInformation> =
+            file.iter_volumes().unwrap().map(|v| v.unwrap()).collect();
+        assert_eq!(volumes.len(), 1);
+        assert_eq!(
+            volumes[0].get_device_path().unwrap(),
+            "\\DEVICE\\HARDDISKVOLUME1"
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use crate::error::Error;
+use crate::file::{AccessMode, File};
+use env_logger;
+use lazy_static::lazy_static;
+use libbfio_rs::handle::{Handle, LibbfioAccessFlags};
+use std::path::PathBuf;
+
+lazy_static! {
+    static ref LOGGER_INIT: () = {
+        env_logger::init();
+    };
+}
+
+pub fn sample_prefetch_path() -> String {
+    let this_file = file!();
+    let sample = PathBuf::from(this_file)
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("examples")
+        .join("CMD.EXE-087B4001.pf");
+
+    sample.to_str().unwrap().to_string()
+}
+
+pub fn sample_prefetch_io_handle() -> Result<Handle, Error> {
+    let prefetch_path = sample_prefetch_path();
+    Ok(Handle::open_file(prefetch_path, LibbfioAccessFlags::Read).expect("libbfio failed"))
+}
+
+pub fn sample_prefetch() -> Result<File, Error> {
+    let prefetch_path = sample_prefetch_path();
+    File::open(&prefetch_path, AccessMode::Read)
+}
@@ -0,0 +1,15 @@
+#![allow(unused_imports)]
+#![allow(unused_variables)]
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate libyal_rs_common;
+
+pub mod error;
+pub mod ffi_error;
+pub mod file;
+mod utils;
+pub mod volume_information;
+
+#[cfg(test)]
+mod fixtures;
@@ -0,0 +1,136 @@
+use crate::error::Error;
+use crate::ffi_error::LibsccaErrorRefMut;
+use crate::file::File;
+use chrono::{DateTime, Utc};
+use libyal_rs_common::ffi::AsTypeRef;
+use std::convert::TryFrom;
+use std::fmt::{self, Debug};
+use std::os::raw::c_int;
+use std::ptr;
+
+#[repr(C)]
+pub struct __VolumeInformation(isize);
+
+pub type VolumeInformationRefMut = *mut __VolumeInformation;
+pub type VolumeInformationRef = *const __VolumeInformation;
+
+#[repr(C)]
+pub struct VolumeInformation<'a>(VolumeInformationRefMut, &'a File);
+
+impl<'a> AsTypeRef for VolumeInformation<'a> {
+    type Ref = VolumeInformationRef;
+    type RefMut = VolumeInformationRefMut;
+
+    #[inline]
+    fn as_type_ref(&self) -> Self::Ref {
+        // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
+        self.0 as *const _
+    }
+
+    #[inline]
+    fn as_type_ref_mut(&mut self) -> Self::RefMut {
+        self.0
+    }
+
+    #[inline]
+    fn as_raw(&mut self) -> *mut Self::RefMut {
+        &mut self.0 as *mut _
+    }
+}
+
+impl<'a> VolumeInformation<'a> {
+    pub fn wrap_ptr(file: &'a File, ptr: VolumeInformationRefMut) -> Self {
+        VolumeInformation(ptr, file)
+    }
+}
+
+impl<'a> Drop for VolumeInformation<'a> {
+    fn drop(&mut self) {
+        use log::trace;
+
+        let mut error = ptr::null_mut();
+
+        trace!("Calling `libscca_volume_information_free`");
+
+        unsafe {
+            libscca_volume_information_free(self.as_raw(), &mut error);
+        }
+
+        debug_assert!(error.is_null(), "`libscca_volume_information_free` failed!");
+    }
+}
+
+impl<'a> Debug for VolumeInformation<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("VolumeInformation")
+            .field(
+                "DevicePath",
+                &self.get_device_path().unwrap_or("".to_string()),
+            )
+            .field("SerialNumber", &self.get_serial_number().unwrap_or(0))
+            .finish()
+    }
+}
+
+extern "C" {
+    pub fn libscca_volume_information_free(
+        volume_information: *mut VolumeInformationRefMut,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+    pub fn libscca_volume_information_get_creation_time(
+        volume_information: VolumeInformationRef,
+        filetime: *mut u64,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+    pub fn libscca_volume_information_get_serial_number(
+        volume_information: VolumeInformationRef,
+        serial_number: *mut u32,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+    pub fn libscca_volume_information_get_utf8_device_path_size(
+        volume_information: VolumeInformationRef,
+        utf8_string_size: *mut usize,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+    pub fn libscca_volume_information_get_utf8_device_path(
+        volume_information: VolumeInformationRef,
+        utf8_string: *mut u8,
+        utf8_string_size: usize,
+        error: *mut LibsccaErrorRefMut,
+    ) -> c_int;
+}
+
+impl<'a> VolumeInformation<'a> {
+    /// Retrieves the volume creation time.
+    pub fn get_creation_time(&self) -> Result<Option<DateTime<Utc>>, Error> {
+        get_date_field!(self, libscca_volume_information_get_creation_time)
+    }
+
+    /// Retrieves the volume serial number.
+    pub fn get_serial_number(&self) -> Result<u32, Error> {
+        let mut serial_number = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libscca_volume_information_get_serial_number(
+                self.as_type_ref(),
+                &mut serial_number,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(serial_number)
+        }
+    }
+
+    /// Retrieves the volume device path.
+    pub fn get_device_path(&self) -> Result<String, Error> {
+        get_sized_utf8_string!(
+            self,
+            libscca_volume_information_get_utf8_device_path_size,
+            libscca_volume_information_get_utf8_device_path
+        )
+    }
+}
@@ -0,0 +1,471 @@
+use crate::error::Error;
+use crate::ffi_error::LibvhdiErrorRefMut;
+use libbfio_rs::handle::{Handle, HandleRef};
+use libvhdi_sys::{
+    LIBVHDI_ACCESS_FLAGS, LIBVHDI_ACCESS_FLAGS_LIBVHDI_ACCESS_FLAG_READ,
+    LIBVHDI_ACCESS_FLAGS_LIBVHDI_ACCESS_FLAG_WRITE,
+};
+use libyal_rs_common::ffi::AsTypeRef;
+use log::error;
+use std::convert::TryFrom;
+use std::ffi::{c_void, CString};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::os::raw::c_int;
+use std::path::Path;
+use std::ptr;
+
+#[repr(C)]
+pub struct __File(isize);
+
+pub type FileRefMut = *mut __File;
+pub type FileRef = *const __File;
+
+/// A VHD or VHDX image, read as the disk it contains.
+pub struct File {
+    ptr: FileRefMut,
+    /// The parent image of a differencing disk, which must outlive this file.
+    parent: Option<Box<File>>,
+}
+
+impl AsTypeRef for File {
+    type Ref = FileRef;
+    type RefMut = FileRefMut;
+
+    #[inline]
+    fn as_type_ref(&self) -> Self::Ref {
+        // https://users.rust-lang.org/t/is-it-ub-to-convert-t-to-mut-t/16238/4
+        self.ptr as *const _
+    }
+
+    fn as_type_ref_mut(&mut self) -> Self::RefMut {
+        self.ptr
+    }
+
+    fn as_raw(&mut self) -> *mut Self::RefMut {
+        &mut self.ptr as *mut _
+    }
+}
+
+impl File {
+    pub fn wrap_ptr(ptr: FileRefMut) -> File {
+        File { ptr, parent: None }
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        let mut error = ptr::null_mut();
+
+        if unsafe { libvhdi_file_close(self.as_type_ref(), &mut error) } != 0 {
+            error!("`libvhdi_file_close` failed!");
+        }
+
+        let mut error = ptr::null_mut();
+        if unsafe { libvhdi_file_free(self.as_raw(), &mut error) } != 1 {
+            error!("`libvhdi_file_free` failed!");
+        }
+    }
+}
+
+extern "C" {
+    pub fn libvhdi_file_initialize(file: *mut FileRefMut, error: *mut LibvhdiErrorRefMut) -> c_int;
+    pub fn libvhdi_file_free(file: *mut FileRefMut, error: *mut LibvhdiErrorRefMut) -> c_int;
+    pub fn libvhdi_file_signal_abort(file: FileRef, error: *mut LibvhdiErrorRefMut) -> c_int;
+    pub fn libvhdi_file_open(
+        file: FileRef,
+        filename: *const ::std::os::raw::c_char,
+        access_flags: c_int,
+        error: *mut LibvhdiErrorRefMut,
+    ) -> c_int;
+    pub fn libvhdi_file_open_file_io_handle(
+        file: FileRef,
+        file_io_handle: HandleRef,
+        access_flags: c_int,
+        error: *mut LibvhdiErrorRefMut,
+    ) -> c_int;
+    pub fn libvhdi_file_close(file: FileRef, error: *mut LibvhdiErrorRefMut) -> c_int;
+    pub fn libvhdi_file_set_parent_file(
+        file: FileRef,
+        parent_file: FileRef,
+        error: *mut LibvhdiErrorRefMut,
+    ) -> c_int;
+    pub fn libvhdi_file_read_buffer(
+        file: FileRef,
+        buffer: *mut c_void,
+        buffer_size: usize,
+        error: *mut LibvhdiErrorRefMut,
+    ) -> isize;
+    pub fn libvhdi_file_seek_offset(
+        file: FileRef,
+        offset: i64,
+        whence: c_int,
+        error: *mut LibvhdiErrorRefMut,
+    ) -> i64;
+    pub fn libvhdi_file_get_format_version(
+        file: FileRef,
+        major_version: *mut u16,
+        minor_version: *mut u16,
+        error: *mut LibvhdiErrorRefMut,
+    ) -> c_int;
+    pub fn libvhdi_file_get_disk_type(
+        file: FileRef,
+        disk_type: *mut u32,
+        error: *mut LibvhdiErrorRefMut,
+    ) -> c_int;
+    pub fn libvhdi_file_get_media_size(
+        file: FileRef,
+        media_size: *mut u64,
+        error: *mut LibvhdiErrorRefMut,
+    ) -> c_int;
+    pub fn libvhdi_file_get_utf8_parent_filename_size(
+        file: FileRef,
+        utf8_string_size: *mut usize,
+        error: *mut LibvhdiErrorRefMut,
+    ) -> c_int;
+    pub fn libvhdi_file_get_utf8_parent_filename(
+        file: FileRef,
+        utf8_string: *mut u8,
+        utf8_string_size: usize,
+        error: *mut LibvhdiErrorRefMut,
+    ) -> c_int;
+}
+
+pub enum AccessMode {
+    Read,
+    Write,
+}
+
+impl AccessMode {
+    fn as_flag(&self) -> LIBVHDI_ACCESS_FLAGS {
+        match self {
+            AccessMode::Read => LIBVHDI_ACCESS_FLAGS_LIBVHDI_ACCESS_FLAG_READ,
+            AccessMode::Write => LIBVHDI_ACCESS_FLAGS_LIBVHDI_ACCESS_FLAG_WRITE,
+        }
+    }
+}
+
+#[derive(PartialOrd, PartialEq, Debug, Clone)]
+#[repr(C)]
+pub enum DiskType {
+    Fixed = 2,
+    Dynamic = 3,
+    Differential = 4,
+}
+
+impl TryFrom<u32> for DiskType {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            2 => Ok(DiskType::Fixed),
+            3 => Ok(DiskType::Dynamic),
+            4 => Ok(DiskType::Differential),
+            _ => Err(Error::UnknownDiskTypeEnumVariant(value)),
+        }
+    }
+}
+
+impl File {
+    /// Opens an image file by filename.
+    ///
+    /// The parent chain of a differencing disk is opened as well, looking up each parent by its
+    /// file name in the directory of its child.
+    pub fn open(filename: impl AsRef<str>, mode: AccessMode) -> Result<Self, Error> {
+        let mut handle = ptr::null_mut();
+
+        let c_string = CString::new(filename.as_ref()).map_err(Error::StringContainsNul)?;
+
+        let mut init_error = ptr::null_mut();
+
+        let retcode = unsafe { libvhdi_file_initialize(&mut handle as _, &mut init_error as _) };
+
+        if retcode != 1 {
+            return Err(Error::try_from(init_error)?);
+        }
+
+        let mut file = File::wrap_ptr(handle);
+
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libvhdi_file_open(
+                file.as_type_ref(),
+                c_string.as_ptr(),
+                mode.as_flag() as c_int,
+                &mut error as _,
+            )
+        } != 1
+        {
+            return Err(Error::try_from(error)?);
+        }
+
+        if file.get_disk_type()? == DiskType::Differential {
+            let parent = file.open_parent(Path::new(filename.as_ref()))?;
+            file.set_parent(parent)?;
+        }
+
+        Ok(file)
+    }
+
+    /// Opens an image file using a libbfio handle.
+    ///
+    /// The parent of a differencing disk cannot be located from a handle, and has to be provided
+    /// with `set_parent` before reading.
+    pub fn open_file_object(file_handle: &Handle) -> Result<Self, Error> {
+        let mut handle = ptr::null_mut();
+        let mut init_error = ptr::null_mut();
+
+        let retcode = unsafe { libvhdi_file_initialize(&mut handle as _, &mut init_error as _) };
+
+        if retcode != 1 {
+            return Err(Error::try_from(init_error)?);
+        }
+
+        let file = File::wrap_ptr(handle);
+
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libvhdi_file_open_file_io_handle(
+                file.as_type_ref(),
+                file_handle.as_type_ref(),
+                AccessMode::Read.as_flag() as c_int,
+                &mut error as _,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(file)
+        }
+    }
+
+    fn open_parent(&self, path: &Path) -> Result<File, Error> {
+        let parent_filename = self
+            .get_parent_filename()?
+            .ok_or_else(|| Error::Other("Differencing disk has no parent filename".to_owned()))?;
+
+        // The stored name is usually an absolute Windows path on the machine that created it.
+        let parent_name = parent_filename
+            .rsplit(|c| c == '\\' || c == '/')
+            .next()
+            .unwrap_or(&parent_filename);
+
+        let parent_path = path.with_file_name(parent_name);
+        let parent_path = parent_path.to_str().ok_or_else(|| {
+            Error::Other(format!("Parent path {:?} is not valid UTF-8", parent_path))
+        })?;
+
+        File::open(parent_path, AccessMode::Read)
+    }
+
+    /// Sets the parent image of a differencing disk.
+    pub fn set_parent(&mut self, parent: File) -> Result<(), Error> {
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libvhdi_file_set_parent_file(self.as_type_ref(), parent.as_type_ref(), &mut error)
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            self.parent = Some(Box::new(parent));
+            Ok(())
+        }
+    }
+
+    /// Retrieves the parent image of a differencing disk, if it was set.
+    pub fn get_parent(&self) -> Option<&File> {
+        self.parent.as_deref()
+    }
+
+    /// Retrieves the format version as a `(major, minor)` pair.
+    pub fn get_format_version(&self) -> Result<(u16, u16), Error> {
+        let mut major_version = 0_u16;
+        let mut minor_version = 0_u16;
+        let mut error = ptr::null_mut();
+
+        if unsafe {
+            libvhdi_file_get_format_version(
+                self.as_type_ref(),
+                &mut major_version,
+                &mut minor_version,
+                &mut error,
+            )
+        } != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok((major_version, minor_version))
+        }
+    }
+
+    /// Retrieves the disk type.
+    pub fn get_disk_type(&self) -> Result<DiskType, Error> {
+        let mut type_as_num = 0_u32;
+        let mut error = ptr::null_mut();
+
+        if unsafe { libvhdi_file_get_disk_type(self.as_type_ref(), &mut type_as_num, &mut error) }
+            != 1
+        {
+            Err(Error::try_from(error)?)
+        } else {
+            Ok(DiskType::try_from(type_as_num)?)
+        }
+    }
+
+    /// Retrieves the size of the contained disk.
+    pub fn get_media_size(&self) -> Result<u64, Error> {
+        get_u64_field!(self, libvhdi_file_get_media_size)
+    }
+
+    /// Retrieves the parent filename of a differencing disk.
+    /// Returns `None` if the disk has no parent.
+    pub fn get_parent_filename(&self) -> Result<Option<String>, Error> {
+        let mut name_size = 0_usize;
+        let mut error = ptr::null_mut();
+
+        match unsafe {
+            libvhdi_file_get_utf8_parent_filename_size(
+                self.as_type_ref(),
+                &mut name_size,
+                &mut error,
+            )
+        } {
+            1 => {}
+            0 => return Ok(None),
+            _ => return Err(Error::try_from(error)?),
+        }
+
+        get_sized_utf8_string!(
+            self,
+            libvhdi_file_get_utf8_parent_filename_size,
+            libvhdi_file_get_utf8_parent_filename
+        )
+        .map(Some)
+    }
+}
+
+fn io_error(error: LibvhdiErrorRefMut) -> io::Error {
+    match Error::try_from(error) {
+        Ok(e) => io::Error::new(io::ErrorKind::Other, format!("{}", e)),
+        Err(_e) => io::Error::new(
+            io::ErrorKind::Other,
+            "error while getting error information",
+        ),
+    }
+}
+
+impl Read for File {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut error = ptr::null_mut();
+
+        let read_count = unsafe {
+            libvhdi_file_read_buffer(
+                self.as_type_ref(),
+                buf.as_mut_ptr() as *mut c_void,
+                buf.len(),
+                &mut error,
+            )
+        };
+
+        if read_count < 0 {
+            Err(io_error(error))
+        } else {
+            Ok(read_count as usize)
+        }
+    }
+}
+
+impl Seek for File {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (offset, whence) = match pos {
+            SeekFrom::Start(p) => (p as i64, libc_seek::SEEK_SET),
+            SeekFrom::Current(p) => (p, libc_seek::SEEK_CUR),
+            SeekFrom::End(p) => (p, libc_seek::SEEK_END),
+        };
+
+        let mut error = ptr::null_mut();
+
+        let offset =
+            unsafe { libvhdi_file_seek_offset(self.as_type_ref(), offset, whence, &mut error) };
+
+        if offset < 0 {
+            Err(io_error(error))
+        } else {
+            Ok(offset as u64)
+        }
+    }
+}
+
+/// `whence` values of `libvhdi_file_seek_offset`, as defined by the C library.
+mod libc_seek {
+    use std::os::raw::c_int;
+
+    pub const SEEK_SET: c_int = 0;
+    pub const SEEK_CUR: c_int = 1;
+    pub const SEEK_END: c_int = 2;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    const MEDIA_SIZE: u64 = 1024 * 1024;
+
+    #[test]
+    fn test_opens_file_io_works() {
+        let handle = sample_image_io_handle().unwrap();
+        let file = File::open_file_object(&handle).unwrap();
+
+        assert_eq!(file.get_media_size().unwrap(), MEDIA_SIZE);
+    }
+
+    #[test]
+    fn test_fixed_image_metadata() {
+        let file = sample_image().unwrap();
+
+        assert_eq!(file.get_media_size().unwrap(), MEDIA_SIZE);
+        assert_eq!(file.get_format_version().unwrap(), (1, 0));
+        assert_eq!(file.get_disk_type().unwrap(), DiskType::Fixed);
+        assert!(file.get_parent_filename().unwrap().is_none());
+        assert!(file.get_parent().is_none());
+    }
+
+    #[test]
+    fn test_read_and_seek() {
+        let mut file = sample_image().unwrap();
+        let mut buf = [0; 29];
+
+        file.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"libvhdi-rs fixed disk fixture");
+
+        assert_eq!(file.seek(SeekFrom::End(-4)).unwrap(), MEDIA_SIZE - 4);
+
+        let mut rest = vec![];
+        file.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, vec![0; 4]);
+    }
+
+    #[test]
+    fn test_opens_parent_of_differencing_disk() {
+        // A differencing disk of `fixed.vhd` without any blocks of its own.
+        let path = sample_image_path().replace("fixed.vhd", "differencing.vhd");
+        let mut file = File::open(&path, AccessMode::Read).unwrap();
+
+        assert_eq!(file.get_disk_type().unwrap(), DiskType::Differential);
+        assert_eq!(
+            file.get_parent_filename().unwrap().as_deref(),
+            Some("fixed.vhd")
+        );
+        assert_eq!(
+            file.get_parent().unwrap().get_media_size().unwrap(),
+            MEDIA_SIZE
+        );
+
+        let mut buf = [0; 29];
+        file.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"libvhdi-rs fixed disk fixture");
+    }
+}
@@ -0,0 +1,37 @@
+use crate::error::Error;
+use crate::file::{AccessMode, File};
+use env_logger;
+use lazy_static::lazy_static;
+use libbfio_rs::handle::{Handle, LibbfioAccessFlags};
+use std::path::PathBuf;
+
+lazy_static! {
+    static ref LOGGER_INIT: () = {
+        env_logger::init();
+    };
+}
+
+pub fn sample_image_path() -> String {
+    let this_file = file!();
+    let sample = PathBuf::from(this_file)
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("examples")
+        .join("fixed.vhd");
+
+    sample.to_str().unwrap().to_string()
+}
+
+pub fn sample_image_io_handle() -> Result<Handle, Error> {
+    let image_path = sample_image_path();
+    Ok(Handle::open_file(image_path, LibbfioAccessFlags::Read).expect("libbfio failed"))
+}
+
+pub fn sample_image() -> Result<File, Error> {
+    let image_path = sample_image_path();
+    File::open(&image_path, AccessMode::Read)
+}
@@ -38,9 +38,9 @@ class SemVer:
 
 
 LIBYAL_LIBRARIES_DIRECTORIES = ["common", "common-build", "libcerror-sys", "libbfio-sys", "libbfio", "libfsntfs-sys",
-                                "libfsntfs"]
+                                "libfsntfs", "libscca-sys", "libscca", "libevtx-sys", "libevtx", "libregf-sys", "libregf", "libesedb-sys", "libesedb", "libvhdi-sys", "libvhdi", "libqcow-sys", "libqcow"]
 LIBYAL_LIBRARIES_PACKAGES = ["libyal-rs-common", "libyal-rs-common-build", "libcerror-sys", "libbfio-sys",
-                             "libfsntfs-sys", "libbfio-rs", "libfsntfs-rs"]
+                             "libfsntfs-sys", "libbfio-rs", "libfsntfs-rs", "libscca-sys", "libscca-rs", "libevtx-sys", "libevtx-rs", "libregf-sys", "libregf-rs", "libesedb-sys", "libesedb-rs", "libvhdi-sys", "libvhdi-rs", "libqcow-sys", "libqcow-rs"]
 
 
 def main():